@@ -1 +1 @@
-pub mod mos;
+pub mod mos;
@@ -1,61 +1,105 @@
 pub mod mos;
-use crate::mos::CPU;
-use crate::mos::Opcodes;
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
 use std::process;
 
 fn main() {
-    
-    let mut cpu = mos::build_cpu();
-    let mut mem = mos::build_memory();
 
-    println!("before memory set ...");
-    println!("[0x0001] : {:#04x}", mem.memory[0x0001]);
-    println!("[0x0002] : {:#04x}", mem.memory[0x0002]);
-    println!("[0x0003] : {:#04x}", mem.memory[0x0003]);
+    // rust6502 asm <source> <output> : assemble to a flat binary instead of starting the monitor
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() == 4 && args[1] == "asm" {
+        let assembly = mos::asm::assemble_file(&args[2]).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+        fs::write(&args[3], assembly.image()).unwrap_or_else(|err| {
+            eprintln!("cannot write {}: {}", args[3], err);
+            process::exit(1);
+        });
+        println!("origin: {:#06x}", assembly.origin());
+        print!("{}", assembly.symbol_file());
+        return;
+    }
 
-    mem.memory[0x0002] = 0x0F;
+    // rust6502 disasm <binary> <origin> [symbols] : list a binary loaded at a hex origin
+    if (args.len() == 4 || args.len() == 5) && args[1] == "disasm" {
+        let data: Vec<u8> = fs::read(&args[2]).unwrap_or_else(|err| {
+            eprintln!("cannot read {}: {}", args[2], err);
+            process::exit(1);
+        });
+        let origin: u16 = u16::from_str_radix(args[3].trim_start_matches('$').trim_start_matches("0x"), 16).unwrap_or_else(|_| {
+            eprintln!("bad origin {}", args[3]);
+            process::exit(1);
+        });
+        if data.is_empty() || origin as usize + data.len() > 0x10000 {
+            eprintln!("{} does not fit at {:#06x}", args[2], origin);
+            process::exit(1);
+        }
+        let mut disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        if args.len() == 5 {
+            disassembler.load_symbol_file(&args[4]).unwrap_or_else(|err| {
+                eprintln!("cannot read {}: {}", args[4], err);
+                process::exit(1);
+            });
+        }
+        let mut mem = mos::build_memory();
+        mem.memory[origin as usize..origin as usize + data.len()].copy_from_slice(&data);
+        print!("{}", disassembler.listing(&mem, origin, (origin as usize + data.len() - 1) as u16));
+        return;
+    }
 
-    println!("after memory set ...");
-    println!("[0x0001] : {:#04x}", mem.memory[0x0001]);
-    println!("[0x0002] : {:#04x}", mem.memory[0x0002]);
-    println!("[0x0003] : {:#04x}", mem.memory[0x0003]);
+    // rust6502 trace <binary> <origin> <start> <count> : nestest.log style trace of count instructions
+    if args.len() == 6 && args[1] == "trace" {
+        let hex = |text: &str| u16::from_str_radix(text.trim_start_matches('$').trim_start_matches("0x"), 16).unwrap_or_else(|_| {
+            eprintln!("bad address {}", text);
+            process::exit(1);
+        });
+        let data: Vec<u8> = fs::read(&args[2]).unwrap_or_else(|err| {
+            eprintln!("cannot read {}: {}", args[2], err);
+            process::exit(1);
+        });
+        let origin: u16 = hex(&args[3]);
+        let count: usize = args[5].parse().unwrap_or_else(|_| {
+            eprintln!("bad instruction count {}", args[5]);
+            process::exit(1);
+        });
+        if data.is_empty() || origin as usize + data.len() > 0x10000 {
+            eprintln!("{} does not fit at {:#06x}", args[2], origin);
+            process::exit(1);
+        }
+        let mut mem = mos::build_memory();
+        mem.memory[origin as usize..origin as usize + data.len()].copy_from_slice(&data);
 
+        // the state a reset leaves behind, as at the top of nestest.log
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        cpu.pc = hex(&args[4]);
+        cpu.sp = 0xFD;
+        cpu.set_processor_status(0x24);
+        let mut tracer = mos::trace::build_tracer(mos::Variant::Nmos6502, std::io::stdout().lock());
+        tracer.cycles = 7;
+        if let Err(err) = tracer.run(&mut cpu, &mut mem, count) {
+            eprintln!("stopped at {:#06x}: {}", cpu.pc, err);
+            process::exit(1);
+        }
+        return;
+    }
 
-    // basic cpu tests
-
-    println!("CPU DEBUGS");
-
-    println!("value test (LDA_IMMEDIATE 0xA9): {:#02x}", CPU::LDA_IMMEDIATE);
-
-    println!("program counter: {:#04x}", cpu.pc);
-    println!("stack pointer: {:#04x}", cpu.sp);
-    println!("reg A: {}", cpu.r_a);
-    println!("------ executing ...");
-
-    // load reg immediate
-
-    mem.memory[0xFFFC] = CPU::LDA_IMMEDIATE;
-    mem.memory[0xFFFD] = 0x84;
-    let cycles_init = 2;
-    let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-        println!("invalid memory and instruction situation {} ...", err);
-        println!{"init cycles: {}", cycles_init};
-        println!("program counter: {:#04x}", cpu.pc);
-        println!("stack pointer: {:#04x}", cpu.sp);
-        println!("reg A: {:#04x}", cpu.r_a);
-        println!("memory stack: ");
-        println!("[0xFFFB] : {:#04x}", mem.memory[0xFFFB]);
-        println!("[0xFFFC] : {:#04x}", mem.memory[0xFFFC]);
-        println!("[0xFFFD] : {:#04x}", mem.memory[0xFFFD]);
-        println!("[0xFFFE] : {:#04x}", mem.memory[0xFFFE]);
-        println!("[0xFFFF] : {:#04x}", mem.memory[0xFFFF]);
-        process::exit(1);
-    });
-
-    println!("init cycles: {}", cycles_init);
-    println!("cycles: {}", cycles);
-    println!("program counter: {:#04x}", cpu.pc);
-    println!("stack pointer: {:#04x}", cpu.sp);
-    println!("reg A: {:#04x}", cpu.r_a);
-    
+    // rust6502 [binary origin] : machine language monitor, optionally with a binary loaded
+    let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+    if args.len() == 3 {
+        print!("{}", monitor.command(&format!("l {} {}", args[1], args[2])));
+        monitor.cpu.pc = u16::from_str_radix(args[2].trim_start_matches('$').trim_start_matches("0x"), 16).unwrap_or(0);
+    }
+    println!("rust6502 monitor, ? for help");
+    let stdin = io::stdin();
+    while monitor.running {
+        print!("{}", monitor.prompt());
+        io::stdout().flush().unwrap_or(());
+        let mut line: String = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => break,   // end of input quits like x
+            Ok(_) => print!("{}", monitor.command(&line)),
+        }
+    }
 }
@@ -1,290 +1,2309 @@
-use std::error::Error;
-
-// core types
-type Byte = u8;                 // 1 byte: 0x00
-type Opcode = Byte;             // 1 byte: 0x00
-type Word = u16;                // 2 bytes: 0x0000
-
-// u32 is unsigned int
-// i32 is signed int
-
-static MAX_MEM: u32 = 1024 * 64;
-
-/* MEMORY */
-
-pub struct MEMORY {
-    pub memory: Vec<Byte>,
-}
-
-impl MEMORY {
-
-    pub fn write_word(&mut self, value: Word, address: Word, mut cycles: i32) -> Result<i32, Box<dyn Error>> {
-        let least_significant: Byte = (value & 0xFF) as Byte;
-        let most_significant: Byte = (value >> 8) as Byte;
-        self.memory[address as usize] = least_significant;
-        self.memory[(address + 1) as usize] = most_significant;
-        cycles -= 2; // write twice : 2 cycles
-        Ok(cycles)
-    }
-}
-
-pub fn build_memory() -> MEMORY {
-    MEMORY {
-        memory: vec![0; MAX_MEM as usize],
-    }
-}
-
-/* CPU */
-
-// as associated constant
-pub trait Opcodes {
-
-    // LDA
-    const LDA_IMMEDIATE: Opcode;
-    const LDA_ZERO_PAGE: Opcode;
-    const LDA_ZERO_PAGE_X: Opcode;
-    const LDA_ABSOLUTE: Opcode;
-    const LDA_ABSOLUTE_X: Opcode;
-    const LDA_ABSOLUTE_Y: Opcode;
-    const LDA_INDIRECT_X: Opcode;
-    const LDA_INDIRECT_Y: Opcode;
-    // LDX
-    const LDX_IMMEDIATE: Opcode;
-    const LDX_ZERO_PAGE: Opcode;
-    const LDX_ZERO_PAGE_Y: Opcode;
-    const LDX_ABSOLUTE: Opcode;
-    const LDX_ABSOLUTE_Y: Opcode;
-    // LDY
-    const LDY_IMMEDIATE: Opcode;
-    const LDY_ZERO_PAGE: Opcode;
-    const LDY_ZERO_PAGE_X: Opcode;
-    const LDY_ABSOLUTE: Opcode;
-    const LDY_ABSOLUTE_X: Opcode;
-    // JSR
-    const JSR_ABSOLUTE: Opcode;
-
-}
-
-pub struct CPU {
-
-    // counter & pointer
-    pub pc: Word,                   // program counter
-    pub sp: Word,                   // stack pointer
-
-    // registers
-    pub r_a: Byte,                  // A register
-    pub r_x: Byte,                  // X register
-    pub r_y: Byte,                  // Y register
-
-    // processor status bit fields
-    pub ps_carry: Byte,             // carry bit
-    pub ps_zero: Byte,              // zero bit
-    pub ps_interrupt: Byte,         // interrupt bit
-    pub ps_decimal: Byte,           // decimal mode bit
-    pub ps_break: Byte,             // break command bit
-    pub ps_overflow: Byte,          // overflow bit
-    pub ps_negative: Byte,          // negative value bit
-
-}
-
-impl Opcodes for CPU {
-    // LDA
-    const LDA_IMMEDIATE: Opcode = 0xA9;
-    const LDA_ZERO_PAGE: Opcode = 0xA5;
-    const LDA_ZERO_PAGE_X: Opcode = 0xB5;
-    const LDA_ABSOLUTE: Opcode = 0xAD;
-    const LDA_ABSOLUTE_X: Opcode = 0xBD;
-    const LDA_ABSOLUTE_Y: Opcode = 0xB9;
-    const LDA_INDIRECT_X: Opcode = 0xA1;
-    const LDA_INDIRECT_Y: Opcode = 0xB1;
-    // LDX
-    const LDX_IMMEDIATE: Opcode = 0xA2;
-    const LDX_ZERO_PAGE: Opcode = 0xA6;
-    const LDX_ZERO_PAGE_Y: Opcode = 0xB6;
-    const LDX_ABSOLUTE: Opcode = 0xAE;
-    const LDX_ABSOLUTE_Y: Opcode = 0xBE;
-    // LDY
-    const LDY_IMMEDIATE: Opcode = 0xA0;
-    const LDY_ZERO_PAGE: Opcode = 0xA4;
-    const LDY_ZERO_PAGE_X: Opcode = 0xB4;
-    const LDY_ABSOLUTE: Opcode = 0xAC;
-    const LDY_ABSOLUTE_X: Opcode = 0xBC;
-    // JSR
-    const JSR_ABSOLUTE: Opcode = 0x20;
-}
-
-impl CPU {
-
-    fn reset_cpu(&mut self) {
-        self.pc = 0xFFFC;
-        self.sp = 0x00FF;
-        self.r_a = 0;
-        self.r_x = 0;
-        self.r_y = 0;
-        self.ps_carry = 0;
-        self.ps_zero = 0;
-        self.ps_interrupt = 0;
-        self.ps_decimal = 0;
-        self.ps_break = 0;
-        self.ps_overflow = 0;
-        self.ps_negative = 0;
-    }
-
-    fn fetch_byte(&mut self, mem: &MEMORY, mut cycles: i32) -> (Byte, i32) {
-        let instruction: Byte = mem.memory[self.pc as usize];
-        self.pc += 1;
-        cycles -= 1;
-        return (instruction, cycles)
-    }
-
-    fn read_byte(address: Word, mem: &MEMORY, mut cycles: i32) -> (Byte, i32){
-        let byte: Byte = mem.memory[address as usize];
-        cycles -= 1;
-        return (byte, cycles)
-    }
-
-    fn read_byte_zero_page(address: Byte, mem: &MEMORY, mut cycles: i32) -> (Byte, i32){
-        let byte: Byte = mem.memory[address as usize];
-        cycles -= 1;
-        return (byte, cycles)
-    }
-
-    fn read_word(address: Word, mem: &MEMORY, cycles: i32) -> (Word, i32) {
-        let (lo_byte, cycles_min_one): (Byte, i32) = CPU::read_byte(address, mem, cycles);
-        let (hi_byte, cycles_min_two): (Byte, i32) = CPU::read_byte(address + 1, mem, cycles_min_one);
-        let lo_byte_word: Word = lo_byte.into();
-        let hi_byte_word: Word = <u8 as Into<Word>>::into(hi_byte) << 8;
-        let full_word: Word = lo_byte_word | hi_byte_word;
-        return (full_word, cycles_min_two)
-    }
-
-    fn fetch_word(&mut self, mem: &MEMORY, mut cycles: i32) -> (Word, i32) {
-        let lo_byte: Word = mem.memory[self.pc as usize].into();
-        self.pc += 1; // goto next memory addr for hi byte
-        let hi_byte: Word = <u8 as Into<Word>>::into(mem.memory[self.pc as usize]) << 8;
-        self.pc += 1;
-        cycles -= 2; // fetch twice: 2 cycles
-        let full_word: Word = lo_byte | hi_byte;
-        return (full_word, cycles)
-    }
-
-    pub fn execute(&mut self, mut cycles: i32, mem: &MEMORY) -> Result<i32, &'static str>{
-
-        let requested_cycles: i32 = cycles;
-
-
-        while cycles > 0 {
-
-            let (instruction, mut cycles): (Opcode, i32) = CPU::fetch_byte(self, mem, cycles);
-            
-            match instruction {
-
-                CPU::LDA_IMMEDIATE => {
-                    let (value, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    self.r_a = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_ZERO_PAGE => {
-                    let (zero_page_address, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte_zero_page(zero_page_address, mem, cycles);
-                    self.r_a = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_ZERO_PAGE_X => {
-                    let (zero_page_address, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    let zero_page_addr_x_word: Word = (zero_page_address as Word) + (self.r_x as Word);
-                    let zero_page_addr_x: Byte = zero_page_addr_x_word as Byte;
-                    cycles -= 1;
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte_zero_page(zero_page_addr_x, mem, cycles);
-                    self.r_a = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_ABSOLUTE => {
-                    let (absolute_address, mut cycles): (Word, i32) = CPU::fetch_word(self, mem, cycles);
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte(absolute_address, mem, cycles);
-                    self.r_a = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_ABSOLUTE_X => {
-                    let (absolute_address, mut cycles): (Word, i32) = CPU::fetch_word(self, mem, cycles);
-                    let absolute_addr_x: Word = absolute_address + (self.r_x as Word);
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte(absolute_addr_x, mem, cycles);
-                    self.r_a = value;
-                    if (absolute_addr_x - absolute_address) >= 0xFF {
-                        cycles -= 1;
-                    }
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_ABSOLUTE_Y => {
-                    let (absolute_address, mut cycles): (Word, i32) = CPU::fetch_word(self, mem, cycles);
-                    let absolute_addr_y: Word = absolute_address + (self.r_y as Word);
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte(absolute_addr_y, mem, cycles);
-                    self.r_a = value;
-                    if (absolute_addr_y - absolute_address) >= 0xFF {
-                        cycles -= 1;
-                    }
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_INDIRECT_X => {
-                    let (zero_page_address, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    let zero_page_addr_x: Word = (zero_page_address + self.r_x) as Word;
-                    cycles -= 1;
-                    let (effective_address, mut cycles): (Word, i32) = CPU::read_word(zero_page_addr_x, mem, cycles);
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte(effective_address, mem, cycles);
-                    self.r_a = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDA_INDIRECT_Y => {
-                    let (zero_page_address, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    let (effective_address, mut cycles): (Word, i32) = CPU::read_word(zero_page_address as Word, mem, cycles);
-                    let effective_addr_y = effective_address + (self.r_y as Word);
-                    let (value, mut cycles): (Byte, i32) = CPU::read_byte(effective_addr_y, mem, cycles);
-                    self.r_a = value;
-                    if (effective_addr_y - effective_address) >= 0xFF {
-                        cycles -= 1;
-                    }
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::JSR_ABSOLUTE => {
-                    let (subroutine_addr, mut cycles): (Word, i32) = CPU::fetch_word(self, mem, cycles);
-                    // finish this later
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDX_IMMEDIATE => {
-                    let (value, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    self.r_x = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                CPU::LDY_IMMEDIATE => {
-                    let (value, mut cycles): (Byte, i32) = CPU::fetch_byte(self, mem, cycles);
-                    self.r_y = value;
-                    return Ok(requested_cycles - cycles);
-                }
-                _ => {
-                    println!("bad instruction {} ...", instruction);
-                    return Err("invalid instruction error");
-                }
-            }
-        }
-
-        return Ok(requested_cycles - cycles);
-
-    }
-}
-
-pub fn build_cpu() -> CPU {
-    CPU {
-        pc: 0xFFFC,
-        sp: 0x00FF,
-        r_a: 0,
-        r_x: 0,
-        r_y: 0,
-        ps_carry: 0,
-        ps_zero: 0,
-        ps_interrupt: 0,
-        ps_decimal: 0,
-        ps_break: 0,
-        ps_overflow: 0,
-        ps_negative: 0,
-    }
-}
+use std::error::Error;
+use std::fmt;
+
+pub mod asm;
+pub mod banked;
+pub mod debug;
+pub mod disasm;
+pub mod memory_map;
+pub mod monitor;
+pub mod opcode_table;
+pub mod trace;
+mod cmos;
+mod tick;
+mod undocumented;
+pub mod w65c816;
+pub use banked::{build_8k_x4, build_banked_memory, build_switchable_16k, BankSource, BankedMemory};
+pub use memory_map::{build_memory_map, MemoryMap, MemoryMapBuilder};
+pub use w65c816::{build_cpu_65816, build_long_memory, LongBus, LongMemory, Opcodes65816, CPU65816};
+
+// core types
+type Byte = u8;                 // 1 byte: 0x00
+type Opcode = Byte;             // 1 byte: 0x00
+type Word = u16;                // 2 bytes: 0x0000
+
+// u32 is unsigned int
+// i32 is signed int
+
+static MAX_MEM: u32 = 1024 * 64;
+static STACK_PAGE: Word = 0x0100;
+
+// interrupt vectors at the top of memory
+pub static NMI_VECTOR: Word = 0xFFFA;
+pub static RESET_VECTOR: Word = 0xFFFC;
+pub static IRQ_VECTOR: Word = 0xFFFE;
+
+/* MEMORY */
+
+pub struct MEMORY {
+    pub memory: Vec<Byte>,
+}
+
+impl MEMORY {
+
+    pub fn write_word(&mut self, value: Word, address: Word, mut cycles: i32) -> i32 {
+        let least_significant: Byte = (value & 0xFF) as Byte;
+        let most_significant: Byte = (value >> 8) as Byte;
+        self.memory[address as usize] = least_significant;
+        self.memory[address.wrapping_add(1) as usize] = most_significant;
+        cycles -= 2; // write twice : 2 cycles
+        cycles
+    }
+}
+
+/* BUS */
+
+// everything the cpu can see lives behind the bus: RAM, ROM and memory mapped devices
+pub trait Bus {
+    // a cpu read, devices may react to it (clear a latch, advance a fifo, ...)
+    fn read(&mut self, address: Word) -> Byte;
+    fn write(&mut self, address: Word, value: Byte);
+    // side-effect-free read for debuggers and disassemblers
+    fn peek(&self, address: Word) -> Byte;
+    // address of an access the bus could not serve since the last call, if any
+    fn take_fault(&mut self) -> Option<Word> {
+        None
+    }
+}
+
+// MEMORY is the default bus: 64 KiB of flat RAM
+impl Bus for MEMORY {
+    fn read(&mut self, address: Word) -> Byte {
+        self.memory[address as usize]
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        self.memory[address as usize] = value;
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        self.memory[address as usize]
+    }
+}
+
+pub fn build_memory() -> MEMORY {
+    MEMORY {
+        memory: vec![0; MAX_MEM as usize],
+    }
+}
+
+/* ERRORS */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmuError {
+    IllegalOpcode { address: Word, opcode: Opcode },   // opcode the cpu does not implement
+    Jammed { address: Word, opcode: Opcode },          // JAM / KIL opcode locked up the cpu
+    CycleBudgetExhausted { cycles: i32 },              // ran out of cycles before reaching the goal
+    BusFault { address: Word },                        // access to an unmapped region
+    StackOverflow,                                     // push wrapped sp past 0x00 (strict mode)
+    StackUnderflow,                                    // pull wrapped sp past 0xFF (strict mode)
+    Stopped { address: Word },                         // WDC STP halted the clock until a reset
+}
+
+impl fmt::Display for EmuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EmuError::IllegalOpcode { address, opcode } => write!(f, "illegal opcode {:#04x} at {:#06x}", opcode, address),
+            EmuError::Jammed { address, opcode } => write!(f, "cpu jammed by opcode {:#04x} at {:#06x}", opcode, address),
+            EmuError::CycleBudgetExhausted { cycles } => write!(f, "cycle budget of {} cycles exhausted", cycles),
+            EmuError::BusFault { address } => write!(f, "bus fault at unmapped address {:#06x}", address),
+            EmuError::StackOverflow => write!(f, "stack overflow: sp wrapped below 0x00"),
+            EmuError::StackUnderflow => write!(f, "stack underflow: sp wrapped above 0xFF"),
+            EmuError::Stopped { address } => write!(f, "cpu stopped by STP at {:#06x}", address),
+        }
+    }
+}
+
+impl Error for EmuError {}
+
+/* CPU */
+
+// as associated constant
+pub trait Opcodes {
+
+    // LDA
+    const LDA_IMMEDIATE: Opcode;
+    const LDA_ZERO_PAGE: Opcode;
+    const LDA_ZERO_PAGE_X: Opcode;
+    const LDA_ABSOLUTE: Opcode;
+    const LDA_ABSOLUTE_X: Opcode;
+    const LDA_ABSOLUTE_Y: Opcode;
+    const LDA_INDIRECT_X: Opcode;
+    const LDA_INDIRECT_Y: Opcode;
+    // LDX
+    const LDX_IMMEDIATE: Opcode;
+    const LDX_ZERO_PAGE: Opcode;
+    const LDX_ZERO_PAGE_Y: Opcode;
+    const LDX_ABSOLUTE: Opcode;
+    const LDX_ABSOLUTE_Y: Opcode;
+    // LDY
+    const LDY_IMMEDIATE: Opcode;
+    const LDY_ZERO_PAGE: Opcode;
+    const LDY_ZERO_PAGE_X: Opcode;
+    const LDY_ABSOLUTE: Opcode;
+    const LDY_ABSOLUTE_X: Opcode;
+    // STA
+    const STA_ZERO_PAGE: Opcode;
+    const STA_ZERO_PAGE_X: Opcode;
+    const STA_ABSOLUTE: Opcode;
+    const STA_ABSOLUTE_X: Opcode;
+    const STA_ABSOLUTE_Y: Opcode;
+    const STA_INDIRECT_X: Opcode;
+    const STA_INDIRECT_Y: Opcode;
+    // STX
+    const STX_ZERO_PAGE: Opcode;
+    const STX_ZERO_PAGE_Y: Opcode;
+    const STX_ABSOLUTE: Opcode;
+    // STY
+    const STY_ZERO_PAGE: Opcode;
+    const STY_ZERO_PAGE_X: Opcode;
+    const STY_ABSOLUTE: Opcode;
+    // ADC
+    const ADC_IMMEDIATE: Opcode;
+    const ADC_ZERO_PAGE: Opcode;
+    const ADC_ZERO_PAGE_X: Opcode;
+    const ADC_ABSOLUTE: Opcode;
+    const ADC_ABSOLUTE_X: Opcode;
+    const ADC_ABSOLUTE_Y: Opcode;
+    const ADC_INDIRECT_X: Opcode;
+    const ADC_INDIRECT_Y: Opcode;
+    // SBC
+    const SBC_IMMEDIATE: Opcode;
+    const SBC_ZERO_PAGE: Opcode;
+    const SBC_ZERO_PAGE_X: Opcode;
+    const SBC_ABSOLUTE: Opcode;
+    const SBC_ABSOLUTE_X: Opcode;
+    const SBC_ABSOLUTE_Y: Opcode;
+    const SBC_INDIRECT_X: Opcode;
+    const SBC_INDIRECT_Y: Opcode;
+    // AND
+    const AND_IMMEDIATE: Opcode;
+    const AND_ZERO_PAGE: Opcode;
+    const AND_ZERO_PAGE_X: Opcode;
+    const AND_ABSOLUTE: Opcode;
+    const AND_ABSOLUTE_X: Opcode;
+    const AND_ABSOLUTE_Y: Opcode;
+    const AND_INDIRECT_X: Opcode;
+    const AND_INDIRECT_Y: Opcode;
+    // ORA
+    const ORA_IMMEDIATE: Opcode;
+    const ORA_ZERO_PAGE: Opcode;
+    const ORA_ZERO_PAGE_X: Opcode;
+    const ORA_ABSOLUTE: Opcode;
+    const ORA_ABSOLUTE_X: Opcode;
+    const ORA_ABSOLUTE_Y: Opcode;
+    const ORA_INDIRECT_X: Opcode;
+    const ORA_INDIRECT_Y: Opcode;
+    // EOR
+    const EOR_IMMEDIATE: Opcode;
+    const EOR_ZERO_PAGE: Opcode;
+    const EOR_ZERO_PAGE_X: Opcode;
+    const EOR_ABSOLUTE: Opcode;
+    const EOR_ABSOLUTE_X: Opcode;
+    const EOR_ABSOLUTE_Y: Opcode;
+    const EOR_INDIRECT_X: Opcode;
+    const EOR_INDIRECT_Y: Opcode;
+    // BIT
+    const BIT_ZERO_PAGE: Opcode;
+    const BIT_ABSOLUTE: Opcode;
+    // CMP
+    const CMP_IMMEDIATE: Opcode;
+    const CMP_ZERO_PAGE: Opcode;
+    const CMP_ZERO_PAGE_X: Opcode;
+    const CMP_ABSOLUTE: Opcode;
+    const CMP_ABSOLUTE_X: Opcode;
+    const CMP_ABSOLUTE_Y: Opcode;
+    const CMP_INDIRECT_X: Opcode;
+    const CMP_INDIRECT_Y: Opcode;
+    // CPX
+    const CPX_IMMEDIATE: Opcode;
+    const CPX_ZERO_PAGE: Opcode;
+    const CPX_ABSOLUTE: Opcode;
+    // CPY
+    const CPY_IMMEDIATE: Opcode;
+    const CPY_ZERO_PAGE: Opcode;
+    const CPY_ABSOLUTE: Opcode;
+    // ASL
+    const ASL_ACCUMULATOR: Opcode;
+    const ASL_ZERO_PAGE: Opcode;
+    const ASL_ZERO_PAGE_X: Opcode;
+    const ASL_ABSOLUTE: Opcode;
+    const ASL_ABSOLUTE_X: Opcode;
+    // LSR
+    const LSR_ACCUMULATOR: Opcode;
+    const LSR_ZERO_PAGE: Opcode;
+    const LSR_ZERO_PAGE_X: Opcode;
+    const LSR_ABSOLUTE: Opcode;
+    const LSR_ABSOLUTE_X: Opcode;
+    // ROL
+    const ROL_ACCUMULATOR: Opcode;
+    const ROL_ZERO_PAGE: Opcode;
+    const ROL_ZERO_PAGE_X: Opcode;
+    const ROL_ABSOLUTE: Opcode;
+    const ROL_ABSOLUTE_X: Opcode;
+    // ROR
+    const ROR_ACCUMULATOR: Opcode;
+    const ROR_ZERO_PAGE: Opcode;
+    const ROR_ZERO_PAGE_X: Opcode;
+    const ROR_ABSOLUTE: Opcode;
+    const ROR_ABSOLUTE_X: Opcode;
+    // INC
+    const INC_ZERO_PAGE: Opcode;
+    const INC_ZERO_PAGE_X: Opcode;
+    const INC_ABSOLUTE: Opcode;
+    const INC_ABSOLUTE_X: Opcode;
+    // DEC
+    const DEC_ZERO_PAGE: Opcode;
+    const DEC_ZERO_PAGE_X: Opcode;
+    const DEC_ABSOLUTE: Opcode;
+    const DEC_ABSOLUTE_X: Opcode;
+    // register increments / decrements
+    const INX_IMPLIED: Opcode;
+    const INY_IMPLIED: Opcode;
+    const DEX_IMPLIED: Opcode;
+    const DEY_IMPLIED: Opcode;
+    // register transfers
+    const TAX_IMPLIED: Opcode;
+    const TAY_IMPLIED: Opcode;
+    const TXA_IMPLIED: Opcode;
+    const TYA_IMPLIED: Opcode;
+    // flag instructions
+    const CLC_IMPLIED: Opcode;
+    const SEC_IMPLIED: Opcode;
+    const CLI_IMPLIED: Opcode;
+    const SEI_IMPLIED: Opcode;
+    const CLV_IMPLIED: Opcode;
+    const CLD_IMPLIED: Opcode;
+    const SED_IMPLIED: Opcode;
+    // NOP
+    const NOP_IMPLIED: Opcode;
+    // branches
+    const BCC_RELATIVE: Opcode;
+    const BCS_RELATIVE: Opcode;
+    const BEQ_RELATIVE: Opcode;
+    const BMI_RELATIVE: Opcode;
+    const BNE_RELATIVE: Opcode;
+    const BPL_RELATIVE: Opcode;
+    const BVC_RELATIVE: Opcode;
+    const BVS_RELATIVE: Opcode;
+    // JMP
+    const JMP_ABSOLUTE: Opcode;
+    const JMP_INDIRECT: Opcode;
+    // BRK / RTI
+    const BRK_IMPLIED: Opcode;
+    const RTI_IMPLIED: Opcode;
+    // JSR / RTS
+    const JSR_ABSOLUTE: Opcode;
+    const RTS_IMPLIED: Opcode;
+    // stack operations
+    const PHA_IMPLIED: Opcode;
+    const PLA_IMPLIED: Opcode;
+    const PHP_IMPLIED: Opcode;
+    const PLP_IMPLIED: Opcode;
+    const TSX_IMPLIED: Opcode;
+    const TXS_IMPLIED: Opcode;
+    // undocumented NMOS opcodes
+    // SLO
+    const SLO_ZERO_PAGE: Opcode;
+    const SLO_ZERO_PAGE_X: Opcode;
+    const SLO_ABSOLUTE: Opcode;
+    const SLO_ABSOLUTE_X: Opcode;
+    const SLO_ABSOLUTE_Y: Opcode;
+    const SLO_INDIRECT_X: Opcode;
+    const SLO_INDIRECT_Y: Opcode;
+    // RLA
+    const RLA_ZERO_PAGE: Opcode;
+    const RLA_ZERO_PAGE_X: Opcode;
+    const RLA_ABSOLUTE: Opcode;
+    const RLA_ABSOLUTE_X: Opcode;
+    const RLA_ABSOLUTE_Y: Opcode;
+    const RLA_INDIRECT_X: Opcode;
+    const RLA_INDIRECT_Y: Opcode;
+    // SRE
+    const SRE_ZERO_PAGE: Opcode;
+    const SRE_ZERO_PAGE_X: Opcode;
+    const SRE_ABSOLUTE: Opcode;
+    const SRE_ABSOLUTE_X: Opcode;
+    const SRE_ABSOLUTE_Y: Opcode;
+    const SRE_INDIRECT_X: Opcode;
+    const SRE_INDIRECT_Y: Opcode;
+    // RRA
+    const RRA_ZERO_PAGE: Opcode;
+    const RRA_ZERO_PAGE_X: Opcode;
+    const RRA_ABSOLUTE: Opcode;
+    const RRA_ABSOLUTE_X: Opcode;
+    const RRA_ABSOLUTE_Y: Opcode;
+    const RRA_INDIRECT_X: Opcode;
+    const RRA_INDIRECT_Y: Opcode;
+    // DCP
+    const DCP_ZERO_PAGE: Opcode;
+    const DCP_ZERO_PAGE_X: Opcode;
+    const DCP_ABSOLUTE: Opcode;
+    const DCP_ABSOLUTE_X: Opcode;
+    const DCP_ABSOLUTE_Y: Opcode;
+    const DCP_INDIRECT_X: Opcode;
+    const DCP_INDIRECT_Y: Opcode;
+    // ISC
+    const ISC_ZERO_PAGE: Opcode;
+    const ISC_ZERO_PAGE_X: Opcode;
+    const ISC_ABSOLUTE: Opcode;
+    const ISC_ABSOLUTE_X: Opcode;
+    const ISC_ABSOLUTE_Y: Opcode;
+    const ISC_INDIRECT_X: Opcode;
+    const ISC_INDIRECT_Y: Opcode;
+    // LAX
+    const LAX_ZERO_PAGE: Opcode;
+    const LAX_ZERO_PAGE_Y: Opcode;
+    const LAX_ABSOLUTE: Opcode;
+    const LAX_ABSOLUTE_Y: Opcode;
+    const LAX_INDIRECT_X: Opcode;
+    const LAX_INDIRECT_Y: Opcode;
+    // SAX
+    const SAX_ZERO_PAGE: Opcode;
+    const SAX_ZERO_PAGE_Y: Opcode;
+    const SAX_ABSOLUTE: Opcode;
+    const SAX_INDIRECT_X: Opcode;
+    // immediate logic
+    const ANC_IMMEDIATE: Opcode;
+    const ALR_IMMEDIATE: Opcode;
+    const ARR_IMMEDIATE: Opcode;
+    const SBX_IMMEDIATE: Opcode;
+    const USBC_IMMEDIATE: Opcode;
+    // unstable
+    const XAA_IMMEDIATE: Opcode;
+    const LXA_IMMEDIATE: Opcode;
+    const AHX_ABSOLUTE_Y: Opcode;
+    const AHX_INDIRECT_Y: Opcode;
+    const TAS_ABSOLUTE_Y: Opcode;
+    const SHY_ABSOLUTE_X: Opcode;
+    const SHX_ABSOLUTE_Y: Opcode;
+    const LAS_ABSOLUTE_Y: Opcode;
+    // multi-byte NOP
+    const NOP_IMMEDIATE: Opcode;
+    const NOP_ZERO_PAGE: Opcode;
+    const NOP_ZERO_PAGE_X: Opcode;
+    const NOP_ABSOLUTE: Opcode;
+    const NOP_ABSOLUTE_X: Opcode;
+    // JAM / KIL
+    const JAM_IMPLIED: Opcode;
+    // 65C02
+    const BRA_RELATIVE: Opcode;
+    const PHX_IMPLIED: Opcode;
+    const PLX_IMPLIED: Opcode;
+    const PHY_IMPLIED: Opcode;
+    const PLY_IMPLIED: Opcode;
+    const STZ_ZERO_PAGE: Opcode;
+    const STZ_ZERO_PAGE_X: Opcode;
+    const STZ_ABSOLUTE: Opcode;
+    const STZ_ABSOLUTE_X: Opcode;
+    const TRB_ZERO_PAGE: Opcode;
+    const TRB_ABSOLUTE: Opcode;
+    const TSB_ZERO_PAGE: Opcode;
+    const TSB_ABSOLUTE: Opcode;
+    const ORA_ZERO_PAGE_INDIRECT: Opcode;
+    const AND_ZERO_PAGE_INDIRECT: Opcode;
+    const EOR_ZERO_PAGE_INDIRECT: Opcode;
+    const ADC_ZERO_PAGE_INDIRECT: Opcode;
+    const STA_ZERO_PAGE_INDIRECT: Opcode;
+    const LDA_ZERO_PAGE_INDIRECT: Opcode;
+    const CMP_ZERO_PAGE_INDIRECT: Opcode;
+    const SBC_ZERO_PAGE_INDIRECT: Opcode;
+    const INC_ACCUMULATOR: Opcode;
+    const DEC_ACCUMULATOR: Opcode;
+    const BIT_IMMEDIATE: Opcode;
+    const BIT_ZERO_PAGE_X: Opcode;
+    const BIT_ABSOLUTE_X: Opcode;
+    const JMP_ABSOLUTE_X_INDIRECT: Opcode;
+    // Rockwell bit instructions
+    const RMB0_ZERO_PAGE: Opcode;
+    const RMB1_ZERO_PAGE: Opcode;
+    const RMB2_ZERO_PAGE: Opcode;
+    const RMB3_ZERO_PAGE: Opcode;
+    const RMB4_ZERO_PAGE: Opcode;
+    const RMB5_ZERO_PAGE: Opcode;
+    const RMB6_ZERO_PAGE: Opcode;
+    const RMB7_ZERO_PAGE: Opcode;
+    const SMB0_ZERO_PAGE: Opcode;
+    const SMB1_ZERO_PAGE: Opcode;
+    const SMB2_ZERO_PAGE: Opcode;
+    const SMB3_ZERO_PAGE: Opcode;
+    const SMB4_ZERO_PAGE: Opcode;
+    const SMB5_ZERO_PAGE: Opcode;
+    const SMB6_ZERO_PAGE: Opcode;
+    const SMB7_ZERO_PAGE: Opcode;
+    const BBR0_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR1_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR2_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR3_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR4_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR5_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR6_ZERO_PAGE_RELATIVE: Opcode;
+    const BBR7_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS0_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS1_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS2_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS3_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS4_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS5_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS6_ZERO_PAGE_RELATIVE: Opcode;
+    const BBS7_ZERO_PAGE_RELATIVE: Opcode;
+    // WDC
+    const WAI_IMPLIED: Opcode;
+    const STP_IMPLIED: Opcode;
+
+}
+
+// the members of the family differ in instruction set and a handful of quirks
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Variant {
+    Nmos6502,                       // original MOS part, undocumented opcodes and all
+    Ricoh2A03,                      // NES cpu: an NMOS core with decimal mode cut out
+    Cmos65C02,                      // new instructions and addressing mode, NMOS bugs fixed
+    Rockwell65C02,                  // 65C02 plus the RMB/SMB/BBR/BBS bit instructions
+    Wdc65C02,                       // Rockwell set plus WAI and STP
+}
+
+impl Variant {
+    pub fn is_cmos(self) -> bool {
+        matches!(self, Variant::Cmos65C02 | Variant::Rockwell65C02 | Variant::Wdc65C02)
+    }
+
+    pub fn has_bit_instructions(self) -> bool {
+        matches!(self, Variant::Rockwell65C02 | Variant::Wdc65C02)
+    }
+
+    pub fn has_wait_and_stop(self) -> bool {
+        self == Variant::Wdc65C02
+    }
+
+    pub fn has_decimal_mode(self) -> bool {
+        self != Variant::Ricoh2A03
+    }
+}
+
+#[derive(Clone)]
+pub struct CPU {
+
+    // chip
+    pub variant: Variant,           // which member of the family is emulated
+
+    // counter & pointer
+    pub pc: Word,                   // program counter
+    pub sp: Byte,                   // stack pointer (offset into page 0x01)
+
+    // registers
+    pub r_a: Byte,                  // A register
+    pub r_x: Byte,                  // X register
+    pub r_y: Byte,                  // Y register
+
+    // processor status bit fields
+    pub ps_carry: Byte,             // carry bit
+    pub ps_zero: Byte,              // zero bit
+    pub ps_interrupt: Byte,         // interrupt bit
+    pub ps_decimal: Byte,           // decimal mode bit
+    pub ps_break: Byte,             // break command bit
+    pub ps_overflow: Byte,          // overflow bit
+    pub ps_negative: Byte,          // negative value bit
+
+    // interrupt lines
+    pub irq_line: bool,             // IRQ is level triggered: serviced while held and I is clear
+    pub nmi_line: bool,             // NMI is edge triggered: only a false -> true transition counts
+    pub nmi_pending: bool,          // latched NMI edge waiting for the next instruction boundary
+
+    // emulation options
+    pub cycle_accurate: bool,       // perform the dummy reads of internal cycles on the bus
+    pub strict_stack: bool,         // report stack wraparound as an error instead of wrapping
+    pub undocumented_opcodes: bool, // run the undocumented NMOS opcodes instead of reporting them
+
+    // unstable undocumented opcodes: the results depend on the chip and even its temperature
+    pub xaa_magic: Byte,            // XAA: A = (A | magic) & X & #imm
+    pub lxa_magic: Byte,            // LXA: A = X = (A | magic) & #imm
+    pub sh_and_high_byte: bool,     // AHX/TAS/SHX/SHY AND the stored value with the address high byte + 1
+
+    // halt state
+    pub jammed: bool,               // a JAM opcode locked up the cpu, only a reset recovers it
+    pub waiting: bool,              // WAI: idle until an interrupt line is asserted
+    pub stopped: bool,              // STP: clock halted, only a reset recovers it
+
+    tick_state: Option<Box<tick::TickState>>,   // instruction part way through tick()
+
+}
+
+impl Opcodes for CPU {
+    // LDA
+    const LDA_IMMEDIATE: Opcode = 0xA9;
+    const LDA_ZERO_PAGE: Opcode = 0xA5;
+    const LDA_ZERO_PAGE_X: Opcode = 0xB5;
+    const LDA_ABSOLUTE: Opcode = 0xAD;
+    const LDA_ABSOLUTE_X: Opcode = 0xBD;
+    const LDA_ABSOLUTE_Y: Opcode = 0xB9;
+    const LDA_INDIRECT_X: Opcode = 0xA1;
+    const LDA_INDIRECT_Y: Opcode = 0xB1;
+    // LDX
+    const LDX_IMMEDIATE: Opcode = 0xA2;
+    const LDX_ZERO_PAGE: Opcode = 0xA6;
+    const LDX_ZERO_PAGE_Y: Opcode = 0xB6;
+    const LDX_ABSOLUTE: Opcode = 0xAE;
+    const LDX_ABSOLUTE_Y: Opcode = 0xBE;
+    // LDY
+    const LDY_IMMEDIATE: Opcode = 0xA0;
+    const LDY_ZERO_PAGE: Opcode = 0xA4;
+    const LDY_ZERO_PAGE_X: Opcode = 0xB4;
+    const LDY_ABSOLUTE: Opcode = 0xAC;
+    const LDY_ABSOLUTE_X: Opcode = 0xBC;
+    // STA
+    const STA_ZERO_PAGE: Opcode = 0x85;
+    const STA_ZERO_PAGE_X: Opcode = 0x95;
+    const STA_ABSOLUTE: Opcode = 0x8D;
+    const STA_ABSOLUTE_X: Opcode = 0x9D;
+    const STA_ABSOLUTE_Y: Opcode = 0x99;
+    const STA_INDIRECT_X: Opcode = 0x81;
+    const STA_INDIRECT_Y: Opcode = 0x91;
+    // STX
+    const STX_ZERO_PAGE: Opcode = 0x86;
+    const STX_ZERO_PAGE_Y: Opcode = 0x96;
+    const STX_ABSOLUTE: Opcode = 0x8E;
+    // STY
+    const STY_ZERO_PAGE: Opcode = 0x84;
+    const STY_ZERO_PAGE_X: Opcode = 0x94;
+    const STY_ABSOLUTE: Opcode = 0x8C;
+    // ADC
+    const ADC_IMMEDIATE: Opcode = 0x69;
+    const ADC_ZERO_PAGE: Opcode = 0x65;
+    const ADC_ZERO_PAGE_X: Opcode = 0x75;
+    const ADC_ABSOLUTE: Opcode = 0x6D;
+    const ADC_ABSOLUTE_X: Opcode = 0x7D;
+    const ADC_ABSOLUTE_Y: Opcode = 0x79;
+    const ADC_INDIRECT_X: Opcode = 0x61;
+    const ADC_INDIRECT_Y: Opcode = 0x71;
+    // SBC
+    const SBC_IMMEDIATE: Opcode = 0xE9;
+    const SBC_ZERO_PAGE: Opcode = 0xE5;
+    const SBC_ZERO_PAGE_X: Opcode = 0xF5;
+    const SBC_ABSOLUTE: Opcode = 0xED;
+    const SBC_ABSOLUTE_X: Opcode = 0xFD;
+    const SBC_ABSOLUTE_Y: Opcode = 0xF9;
+    const SBC_INDIRECT_X: Opcode = 0xE1;
+    const SBC_INDIRECT_Y: Opcode = 0xF1;
+    // AND
+    const AND_IMMEDIATE: Opcode = 0x29;
+    const AND_ZERO_PAGE: Opcode = 0x25;
+    const AND_ZERO_PAGE_X: Opcode = 0x35;
+    const AND_ABSOLUTE: Opcode = 0x2D;
+    const AND_ABSOLUTE_X: Opcode = 0x3D;
+    const AND_ABSOLUTE_Y: Opcode = 0x39;
+    const AND_INDIRECT_X: Opcode = 0x21;
+    const AND_INDIRECT_Y: Opcode = 0x31;
+    // ORA
+    const ORA_IMMEDIATE: Opcode = 0x09;
+    const ORA_ZERO_PAGE: Opcode = 0x05;
+    const ORA_ZERO_PAGE_X: Opcode = 0x15;
+    const ORA_ABSOLUTE: Opcode = 0x0D;
+    const ORA_ABSOLUTE_X: Opcode = 0x1D;
+    const ORA_ABSOLUTE_Y: Opcode = 0x19;
+    const ORA_INDIRECT_X: Opcode = 0x01;
+    const ORA_INDIRECT_Y: Opcode = 0x11;
+    // EOR
+    const EOR_IMMEDIATE: Opcode = 0x49;
+    const EOR_ZERO_PAGE: Opcode = 0x45;
+    const EOR_ZERO_PAGE_X: Opcode = 0x55;
+    const EOR_ABSOLUTE: Opcode = 0x4D;
+    const EOR_ABSOLUTE_X: Opcode = 0x5D;
+    const EOR_ABSOLUTE_Y: Opcode = 0x59;
+    const EOR_INDIRECT_X: Opcode = 0x41;
+    const EOR_INDIRECT_Y: Opcode = 0x51;
+    // BIT
+    const BIT_ZERO_PAGE: Opcode = 0x24;
+    const BIT_ABSOLUTE: Opcode = 0x2C;
+    // CMP
+    const CMP_IMMEDIATE: Opcode = 0xC9;
+    const CMP_ZERO_PAGE: Opcode = 0xC5;
+    const CMP_ZERO_PAGE_X: Opcode = 0xD5;
+    const CMP_ABSOLUTE: Opcode = 0xCD;
+    const CMP_ABSOLUTE_X: Opcode = 0xDD;
+    const CMP_ABSOLUTE_Y: Opcode = 0xD9;
+    const CMP_INDIRECT_X: Opcode = 0xC1;
+    const CMP_INDIRECT_Y: Opcode = 0xD1;
+    // CPX
+    const CPX_IMMEDIATE: Opcode = 0xE0;
+    const CPX_ZERO_PAGE: Opcode = 0xE4;
+    const CPX_ABSOLUTE: Opcode = 0xEC;
+    // CPY
+    const CPY_IMMEDIATE: Opcode = 0xC0;
+    const CPY_ZERO_PAGE: Opcode = 0xC4;
+    const CPY_ABSOLUTE: Opcode = 0xCC;
+    // ASL
+    const ASL_ACCUMULATOR: Opcode = 0x0A;
+    const ASL_ZERO_PAGE: Opcode = 0x06;
+    const ASL_ZERO_PAGE_X: Opcode = 0x16;
+    const ASL_ABSOLUTE: Opcode = 0x0E;
+    const ASL_ABSOLUTE_X: Opcode = 0x1E;
+    // LSR
+    const LSR_ACCUMULATOR: Opcode = 0x4A;
+    const LSR_ZERO_PAGE: Opcode = 0x46;
+    const LSR_ZERO_PAGE_X: Opcode = 0x56;
+    const LSR_ABSOLUTE: Opcode = 0x4E;
+    const LSR_ABSOLUTE_X: Opcode = 0x5E;
+    // ROL
+    const ROL_ACCUMULATOR: Opcode = 0x2A;
+    const ROL_ZERO_PAGE: Opcode = 0x26;
+    const ROL_ZERO_PAGE_X: Opcode = 0x36;
+    const ROL_ABSOLUTE: Opcode = 0x2E;
+    const ROL_ABSOLUTE_X: Opcode = 0x3E;
+    // ROR
+    const ROR_ACCUMULATOR: Opcode = 0x6A;
+    const ROR_ZERO_PAGE: Opcode = 0x66;
+    const ROR_ZERO_PAGE_X: Opcode = 0x76;
+    const ROR_ABSOLUTE: Opcode = 0x6E;
+    const ROR_ABSOLUTE_X: Opcode = 0x7E;
+    // INC
+    const INC_ZERO_PAGE: Opcode = 0xE6;
+    const INC_ZERO_PAGE_X: Opcode = 0xF6;
+    const INC_ABSOLUTE: Opcode = 0xEE;
+    const INC_ABSOLUTE_X: Opcode = 0xFE;
+    // DEC
+    const DEC_ZERO_PAGE: Opcode = 0xC6;
+    const DEC_ZERO_PAGE_X: Opcode = 0xD6;
+    const DEC_ABSOLUTE: Opcode = 0xCE;
+    const DEC_ABSOLUTE_X: Opcode = 0xDE;
+    // register increments / decrements
+    const INX_IMPLIED: Opcode = 0xE8;
+    const INY_IMPLIED: Opcode = 0xC8;
+    const DEX_IMPLIED: Opcode = 0xCA;
+    const DEY_IMPLIED: Opcode = 0x88;
+    // register transfers
+    const TAX_IMPLIED: Opcode = 0xAA;
+    const TAY_IMPLIED: Opcode = 0xA8;
+    const TXA_IMPLIED: Opcode = 0x8A;
+    const TYA_IMPLIED: Opcode = 0x98;
+    // flag instructions
+    const CLC_IMPLIED: Opcode = 0x18;
+    const SEC_IMPLIED: Opcode = 0x38;
+    const CLI_IMPLIED: Opcode = 0x58;
+    const SEI_IMPLIED: Opcode = 0x78;
+    const CLV_IMPLIED: Opcode = 0xB8;
+    const CLD_IMPLIED: Opcode = 0xD8;
+    const SED_IMPLIED: Opcode = 0xF8;
+    // NOP
+    const NOP_IMPLIED: Opcode = 0xEA;
+    // branches
+    const BCC_RELATIVE: Opcode = 0x90;
+    const BCS_RELATIVE: Opcode = 0xB0;
+    const BEQ_RELATIVE: Opcode = 0xF0;
+    const BMI_RELATIVE: Opcode = 0x30;
+    const BNE_RELATIVE: Opcode = 0xD0;
+    const BPL_RELATIVE: Opcode = 0x10;
+    const BVC_RELATIVE: Opcode = 0x50;
+    const BVS_RELATIVE: Opcode = 0x70;
+    // JMP
+    const JMP_ABSOLUTE: Opcode = 0x4C;
+    const JMP_INDIRECT: Opcode = 0x6C;
+    // BRK / RTI
+    const BRK_IMPLIED: Opcode = 0x00;
+    const RTI_IMPLIED: Opcode = 0x40;
+    // JSR / RTS
+    const JSR_ABSOLUTE: Opcode = 0x20;
+    const RTS_IMPLIED: Opcode = 0x60;
+    // stack operations
+    const PHA_IMPLIED: Opcode = 0x48;
+    const PLA_IMPLIED: Opcode = 0x68;
+    const PHP_IMPLIED: Opcode = 0x08;
+    const PLP_IMPLIED: Opcode = 0x28;
+    const TSX_IMPLIED: Opcode = 0xBA;
+    const TXS_IMPLIED: Opcode = 0x9A;
+    // undocumented NMOS opcodes
+    // SLO
+    const SLO_ZERO_PAGE: Opcode = 0x07;
+    const SLO_ZERO_PAGE_X: Opcode = 0x17;
+    const SLO_ABSOLUTE: Opcode = 0x0F;
+    const SLO_ABSOLUTE_X: Opcode = 0x1F;
+    const SLO_ABSOLUTE_Y: Opcode = 0x1B;
+    const SLO_INDIRECT_X: Opcode = 0x03;
+    const SLO_INDIRECT_Y: Opcode = 0x13;
+    // RLA
+    const RLA_ZERO_PAGE: Opcode = 0x27;
+    const RLA_ZERO_PAGE_X: Opcode = 0x37;
+    const RLA_ABSOLUTE: Opcode = 0x2F;
+    const RLA_ABSOLUTE_X: Opcode = 0x3F;
+    const RLA_ABSOLUTE_Y: Opcode = 0x3B;
+    const RLA_INDIRECT_X: Opcode = 0x23;
+    const RLA_INDIRECT_Y: Opcode = 0x33;
+    // SRE
+    const SRE_ZERO_PAGE: Opcode = 0x47;
+    const SRE_ZERO_PAGE_X: Opcode = 0x57;
+    const SRE_ABSOLUTE: Opcode = 0x4F;
+    const SRE_ABSOLUTE_X: Opcode = 0x5F;
+    const SRE_ABSOLUTE_Y: Opcode = 0x5B;
+    const SRE_INDIRECT_X: Opcode = 0x43;
+    const SRE_INDIRECT_Y: Opcode = 0x53;
+    // RRA
+    const RRA_ZERO_PAGE: Opcode = 0x67;
+    const RRA_ZERO_PAGE_X: Opcode = 0x77;
+    const RRA_ABSOLUTE: Opcode = 0x6F;
+    const RRA_ABSOLUTE_X: Opcode = 0x7F;
+    const RRA_ABSOLUTE_Y: Opcode = 0x7B;
+    const RRA_INDIRECT_X: Opcode = 0x63;
+    const RRA_INDIRECT_Y: Opcode = 0x73;
+    // DCP
+    const DCP_ZERO_PAGE: Opcode = 0xC7;
+    const DCP_ZERO_PAGE_X: Opcode = 0xD7;
+    const DCP_ABSOLUTE: Opcode = 0xCF;
+    const DCP_ABSOLUTE_X: Opcode = 0xDF;
+    const DCP_ABSOLUTE_Y: Opcode = 0xDB;
+    const DCP_INDIRECT_X: Opcode = 0xC3;
+    const DCP_INDIRECT_Y: Opcode = 0xD3;
+    // ISC
+    const ISC_ZERO_PAGE: Opcode = 0xE7;
+    const ISC_ZERO_PAGE_X: Opcode = 0xF7;
+    const ISC_ABSOLUTE: Opcode = 0xEF;
+    const ISC_ABSOLUTE_X: Opcode = 0xFF;
+    const ISC_ABSOLUTE_Y: Opcode = 0xFB;
+    const ISC_INDIRECT_X: Opcode = 0xE3;
+    const ISC_INDIRECT_Y: Opcode = 0xF3;
+    // LAX
+    const LAX_ZERO_PAGE: Opcode = 0xA7;
+    const LAX_ZERO_PAGE_Y: Opcode = 0xB7;
+    const LAX_ABSOLUTE: Opcode = 0xAF;
+    const LAX_ABSOLUTE_Y: Opcode = 0xBF;
+    const LAX_INDIRECT_X: Opcode = 0xA3;
+    const LAX_INDIRECT_Y: Opcode = 0xB3;
+    // SAX
+    const SAX_ZERO_PAGE: Opcode = 0x87;
+    const SAX_ZERO_PAGE_Y: Opcode = 0x97;
+    const SAX_ABSOLUTE: Opcode = 0x8F;
+    const SAX_INDIRECT_X: Opcode = 0x83;
+    // immediate logic
+    const ANC_IMMEDIATE: Opcode = 0x0B;
+    const ALR_IMMEDIATE: Opcode = 0x4B;
+    const ARR_IMMEDIATE: Opcode = 0x6B;
+    const SBX_IMMEDIATE: Opcode = 0xCB;
+    const USBC_IMMEDIATE: Opcode = 0xEB;
+    // unstable
+    const XAA_IMMEDIATE: Opcode = 0x8B;
+    const LXA_IMMEDIATE: Opcode = 0xAB;
+    const AHX_ABSOLUTE_Y: Opcode = 0x9F;
+    const AHX_INDIRECT_Y: Opcode = 0x93;
+    const TAS_ABSOLUTE_Y: Opcode = 0x9B;
+    const SHY_ABSOLUTE_X: Opcode = 0x9C;
+    const SHX_ABSOLUTE_Y: Opcode = 0x9E;
+    const LAS_ABSOLUTE_Y: Opcode = 0xBB;
+    // multi-byte NOP
+    const NOP_IMMEDIATE: Opcode = 0x80;
+    const NOP_ZERO_PAGE: Opcode = 0x04;
+    const NOP_ZERO_PAGE_X: Opcode = 0x14;
+    const NOP_ABSOLUTE: Opcode = 0x0C;
+    const NOP_ABSOLUTE_X: Opcode = 0x1C;
+    // JAM / KIL
+    const JAM_IMPLIED: Opcode = 0x02;
+    // 65C02
+    const BRA_RELATIVE: Opcode = 0x80;
+    const PHX_IMPLIED: Opcode = 0xDA;
+    const PLX_IMPLIED: Opcode = 0xFA;
+    const PHY_IMPLIED: Opcode = 0x5A;
+    const PLY_IMPLIED: Opcode = 0x7A;
+    const STZ_ZERO_PAGE: Opcode = 0x64;
+    const STZ_ZERO_PAGE_X: Opcode = 0x74;
+    const STZ_ABSOLUTE: Opcode = 0x9C;
+    const STZ_ABSOLUTE_X: Opcode = 0x9E;
+    const TRB_ZERO_PAGE: Opcode = 0x14;
+    const TRB_ABSOLUTE: Opcode = 0x1C;
+    const TSB_ZERO_PAGE: Opcode = 0x04;
+    const TSB_ABSOLUTE: Opcode = 0x0C;
+    const ORA_ZERO_PAGE_INDIRECT: Opcode = 0x12;
+    const AND_ZERO_PAGE_INDIRECT: Opcode = 0x32;
+    const EOR_ZERO_PAGE_INDIRECT: Opcode = 0x52;
+    const ADC_ZERO_PAGE_INDIRECT: Opcode = 0x72;
+    const STA_ZERO_PAGE_INDIRECT: Opcode = 0x92;
+    const LDA_ZERO_PAGE_INDIRECT: Opcode = 0xB2;
+    const CMP_ZERO_PAGE_INDIRECT: Opcode = 0xD2;
+    const SBC_ZERO_PAGE_INDIRECT: Opcode = 0xF2;
+    const INC_ACCUMULATOR: Opcode = 0x1A;
+    const DEC_ACCUMULATOR: Opcode = 0x3A;
+    const BIT_IMMEDIATE: Opcode = 0x89;
+    const BIT_ZERO_PAGE_X: Opcode = 0x34;
+    const BIT_ABSOLUTE_X: Opcode = 0x3C;
+    const JMP_ABSOLUTE_X_INDIRECT: Opcode = 0x7C;
+    // Rockwell bit instructions
+    const RMB0_ZERO_PAGE: Opcode = 0x07;
+    const RMB1_ZERO_PAGE: Opcode = 0x17;
+    const RMB2_ZERO_PAGE: Opcode = 0x27;
+    const RMB3_ZERO_PAGE: Opcode = 0x37;
+    const RMB4_ZERO_PAGE: Opcode = 0x47;
+    const RMB5_ZERO_PAGE: Opcode = 0x57;
+    const RMB6_ZERO_PAGE: Opcode = 0x67;
+    const RMB7_ZERO_PAGE: Opcode = 0x77;
+    const SMB0_ZERO_PAGE: Opcode = 0x87;
+    const SMB1_ZERO_PAGE: Opcode = 0x97;
+    const SMB2_ZERO_PAGE: Opcode = 0xA7;
+    const SMB3_ZERO_PAGE: Opcode = 0xB7;
+    const SMB4_ZERO_PAGE: Opcode = 0xC7;
+    const SMB5_ZERO_PAGE: Opcode = 0xD7;
+    const SMB6_ZERO_PAGE: Opcode = 0xE7;
+    const SMB7_ZERO_PAGE: Opcode = 0xF7;
+    const BBR0_ZERO_PAGE_RELATIVE: Opcode = 0x0F;
+    const BBR1_ZERO_PAGE_RELATIVE: Opcode = 0x1F;
+    const BBR2_ZERO_PAGE_RELATIVE: Opcode = 0x2F;
+    const BBR3_ZERO_PAGE_RELATIVE: Opcode = 0x3F;
+    const BBR4_ZERO_PAGE_RELATIVE: Opcode = 0x4F;
+    const BBR5_ZERO_PAGE_RELATIVE: Opcode = 0x5F;
+    const BBR6_ZERO_PAGE_RELATIVE: Opcode = 0x6F;
+    const BBR7_ZERO_PAGE_RELATIVE: Opcode = 0x7F;
+    const BBS0_ZERO_PAGE_RELATIVE: Opcode = 0x8F;
+    const BBS1_ZERO_PAGE_RELATIVE: Opcode = 0x9F;
+    const BBS2_ZERO_PAGE_RELATIVE: Opcode = 0xAF;
+    const BBS3_ZERO_PAGE_RELATIVE: Opcode = 0xBF;
+    const BBS4_ZERO_PAGE_RELATIVE: Opcode = 0xCF;
+    const BBS5_ZERO_PAGE_RELATIVE: Opcode = 0xDF;
+    const BBS6_ZERO_PAGE_RELATIVE: Opcode = 0xEF;
+    const BBS7_ZERO_PAGE_RELATIVE: Opcode = 0xFF;
+    // WDC
+    const WAI_IMPLIED: Opcode = 0xCB;
+    const STP_IMPLIED: Opcode = 0xDB;
+}
+
+// indexed addressing only pays for a page crossing when reading,
+// writes always spend the extra cycle fixing up the high byte
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+// one clock's worth of bus activity, as reported by CPU::tick
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct BusCycle {
+    pub address: Word,
+    pub value: Byte,
+    pub access: Access,
+}
+
+impl CPU {
+
+    // boots the cpu the way the RESET line does: the sequence runs three dummy stack
+    // pushes (sp ends at 0xFD), masks interrupts and loads pc from the reset vector
+    pub fn reset_cpu<B: Bus>(&mut self, bus: &mut B) {
+        let (reset_address, _): (Word, i32) = CPU::read_word(RESET_VECTOR, bus, 0);
+        self.pc = reset_address;
+        self.sp = 0xFD;
+        self.r_a = 0;
+        self.r_x = 0;
+        self.r_y = 0;
+        self.ps_carry = 0;
+        self.ps_zero = 0;
+        self.ps_interrupt = 1;
+        self.ps_decimal = 0;
+        self.ps_break = 0;
+        self.ps_overflow = 0;
+        self.ps_negative = 0;
+        self.nmi_pending = false;
+        self.jammed = false;
+        self.waiting = false;
+        self.stopped = false;
+    }
+
+    // the IRQ line stays asserted until the device acknowledges it, so the handler
+    // is re-entered after RTI for as long as the line is held and I is clear
+    pub fn set_irq_line(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    // NMI is latched on the falling edge of the (active low) line, holding it does not retrigger
+    pub fn set_nmi_line(&mut self, asserted: bool) {
+        if asserted && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = asserted;
+    }
+
+    // hardware interrupt sequence: 2 dummy reads, push pc and status, load the vector (7 cycles)
+    fn interrupt<B: Bus>(&mut self, vector: Word, bus: &mut B, cycles: i32) -> Result<i32, EmuError> {
+        let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+        let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+        let cycles: i32 = self.push_word(self.pc, bus, cycles)?;
+        // B is pushed clear so the handler can tell a hardware interrupt from BRK
+        let cycles: i32 = self.push_byte((self.processor_status() | 0x20) & !0x10, bus, cycles)?;
+        self.ps_interrupt = 1;
+        if self.variant.is_cmos() {
+            self.ps_decimal = 0;
+        }
+        let (handler_address, cycles): (Word, i32) = CPU::read_word(vector, bus, cycles);
+        self.pc = handler_address;
+        Ok(cycles)
+    }
+
+    fn fetch_byte<B: Bus>(&mut self, bus: &mut B, mut cycles: i32) -> (Byte, i32) {
+        let instruction: Byte = bus.read(self.pc);
+        self.pc = self.pc.wrapping_add(1);
+        cycles -= 1;
+        (instruction, cycles)
+    }
+
+    fn read_byte<B: Bus>(address: Word, bus: &mut B, mut cycles: i32) -> (Byte, i32){
+        let byte: Byte = bus.read(address);
+        cycles -= 1;
+        (byte, cycles)
+    }
+
+    fn read_byte_zero_page<B: Bus>(address: Byte, bus: &mut B, mut cycles: i32) -> (Byte, i32){
+        let byte: Byte = bus.read(address as Word);
+        cycles -= 1;
+        (byte, cycles)
+    }
+
+    fn read_word<B: Bus>(address: Word, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles_min_one): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+        let (hi_byte, cycles_min_two): (Byte, i32) = CPU::read_byte(address.wrapping_add(1), bus, cycles_min_one);
+        let lo_byte_word: Word = lo_byte.into();
+        let hi_byte_word: Word = <u8 as Into<Word>>::into(hi_byte) << 8;
+        let full_word: Word = lo_byte_word | hi_byte_word;
+        (full_word, cycles_min_two)
+    }
+
+    // pointers stored in the zero page wrap around to 0x00 instead of spilling into page 1
+    fn read_word_zero_page<B: Bus>(address: Byte, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles_min_one): (Byte, i32) = CPU::read_byte_zero_page(address, bus, cycles);
+        let (hi_byte, cycles_min_two): (Byte, i32) = CPU::read_byte_zero_page(address.wrapping_add(1), bus, cycles_min_one);
+        let lo_byte_word: Word = lo_byte.into();
+        let hi_byte_word: Word = <u8 as Into<Word>>::into(hi_byte) << 8;
+        let full_word: Word = lo_byte_word | hi_byte_word;
+        (full_word, cycles_min_two)
+    }
+
+    fn fetch_word<B: Bus>(&mut self, bus: &mut B, mut cycles: i32) -> (Word, i32) {
+        let lo_byte: Word = bus.read(self.pc).into();
+        self.pc = self.pc.wrapping_add(1); // goto next memory addr for hi byte
+        let hi_byte: Word = <u8 as Into<Word>>::into(bus.read(self.pc)) << 8;
+        self.pc = self.pc.wrapping_add(1);
+        cycles -= 2; // fetch twice: 2 cycles
+        let full_word: Word = lo_byte | hi_byte;
+        (full_word, cycles)
+    }
+
+    fn write_byte<B: Bus>(value: Byte, address: Word, bus: &mut B, mut cycles: i32) -> i32 {
+        bus.write(address, value);
+        cycles -= 1;
+        cycles
+    }
+
+    // an internal cycle still puts an address on the bus and reads it; in cycle accurate
+    // mode that read really happens, which matters to devices with read side effects
+    fn dummy_read<B: Bus>(&self, address: Word, bus: &mut B, cycles: i32) -> i32 {
+        if self.cycle_accurate {
+            bus.read(address);
+        }
+        cycles - 1
+    }
+
+    // the address an indexed access reads first, before the carry reaches the high byte
+    fn unfixed_address(address: Word, indexed_address: Word) -> Word {
+        (address & 0xFF00) | (indexed_address & 0x00FF)
+    }
+
+    // the CMOS parts avoid the bogus read of the unfixed address on a page crossing
+    // and read the last operand byte again instead
+    fn index_fixup_address(&self, address: Word, indexed_address: Word) -> Word {
+        if self.variant.is_cmos() && CPU::page_crossed(address, indexed_address) {
+            return self.pc.wrapping_sub(1);
+        }
+        CPU::unfixed_address(address, indexed_address)
+    }
+
+    // ASL/LSR/ROL/ROR abs,X only pay for the high byte fix-up on a page crossing on CMOS
+    fn shift_indexed_access(&self) -> Access {
+        match self.variant.is_cmos() {
+            true => Access::Read,
+            false => Access::Write,
+        }
+    }
+
+    // ADC and SBC take an extra cycle for the decimal fix-up on CMOS
+    fn decimal_cycle<B: Bus>(&self, bus: &mut B, cycles: i32) -> i32 {
+        if self.variant.is_cmos() && self.ps_decimal == 1 {
+            return self.dummy_read(self.pc.wrapping_sub(1), bus, cycles);
+        }
+        cycles
+    }
+
+    /* logical, compare and read-modify-write helpers */
+
+    fn logical_and(&mut self, value: Byte) {
+        self.r_a &= value;
+        self.set_zero_negative_flags(self.r_a);
+    }
+
+    fn logical_or(&mut self, value: Byte) {
+        self.r_a |= value;
+        self.set_zero_negative_flags(self.r_a);
+    }
+
+    fn exclusive_or(&mut self, value: Byte) {
+        self.r_a ^= value;
+        self.set_zero_negative_flags(self.r_a);
+    }
+
+    // BIT: Z from A & M, N and V copied straight from bits 7 and 6 of the operand
+    fn bit_test(&mut self, value: Byte) {
+        self.ps_zero = ((self.r_a & value) == 0) as Byte;
+        self.ps_negative = (value >> 7) & 1;
+        self.ps_overflow = (value >> 6) & 1;
+    }
+
+    // CMP / CPX / CPY: register - M without storing, C is set when register >= M
+    fn compare(&mut self, register: Byte, value: Byte) {
+        self.ps_carry = (register >= value) as Byte;
+        self.set_zero_negative_flags(register.wrapping_sub(value));
+    }
+
+    fn shift_left(&mut self, value: Byte) -> Byte {
+        let result: Byte = value << 1;
+        self.ps_carry = value >> 7;
+        self.set_zero_negative_flags(result);
+        result
+    }
+
+    fn shift_right(&mut self, value: Byte) -> Byte {
+        let result: Byte = value >> 1;
+        self.ps_carry = value & 1;
+        self.set_zero_negative_flags(result);
+        result
+    }
+
+    fn rotate_left(&mut self, value: Byte) -> Byte {
+        let result: Byte = (value << 1) | self.ps_carry;
+        self.ps_carry = value >> 7;
+        self.set_zero_negative_flags(result);
+        result
+    }
+
+    fn rotate_right(&mut self, value: Byte) -> Byte {
+        let result: Byte = (value >> 1) | (self.ps_carry << 7);
+        self.ps_carry = value & 1;
+        self.set_zero_negative_flags(result);
+        result
+    }
+
+    fn increment(&mut self, value: Byte) -> Byte {
+        let result: Byte = value.wrapping_add(1);
+        self.set_zero_negative_flags(result);
+        result
+    }
+
+    fn decrement(&mut self, value: Byte) -> Byte {
+        let result: Byte = value.wrapping_sub(1);
+        self.set_zero_negative_flags(result);
+        result
+    }
+
+    // read-modify-write: the NMOS part writes the unmodified value back
+    // while the ALU works, then writes the result (read + 2 writes);
+    // the CMOS parts read the operand again instead of the extra write
+    fn read_modify_write<B: Bus>(&mut self, address: Word, bus: &mut B, cycles: i32, operation: fn(&mut CPU, Byte) -> Byte) -> i32 {
+        let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+        let cycles: i32 = match self.variant.is_cmos() {
+            true => self.dummy_read(address, bus, cycles),
+            false => CPU::write_byte(value, address, bus, cycles),
+        };
+        let result: Byte = operation(self, value);
+        CPU::write_byte(result, address, bus, cycles)
+    }
+
+    // relative branch: +1 cycle when taken, +1 more when the target lands on another page
+    fn branch<B: Bus>(&mut self, condition: bool, bus: &mut B, cycles: i32) -> i32 {
+        let (offset, mut cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        if condition {
+            let target: Word = self.pc.wrapping_add(offset as i8 as Word);
+            cycles = self.dummy_read(self.pc, bus, cycles);
+            if CPU::page_crossed(self.pc, target) {
+                cycles = self.dummy_read(CPU::unfixed_address(self.pc, target), bus, cycles);
+            }
+            self.pc = target;
+        }
+        cycles
+    }
+
+    /* stack: grows downward through page 0x01, sp wraps around inside the page */
+
+    fn push_byte<B: Bus>(&mut self, value: Byte, bus: &mut B, cycles: i32) -> Result<i32, EmuError> {
+        if self.strict_stack && self.sp == 0x00 {
+            return Err(EmuError::StackOverflow);
+        }
+        let cycles: i32 = CPU::write_byte(value, STACK_PAGE | self.sp as Word, bus, cycles);
+        self.sp = self.sp.wrapping_sub(1);
+        Ok(cycles)
+    }
+
+    fn pull_byte<B: Bus>(&mut self, bus: &mut B, cycles: i32) -> Result<(Byte, i32), EmuError> {
+        if self.strict_stack && self.sp == 0xFF {
+            return Err(EmuError::StackUnderflow);
+        }
+        self.sp = self.sp.wrapping_add(1);
+        Ok(CPU::read_byte(STACK_PAGE | self.sp as Word, bus, cycles))
+    }
+
+    // high byte goes first so the word sits little-endian in memory
+    fn push_word<B: Bus>(&mut self, value: Word, bus: &mut B, cycles: i32) -> Result<i32, EmuError> {
+        let cycles: i32 = self.push_byte((value >> 8) as Byte, bus, cycles)?;
+        self.push_byte((value & 0xFF) as Byte, bus, cycles)
+    }
+
+    fn pull_word<B: Bus>(&mut self, bus: &mut B, cycles: i32) -> Result<(Word, i32), EmuError> {
+        let (lo_byte, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+        let (hi_byte, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+        Ok((((hi_byte as Word) << 8) | lo_byte as Word, cycles))
+    }
+
+    /* processor status register: NV1B DIZC */
+
+    pub fn processor_status(&self) -> Byte {
+        (self.ps_negative << 7)
+            | (self.ps_overflow << 6)
+            | (1 << 5)
+            | (self.ps_break << 4)
+            | (self.ps_decimal << 3)
+            | (self.ps_interrupt << 2)
+            | (self.ps_zero << 1)
+            | self.ps_carry
+    }
+
+    // B and the unused bit only exist on the stack copy, pulling the status ignores them
+    pub fn set_processor_status(&mut self, status: Byte) {
+        self.ps_negative = (status >> 7) & 1;
+        self.ps_overflow = (status >> 6) & 1;
+        self.ps_decimal = (status >> 3) & 1;
+        self.ps_interrupt = (status >> 2) & 1;
+        self.ps_zero = (status >> 1) & 1;
+        self.ps_carry = status & 1;
+    }
+
+    // N mirrors bit 7 of the result, Z is set when the result is 0x00
+    fn set_zero_negative_flags(&mut self, value: Byte) {
+        self.ps_zero = (value == 0) as Byte;
+        self.ps_negative = (value >> 7) & 1;
+    }
+
+    // ADC: A + M + C, in BCD when the decimal flag is set
+    fn add_with_carry(&mut self, value: Byte) {
+        let binary_sum: Word = self.r_a as Word + value as Word + self.ps_carry as Word;
+        let binary_result: Byte = binary_sum as Byte;
+
+        // the 2A03 keeps the D flag but has no BCD adder behind it
+        if self.ps_decimal == 0 || !self.variant.has_decimal_mode() {
+            self.ps_carry = (binary_sum > 0xFF) as Byte;
+            self.ps_overflow = ((!(self.r_a ^ value) & (self.r_a ^ binary_result)) >> 7) & 1;
+            self.r_a = binary_result;
+            self.set_zero_negative_flags(binary_result);
+            return;
+        }
+
+        // NMOS decimal mode: the low nibble is adjusted first, N and V are taken from the
+        // half-adjusted sum before the high nibble is fixed up and Z comes from the binary sum
+        let mut low_nibble: i16 = (self.r_a & 0x0F) as i16 + (value & 0x0F) as i16 + self.ps_carry as i16;
+        if low_nibble >= 0x0A {
+            low_nibble = ((low_nibble + 0x06) & 0x0F) + 0x10;
+        }
+        let signed_sum: i16 = ((self.r_a & 0xF0) as i8) as i16 + ((value & 0xF0) as i8) as i16 + low_nibble;
+        let mut sum: i16 = (self.r_a & 0xF0) as i16 + (value & 0xF0) as i16 + low_nibble;
+        if sum >= 0xA0 {
+            sum += 0x60;
+        }
+
+        self.ps_negative = ((signed_sum >> 7) & 1) as Byte;
+        self.ps_overflow = !(-128..=127).contains(&signed_sum) as Byte;
+        self.ps_zero = (binary_result == 0) as Byte;
+        self.ps_carry = (sum >= 0x100) as Byte;
+        self.r_a = sum as Byte;
+
+        // the CMOS parts spend their extra cycle making N and Z valid for the BCD result
+        if self.variant.is_cmos() {
+            self.set_zero_negative_flags(self.r_a);
+        }
+    }
+
+    // SBC: A - M - (1 - C), in BCD when the decimal flag is set
+    fn subtract_with_carry(&mut self, value: Byte) {
+        let accumulator: Byte = self.r_a;
+        let borrow: i16 = 1 - self.ps_carry as i16;
+
+        // the NMOS part computes every flag from the binary difference, even in decimal mode
+        let binary_difference: Word = accumulator as Word + (!value) as Word + self.ps_carry as Word;
+        let binary_result: Byte = binary_difference as Byte;
+        self.ps_carry = (binary_difference > 0xFF) as Byte;
+        self.ps_overflow = (((accumulator ^ value) & (accumulator ^ binary_result)) >> 7) & 1;
+        self.set_zero_negative_flags(binary_result);
+
+        if self.ps_decimal == 0 || !self.variant.has_decimal_mode() {
+            self.r_a = binary_result;
+            return;
+        }
+
+        let mut low_nibble: i16 = (accumulator & 0x0F) as i16 - (value & 0x0F) as i16 - borrow;
+
+        // the 65C02 corrects the whole difference first and the low nibble after it, which only
+        // differs from the NMOS order for invalid BCD operands
+        if self.variant.is_cmos() {
+            let mut difference: i16 = accumulator as i16 - value as i16 - borrow;
+            if difference < 0 {
+                difference -= 0x60;
+            }
+            if low_nibble < 0 {
+                difference -= 0x06;
+            }
+            self.r_a = difference as Byte;
+            self.set_zero_negative_flags(self.r_a);
+            return;
+        }
+
+        if low_nibble < 0 {
+            low_nibble = ((low_nibble - 0x06) & 0x0F) - 0x10;
+        }
+        let mut difference: i16 = (accumulator & 0xF0) as i16 - (value & 0xF0) as i16 + low_nibble;
+        if difference < 0 {
+            difference -= 0x60;
+        }
+        self.r_a = difference as Byte;
+    }
+
+    fn page_crossed(address: Word, indexed_address: Word) -> bool {
+        (address & 0xFF00) != (indexed_address & 0xFF00)
+    }
+
+    /* addressing modes: resolve the effective address of the operand */
+
+    fn addr_zero_page<B: Bus>(&mut self, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (zero_page_address, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        (zero_page_address as Word, cycles)
+    }
+
+    fn addr_zero_page_indexed<B: Bus>(&mut self, bus: &mut B, cycles: i32, index: Byte) -> (Word, i32) {
+        let (zero_page_address, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let zero_page_addr_indexed: Byte = zero_page_address.wrapping_add(index);
+        let cycles: i32 = self.dummy_read(zero_page_address as Word, bus, cycles);
+        (zero_page_addr_indexed as Word, cycles)
+    }
+
+    fn addr_absolute_indexed<B: Bus>(&mut self, bus: &mut B, cycles: i32, index: Byte, access: Access) -> (Word, i32) {
+        let (absolute_address, mut cycles): (Word, i32) = self.fetch_word(bus, cycles);
+        let absolute_addr_indexed: Word = absolute_address.wrapping_add(index as Word);
+        if access == Access::Write || CPU::page_crossed(absolute_address, absolute_addr_indexed) {
+            cycles = self.dummy_read(self.index_fixup_address(absolute_address, absolute_addr_indexed), bus, cycles);
+        }
+        (absolute_addr_indexed, cycles)
+    }
+
+    fn addr_indirect_x<B: Bus>(&mut self, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (zero_page_address, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let zero_page_addr_x: Byte = zero_page_address.wrapping_add(self.r_x);
+        let cycles: i32 = self.dummy_read(zero_page_address as Word, bus, cycles);
+        CPU::read_word_zero_page(zero_page_addr_x, bus, cycles)
+    }
+
+    fn addr_indirect_y<B: Bus>(&mut self, bus: &mut B, cycles: i32, access: Access) -> (Word, i32) {
+        let (zero_page_address, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let (effective_address, mut cycles): (Word, i32) = CPU::read_word_zero_page(zero_page_address, bus, cycles);
+        let effective_addr_y: Word = effective_address.wrapping_add(self.r_y as Word);
+        if access == Access::Write || CPU::page_crossed(effective_address, effective_addr_y) {
+            cycles = self.dummy_read(self.index_fixup_address(effective_address, effective_addr_y), bus, cycles);
+        }
+        (effective_addr_y, cycles)
+    }
+
+    // runs whole instructions until the cycle budget is used up and returns the cycles spent;
+    // the last instruction is never cut short, so the result can overshoot the budget
+    pub fn execute<B: Bus>(&mut self, mut cycles: i32, bus: &mut B) -> Result<i32, EmuError>{
+
+        let requested_cycles: i32 = cycles;
+        cycles -= self.finish_instruction(bus)?;
+
+        while cycles > 0 {
+            cycles = self.execute_instruction(cycles, bus)?;
+            if let Some(address) = bus.take_fault() {
+                return Err(EmuError::BusFault { address });
+            }
+        }
+
+        Ok(requested_cycles - cycles)
+
+    }
+
+    // runs exactly one instruction (or interrupt entry) and returns the cycles it took,
+    // an instruction left part way through by tick() is finished instead
+    pub fn step<B: Bus>(&mut self, bus: &mut B) -> Result<i32, EmuError> {
+        if !self.at_instruction_boundary() {
+            return self.finish_instruction(bus);
+        }
+        let cycles: i32 = self.execute_instruction(0, bus)?;
+        if let Some(address) = bus.take_fault() {
+            return Err(EmuError::BusFault { address });
+        }
+        Ok(-cycles)
+    }
+
+    // true when the next step() enters an NMI or IRQ handler instead of running the opcode at pc
+    pub fn interrupt_pending(&self) -> bool {
+        self.nmi_pending || (self.irq_line && self.ps_interrupt == 0)
+    }
+
+    // runs until an instruction jumps or branches to itself, the JMP * idiom test suites use
+    // to report a result, and returns the address of that trap. A cpu idling in WAI counts too
+    pub fn run_until_trap<B: Bus>(&mut self, bus: &mut B, max_cycles: u64) -> Result<Word, EmuError> {
+        let mut total_cycles: u64 = 0;
+        while total_cycles < max_cycles {
+            let address: Word = self.pc;
+            total_cycles += self.step(bus)? as u64;
+            if self.pc == address {
+                return Ok(address);
+            }
+        }
+        Err(EmuError::CycleBudgetExhausted { cycles: max_cycles.min(i32::MAX as u64) as i32 })
+    }
+
+    // runs one instruction (or enters a pending interrupt) and returns what is left of the cycle budget
+    fn execute_instruction<B: Bus>(&mut self, cycles: i32, bus: &mut B) -> Result<i32, EmuError> {
+
+        // a jammed cpu ignores interrupts and keeps reporting the opcode that locked it up
+        if self.jammed {
+            return Err(EmuError::Jammed { address: self.pc, opcode: bus.peek(self.pc) });
+        }
+        if self.stopped {
+            return Err(EmuError::Stopped { address: self.pc });
+        }
+        // WAI idles one cycle at a time until IRQ or NMI shows up, even with I set
+        if self.waiting {
+            if !self.nmi_pending && !self.irq_line {
+                return Ok(self.dummy_read(self.pc, bus, cycles));
+            }
+            self.waiting = false;
+        }
+
+        // interrupts are polled between instructions, NMI wins over IRQ
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            return self.interrupt(NMI_VECTOR, bus, cycles);
+        }
+        if self.irq_line && self.ps_interrupt == 0 {
+            return self.interrupt(IRQ_VECTOR, bus, cycles);
+        }
+
+        let (instruction, cycles): (Opcode, i32) = self.fetch_byte(bus, cycles);
+
+        match instruction {
+
+            // LDA
+            CPU::LDA_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDA_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+
+            // LDX
+            CPU::LDX_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.r_x = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_x = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ZERO_PAGE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_y);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_x = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_x = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_x = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+
+            // LDY
+            CPU::LDY_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.r_y = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_y = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_y = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_y = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_y = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+
+            // STA
+            CPU::STA_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::STA_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::STA_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::STA_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::STA_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::STA_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::STA_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+
+            // STX
+            CPU::STX_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(CPU::write_byte(self.r_x, address, bus, cycles))
+            }
+            CPU::STX_ZERO_PAGE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_y);
+                Ok(CPU::write_byte(self.r_x, address, bus, cycles))
+            }
+            CPU::STX_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(CPU::write_byte(self.r_x, address, bus, cycles))
+            }
+
+            // STY
+            CPU::STY_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(CPU::write_byte(self.r_y, address, bus, cycles))
+            }
+            CPU::STY_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(CPU::write_byte(self.r_y, address, bus, cycles))
+            }
+            CPU::STY_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(CPU::write_byte(self.r_y, address, bus, cycles))
+            }
+
+            // ADC
+            CPU::ADC_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::ADC_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+
+            // SBC
+            CPU::SBC_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::SBC_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+
+            // AND
+            CPU::AND_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            // ORA
+            CPU::ORA_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            // EOR
+            CPU::EOR_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+
+            // BIT
+            CPU::BIT_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+            CPU::BIT_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+
+            // CMP
+            CPU::CMP_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::CMP_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            // CPX
+            CPU::CPX_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.compare(self.r_x, value);
+                Ok(cycles)
+            }
+            CPU::CPX_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_x, value);
+                Ok(cycles)
+            }
+            CPU::CPX_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_x, value);
+                Ok(cycles)
+            }
+            // CPY
+            CPU::CPY_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.compare(self.r_y, value);
+                Ok(cycles)
+            }
+            CPU::CPY_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_y, value);
+                Ok(cycles)
+            }
+            CPU::CPY_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_y, value);
+                Ok(cycles)
+            }
+
+            // ASL
+            CPU::ASL_ACCUMULATOR => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = CPU::shift_left(self, self.r_a);
+                Ok(cycles)
+            }
+            CPU::ASL_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left))
+            }
+            CPU::ASL_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left))
+            }
+            CPU::ASL_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left))
+            }
+            CPU::ASL_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, self.shift_indexed_access());
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left))
+            }
+            // LSR
+            CPU::LSR_ACCUMULATOR => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = CPU::shift_right(self, self.r_a);
+                Ok(cycles)
+            }
+            CPU::LSR_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right))
+            }
+            CPU::LSR_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right))
+            }
+            CPU::LSR_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right))
+            }
+            CPU::LSR_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, self.shift_indexed_access());
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right))
+            }
+            // ROL
+            CPU::ROL_ACCUMULATOR => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = CPU::rotate_left(self, self.r_a);
+                Ok(cycles)
+            }
+            CPU::ROL_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left))
+            }
+            CPU::ROL_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left))
+            }
+            CPU::ROL_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left))
+            }
+            CPU::ROL_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, self.shift_indexed_access());
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left))
+            }
+            // ROR
+            CPU::ROR_ACCUMULATOR => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = CPU::rotate_right(self, self.r_a);
+                Ok(cycles)
+            }
+            CPU::ROR_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right))
+            }
+            CPU::ROR_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right))
+            }
+            CPU::ROR_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right))
+            }
+            CPU::ROR_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, self.shift_indexed_access());
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right))
+            }
+
+            // INC
+            CPU::INC_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment))
+            }
+            CPU::INC_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment))
+            }
+            CPU::INC_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment))
+            }
+            CPU::INC_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment))
+            }
+            // DEC
+            CPU::DEC_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement))
+            }
+            CPU::DEC_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement))
+            }
+            CPU::DEC_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement))
+            }
+            CPU::DEC_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement))
+            }
+
+            // register increments / decrements
+            CPU::INX_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_x = self.increment(self.r_x);
+                Ok(cycles)
+            }
+            CPU::INY_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_y = self.increment(self.r_y);
+                Ok(cycles)
+            }
+            CPU::DEX_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_x = self.decrement(self.r_x);
+                Ok(cycles)
+            }
+            CPU::DEY_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_y = self.decrement(self.r_y);
+                Ok(cycles)
+            }
+
+            // register transfers
+            CPU::TAX_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_x = self.r_a;
+                self.set_zero_negative_flags(self.r_x);
+                Ok(cycles)
+            }
+            CPU::TAY_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_y = self.r_a;
+                self.set_zero_negative_flags(self.r_y);
+                Ok(cycles)
+            }
+            CPU::TXA_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = self.r_x;
+                self.set_zero_negative_flags(self.r_a);
+                Ok(cycles)
+            }
+            CPU::TYA_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = self.r_y;
+                self.set_zero_negative_flags(self.r_a);
+                Ok(cycles)
+            }
+
+            // flag instructions
+            CPU::CLC_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_carry = 0;
+                Ok(cycles)
+            }
+            CPU::SEC_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_carry = 1;
+                Ok(cycles)
+            }
+            CPU::CLI_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_interrupt = 0;
+                Ok(cycles)
+            }
+            CPU::SEI_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_interrupt = 1;
+                Ok(cycles)
+            }
+            CPU::CLV_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_overflow = 0;
+                Ok(cycles)
+            }
+            CPU::CLD_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_decimal = 0;
+                Ok(cycles)
+            }
+            CPU::SED_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.ps_decimal = 1;
+                Ok(cycles)
+            }
+
+            // NOP
+            CPU::NOP_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                Ok(cycles)
+            }
+
+            // branches
+            CPU::BCC_RELATIVE => {
+                Ok(self.branch(self.ps_carry == 0, bus, cycles))
+            }
+            CPU::BCS_RELATIVE => {
+                Ok(self.branch(self.ps_carry == 1, bus, cycles))
+            }
+            CPU::BEQ_RELATIVE => {
+                Ok(self.branch(self.ps_zero == 1, bus, cycles))
+            }
+            CPU::BMI_RELATIVE => {
+                Ok(self.branch(self.ps_negative == 1, bus, cycles))
+            }
+            CPU::BNE_RELATIVE => {
+                Ok(self.branch(self.ps_zero == 0, bus, cycles))
+            }
+            CPU::BPL_RELATIVE => {
+                Ok(self.branch(self.ps_negative == 0, bus, cycles))
+            }
+            CPU::BVC_RELATIVE => {
+                Ok(self.branch(self.ps_overflow == 0, bus, cycles))
+            }
+            CPU::BVS_RELATIVE => {
+                Ok(self.branch(self.ps_overflow == 1, bus, cycles))
+            }
+
+            // JMP
+            CPU::JMP_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU::JMP_INDIRECT if self.variant.is_cmos() => {
+                // fixed on CMOS, at the price of an extra cycle
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = self.dummy_read(self.pc.wrapping_sub(1), bus, cycles);
+                let (address, cycles): (Word, i32) = CPU::read_word(pointer, bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU::JMP_INDIRECT => {
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (lo_byte, cycles): (Byte, i32) = CPU::read_byte(pointer, bus, cycles);
+                // NMOS bug: the high byte of the vector never carries into the next page,
+                // so JMP ($10FF) reads its target from 0x10FF and 0x1000
+                let hi_pointer: Word = (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF);
+                let (hi_byte, cycles): (Byte, i32) = CPU::read_byte(hi_pointer, bus, cycles);
+                self.pc = ((hi_byte as Word) << 8) | lo_byte as Word;
+                Ok(cycles)
+            }
+
+            // BRK / RTI
+            CPU::BRK_IMPLIED => {
+                // BRK skips a padding byte, so the pushed return address is BRK + 2
+                let (_padding, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                let cycles: i32 = self.push_word(self.pc, bus, cycles)?;
+                let cycles: i32 = self.push_byte(self.processor_status() | 0x30, bus, cycles)?;
+                self.ps_interrupt = 1;
+                if self.variant.is_cmos() {
+                    self.ps_decimal = 0; // CMOS handlers always start in binary mode
+                }
+                let (handler_address, cycles): (Word, i32) = CPU::read_word(IRQ_VECTOR, bus, cycles);
+                self.pc = handler_address;
+                Ok(cycles)
+            }
+            CPU::RTI_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // before the sp increment
+                let (status, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+                self.set_processor_status(status);
+                let (return_addr, cycles): (Word, i32) = self.pull_word(bus, cycles)?;
+                self.pc = return_addr;
+                Ok(cycles)
+            }
+
+            // JSR / RTS
+            CPU::JSR_ABSOLUTE => {
+                // the high byte of the target is only fetched after the return address is pushed
+                let (low, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // internal operation
+                // the return address pushed is the last byte of the JSR itself
+                let cycles: i32 = self.push_word(self.pc, bus, cycles)?;
+                let (high, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.pc = (high as Word) << 8 | low as Word;
+                Ok(cycles)
+            }
+            CPU::RTS_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // before the sp increment
+                let (return_addr, cycles): (Word, i32) = self.pull_word(bus, cycles)?;
+                let cycles: i32 = self.dummy_read(return_addr, bus, cycles); // increment pc past the JSR
+                self.pc = return_addr.wrapping_add(1);
+                Ok(cycles)
+            }
+
+            // stack operations
+            CPU::PHA_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.push_byte(self.r_a, bus, cycles)
+            }
+            CPU::PLA_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // before the sp increment
+                let (value, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::PHP_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                // PHP always pushes with B and the unused bit set
+                self.push_byte(self.processor_status() | 0x30, bus, cycles)
+            }
+            CPU::PLP_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // before the sp increment
+                let (status, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+                self.set_processor_status(status);
+                Ok(cycles)
+            }
+            CPU::TSX_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_x = self.sp;
+                self.set_zero_negative_flags(self.r_x);
+                Ok(cycles)
+            }
+            CPU::TXS_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.sp = self.r_x;
+                Ok(cycles)
+            }
+            _ if self.variant.is_cmos() => {
+                self.execute_cmos(instruction, cycles, bus)
+            }
+            _ if self.undocumented_opcodes => {
+                self.execute_undocumented(instruction, cycles, bus)
+            }
+            _ => {
+                Err(EmuError::IllegalOpcode { address: self.pc.wrapping_sub(1), opcode: instruction })
+            }
+        }
+
+    }
+}
+
+pub fn build_cpu(variant: Variant) -> CPU {
+    CPU {
+        variant,
+        pc: 0xFFFC,
+        sp: 0xFF,
+        r_a: 0,
+        r_x: 0,
+        r_y: 0,
+        ps_carry: 0,
+        ps_zero: 0,
+        ps_interrupt: 0,
+        ps_decimal: 0,
+        ps_break: 0,
+        ps_overflow: 0,
+        ps_negative: 0,
+        irq_line: false,
+        nmi_line: false,
+        nmi_pending: false,
+        cycle_accurate: false,
+        strict_stack: false,
+        undocumented_opcodes: true,
+        xaa_magic: 0xEE,
+        lxa_magic: 0xEE,
+        sh_and_high_byte: true,
+        jammed: false,
+        waiting: false,
+        stopped: false,
+        tick_state: None,
+    }
+}
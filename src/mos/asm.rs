@@ -0,0 +1,823 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::opcode_table::{find_opcode, is_mnemonic, AddressingMode};
+use super::{Bus, Byte, Opcode, Word};
+
+/* ASSEMBLER */
+
+// a two-pass assembler for 6502 family source:
+//
+//   start:  LDX #<table        ; labels end in ':' or start in column 0
+//   @loop:  DEX                ; '@' labels are local to the last global label
+//           BNE @loop
+//   value = $10 + 2            ; constants
+//   * = $9000                  ; same as .org $9000
+//           .byte 1, 2, "text"
+//           .word start, * + 4 ; '*' is the address of the current line
+//           .include "io.s"    ; relative to the including file
+//
+// pass 1 sizes every line and assigns the labels, pass 2 evaluates the operands and emits
+// the bytes. An operand that is still unknown in pass 1 (a forward reference) assembles to
+// the absolute form even if it turns out to be on the zero page.
+
+static MAX_INCLUDE_DEPTH: usize = 16;
+
+// operand syntax, before the zero page / absolute choice is made
+enum Operand {
+    None,
+    Accumulator,
+    Immediate(String),
+    Direct(String),
+    DirectX(String),
+    DirectY(String),
+    Indirect(String),
+    IndirectX(String),
+    IndirectY(String),
+    BitBranch(String, String),      // BBRn / BBSn zp, target
+}
+
+/* OUTPUT */
+
+// a run of bytes assembled to consecutive addresses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub address: Word,
+    pub bytes: Vec<Byte>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assembly {
+    pub segments: Vec<Segment>,
+    pub symbols: BTreeMap<String, Word>,    // local labels are stored as global@local
+}
+
+impl Assembly {
+
+    // writes every segment through the bus
+    pub fn load<B: Bus>(&self, bus: &mut B) {
+        for segment in &self.segments {
+            for (offset, byte) in segment.bytes.iter().enumerate() {
+                bus.write(segment.address.wrapping_add(offset as Word), *byte);
+            }
+        }
+    }
+
+    pub fn symbol(&self, name: &str) -> Option<Word> {
+        self.symbols.get(name).copied()
+    }
+
+    // lowest address assembled to, 0 for an empty assembly
+    pub fn origin(&self) -> Word {
+        self.segments.iter().map(|segment| segment.address).min().unwrap_or(0)
+    }
+
+    // one flat image from origin() to the last byte, gaps between segments zero filled
+    pub fn image(&self) -> Vec<Byte> {
+        let origin: usize = self.origin() as usize;
+        let end: usize = self.segments.iter().map(|segment| segment.address as usize + segment.bytes.len()).max().unwrap_or(origin);
+        let mut image: Vec<Byte> = vec![0; end - origin];
+        for segment in &self.segments {
+            let start: usize = segment.address as usize - origin;
+            image[start..start + segment.bytes.len()].copy_from_slice(&segment.bytes);
+        }
+        image
+    }
+
+    // "name = $XXXX" lines sorted by address, for Disassembler::load_symbols. Local labels
+    // keep their global@local names, so the file does not assemble again
+    pub fn symbol_file(&self) -> String {
+        let mut symbols: Vec<(&String, &Word)> = self.symbols.iter().collect();
+        symbols.sort_by_key(|(name, address)| (**address, (*name).clone()));
+        symbols.iter().map(|(name, address)| format!("{} = ${:04X}\n", name, address)).collect()
+    }
+}
+
+/* ERRORS */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmError {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+impl Error for AsmError {}
+
+/* ENTRY POINTS */
+
+// includes are looked up relative to the working directory
+pub fn assemble(source: &str) -> Result<Assembly, AsmError> {
+    let mut lines: Vec<SourceLine> = Vec::new();
+    read_lines(source, "<source>", Path::new("."), 0, &mut lines)?;
+    assemble_lines(&lines)
+}
+
+// includes are looked up relative to the directory of `path`
+pub fn assemble_file(path: &str) -> Result<Assembly, AsmError> {
+    let source: String = fs::read_to_string(path).map_err(|err| AsmError {
+        file: path.to_string(),
+        line: 0,
+        message: err.to_string(),
+    })?;
+    let directory: &Path = Path::new(path).parent().unwrap_or(Path::new("."));
+    let mut lines: Vec<SourceLine> = Vec::new();
+    read_lines(&source, path, directory, 0, &mut lines)?;
+    assemble_lines(&lines)
+}
+
+fn assemble_lines(lines: &[SourceLine]) -> Result<Assembly, AsmError> {
+    let mut assembler: Assembler = Assembler {
+        symbols: BTreeMap::new(),
+        pc: 0,
+        line_pc: 0,
+        scope: String::new(),
+        pass: 1,
+        modes: vec![None; lines.len()],
+        pending: Vec::new(),
+        segments: Vec::new(),
+    };
+    for pass in 1..=2 {
+        assembler.pass = pass;
+        assembler.pc = 0;
+        assembler.scope.clear();
+        for (index, line) in lines.iter().enumerate() {
+            assembler.line(index, &line.text).map_err(|message| AsmError {
+                file: line.file.clone(),
+                line: line.number,
+                message,
+            })?;
+        }
+        if pass == 1 {
+            assembler.settle_constants().map_err(|(index, message)| AsmError {
+                file: lines[index].file.clone(),
+                line: lines[index].number,
+                message,
+            })?;
+        }
+    }
+    Ok(Assembly {
+        segments: assembler.segments.into_iter().filter(|segment| !segment.bytes.is_empty()).collect(),
+        symbols: assembler.symbols,
+    })
+}
+
+/* SOURCE */
+
+struct SourceLine {
+    file: String,
+    number: usize,
+    text: String,
+}
+
+// flattens .include directives into one list of lines
+fn read_lines(source: &str, file: &str, directory: &Path, depth: usize, lines: &mut Vec<SourceLine>) -> Result<(), AsmError> {
+    for (index, text) in source.lines().enumerate() {
+        let error = |message: String| AsmError {
+            file: file.to_string(),
+            line: index + 1,
+            message,
+        };
+        let statement: &str = strip_comment(text).trim();
+        if statement.len() >= 8 && statement[..8].eq_ignore_ascii_case(".include") {
+            if depth >= MAX_INCLUDE_DEPTH {
+                return Err(error("includes nested too deeply".to_string()));
+            }
+            let name: &str = statement[8..].trim().trim_matches('"');
+            let path: PathBuf = directory.join(name);
+            let path_name: String = path.to_string_lossy().into_owned();
+            let included: String = fs::read_to_string(&path).map_err(|err| error(format!("cannot include {}: {}", path_name, err)))?;
+            let included_directory: &Path = path.parent().unwrap_or(Path::new("."));
+            read_lines(&included, &path_name, included_directory, depth + 1, lines)?;
+            continue;
+        }
+        lines.push(SourceLine {
+            file: file.to_string(),
+            number: index + 1,
+            text: text.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// drops a ';' comment that is not inside a string or character literal
+fn strip_comment(text: &str) -> &str {
+    let mut quote: Option<char> = None;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ';' => return &text[..index],
+            None => {}
+        }
+    }
+    text
+}
+
+// splits on commas outside quotes and parentheses
+fn split_list(text: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut quote: Option<char> = None;
+    let mut depth: i32 = 0;
+    for c in text.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                current.push(c);
+            }
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                '(' => {
+                    depth += 1;
+                    current.push(c);
+                }
+                ')' => {
+                    depth -= 1;
+                    current.push(c);
+                }
+                ',' if depth == 0 => items.push(std::mem::take(&mut current).trim().to_string()),
+                _ => current.push(c),
+            },
+        }
+    }
+    items.push(current.trim().to_string());
+    items
+}
+
+fn is_symbol_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '@'
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn symbol_length(text: &str) -> usize {
+    let mut chars = text.char_indices();
+    match chars.next() {
+        Some((_, c)) if is_symbol_start(c) => {}
+        _ => return 0,
+    }
+    chars.find(|(_, c)| !is_symbol_char(*c)).map(|(index, _)| index).unwrap_or(text.len())
+}
+
+/* OPCODES */
+
+fn has_mode(mnemonic: &str, mode: AddressingMode) -> bool {
+    find_opcode(mnemonic, mode).is_some()
+}
+
+/* PASSES */
+
+struct Assembler {
+    symbols: BTreeMap<String, Word>,
+    pc: Word,
+    line_pc: Word,                  // value of '*': the address the current line starts at
+    scope: String,                  // last global label, owner of the '@' labels
+    pass: u8,
+    modes: Vec<Option<AddressingMode>>,       // addressing mode picked for each line in pass 1
+    pending: Vec<PendingConstant>,  // constants pass 1 could not evaluate yet
+    segments: Vec<Segment>,
+}
+
+// a constant whose expression names a symbol defined further down, kept with the
+// scope and '*' of its line so it can be evaluated again once pass 1 is done
+struct PendingConstant {
+    index: usize,
+    name: String,
+    expression: String,
+    scope: String,
+    line_pc: Word,
+}
+
+impl Assembler {
+
+    fn line(&mut self, index: usize, text: &str) -> Result<(), String> {
+        let text: &str = strip_comment(text);
+        let statement: &str = text.trim();
+        if statement.is_empty() {
+            return Ok(());
+        }
+        self.line_pc = self.pc;
+
+        // * = address
+        if let Some(rest) = statement.strip_prefix('*') {
+            if let Some(expression) = rest.trim_start().strip_prefix('=') {
+                return self.origin(expression);
+            }
+        }
+
+        // label, label: or name = value
+        let indented: bool = text.starts_with(char::is_whitespace);
+        let length: usize = symbol_length(statement);
+        let (name, rest): (&str, &str) = statement.split_at(length);
+        let statement: &str = if length > 0 && rest.starts_with(':') {
+            self.define_label(name)?;
+            rest[1..].trim()
+        } else if length > 0 && rest.trim_start().starts_with('=') {
+            return self.define_constant(index, name, &rest.trim_start()[1..]);
+        } else if length > 0 && !indented && !is_mnemonic(name) {
+            self.define_label(name)?;
+            rest.trim()
+        } else {
+            statement
+        };
+        if statement.is_empty() {
+            return Ok(());
+        }
+
+        if statement.starts_with('.') {
+            return self.directive(statement);
+        }
+        self.instruction(index, statement)
+    }
+
+    /* symbols */
+
+    fn qualify(&self, name: &str) -> String {
+        match name.strip_prefix('@') {
+            Some(local) => format!("{}@{}", self.scope, local),
+            None => name.to_string(),
+        }
+    }
+
+    fn define_label(&mut self, name: &str) -> Result<(), String> {
+        if !name.starts_with('@') {
+            self.scope = name.to_string();
+        }
+        let name: String = self.qualify(name);
+        if self.pass == 1 && self.symbols.insert(name.clone(), self.pc).is_some() {
+            return Err(format!("symbol {} defined twice", name));
+        }
+        Ok(())
+    }
+
+    // constants are evaluated again in pass 2 so they may use forward references
+    fn define_constant(&mut self, index: usize, name: &str, expression: &str) -> Result<(), String> {
+        let name: String = self.qualify(name);
+        if self.pass == 1 && self.symbols.contains_key(&name) {
+            return Err(format!("symbol {} defined twice", name));
+        }
+        match self.evaluate(expression)? {
+            Some(value) => {
+                self.symbols.insert(name, word(value)?);
+            }
+            None => self.pending.push(PendingConstant {
+                index,
+                name,
+                expression: expression.to_string(),
+                scope: self.scope.clone(),
+                line_pc: self.line_pc,
+            }),
+        }
+        Ok(())
+    }
+
+    // evaluates the pending constants until no more of them resolve, so pass 2 finds
+    // a constant even where it is used above its definition. Whatever is left names a
+    // symbol that is never defined, or itself, and fails on its own line
+    fn settle_constants(&mut self) -> Result<(), (usize, String)> {
+        let mut progress: bool = true;
+        while progress {
+            progress = false;
+            for constant in std::mem::take(&mut self.pending) {
+                self.scope = constant.scope.clone();
+                self.line_pc = constant.line_pc;
+                match self.evaluate(&constant.expression).map_err(|message| (constant.index, message))? {
+                    Some(value) => {
+                        self.symbols.insert(constant.name.clone(), word(value).map_err(|message| (constant.index, message))?);
+                        progress = true;
+                    }
+                    None => self.pending.push(constant),
+                }
+            }
+        }
+        if let Some(constant) = self.pending.first() {
+            // evaluated with the pass 2 rules the missing symbol becomes the error
+            self.pass = 2;
+            self.scope = constant.scope.clone();
+            self.line_pc = constant.line_pc;
+            let message: String = self.evaluate(&constant.expression).err().unwrap_or_else(|| format!("undefined symbol in {}", constant.name));
+            return Err((constant.index, message));
+        }
+        Ok(())
+    }
+
+    /* output */
+
+    fn origin(&mut self, expression: &str) -> Result<(), String> {
+        match self.evaluate(expression)? {
+            Some(value) => {
+                self.pc = word(value)?;
+                Ok(())
+            }
+            None => Err("origin must not use forward references".to_string()),
+        }
+    }
+
+    fn emit(&mut self, bytes: &[Byte]) {
+        if self.pass == 2 {
+            let contiguous: bool = match self.segments.last() {
+                Some(segment) => segment.address.wrapping_add(segment.bytes.len() as Word) == self.pc,
+                None => false,
+            };
+            if !contiguous {
+                self.segments.push(Segment {
+                    address: self.pc,
+                    bytes: Vec::new(),
+                });
+            }
+            self.segments.last_mut().unwrap().bytes.extend_from_slice(bytes);
+        }
+        self.pc = self.pc.wrapping_add(bytes.len() as Word);
+    }
+
+    /* directives */
+
+    fn directive(&mut self, statement: &str) -> Result<(), String> {
+        let length: usize = statement.find(char::is_whitespace).unwrap_or(statement.len());
+        let (directive, arguments): (&str, &str) = statement.split_at(length);
+        let arguments: &str = arguments.trim();
+        match directive.to_ascii_lowercase().as_str() {
+            ".org" => self.origin(arguments),
+            ".byte" | ".db" | ".text" => {
+                for item in split_list(arguments) {
+                    if let Some(text) = item.strip_prefix('"') {
+                        let text: &str = text.strip_suffix('"').ok_or(format!("unterminated string {}", item))?;
+                        self.emit(text.as_bytes());
+                        continue;
+                    }
+                    let value: i32 = self.evaluate(&item)?.unwrap_or(0);
+                    self.emit(&[byte(value)?]);
+                }
+                Ok(())
+            }
+            ".word" | ".dw" => {
+                for item in split_list(arguments) {
+                    let value: Word = word(self.evaluate(&item)?.unwrap_or(0))?;
+                    self.emit(&value.to_le_bytes());
+                }
+                Ok(())
+            }
+            _ => Err(format!("unknown directive {}", directive)),
+        }
+    }
+
+    /* instructions */
+
+    fn instruction(&mut self, index: usize, statement: &str) -> Result<(), String> {
+        let length: usize = statement.find(char::is_whitespace).unwrap_or(statement.len());
+        let (mnemonic, operand): (&str, &str) = statement.split_at(length);
+        if !is_mnemonic(mnemonic) {
+            return Err(format!("unknown instruction {}", mnemonic));
+        }
+        let operand: Operand = parse_operand(operand);
+
+        let mode: AddressingMode = match self.modes[index] {
+            Some(mode) => mode,
+            None => {
+                let mode: AddressingMode = self.pick_mode(mnemonic, &operand)?;
+                self.modes[index] = Some(mode);
+                mode
+            }
+        };
+        let opcode: Opcode = find_opcode(mnemonic, mode).ok_or(format!("{} does not support this addressing mode", mnemonic))?;
+        let pc: Word = self.pc;
+        let end: i32 = pc as i32 + 1 + mode.operand_size() as i32;
+
+        match operand {
+            Operand::None | Operand::Accumulator => self.emit(&[opcode]),
+            Operand::BitBranch(zero_page, target) => {
+                let zero_page: i32 = self.evaluate(&zero_page)?.unwrap_or(0);
+                let offset: Byte = self.branch_offset(&target, end)?;
+                self.emit(&[opcode, byte(zero_page)?, offset]);
+            }
+            Operand::Immediate(expression)
+            | Operand::Direct(expression)
+            | Operand::DirectX(expression)
+            | Operand::DirectY(expression)
+            | Operand::Indirect(expression)
+            | Operand::IndirectX(expression)
+            | Operand::IndirectY(expression) => match mode {
+                AddressingMode::Relative => {
+                    let offset: Byte = self.branch_offset(&expression, end)?;
+                    self.emit(&[opcode, offset]);
+                }
+                _ if mode.operand_size() == 1 => {
+                    let value: i32 = self.evaluate(&expression)?.unwrap_or(0);
+                    let value: Byte = match mode {
+                        AddressingMode::Immediate => byte(value)?,
+                        _ => zero_page(value)?,
+                    };
+                    self.emit(&[opcode, value]);
+                }
+                _ => {
+                    let value: Word = word(self.evaluate(&expression)?.unwrap_or(0))?;
+                    let [lo_byte, hi_byte] = value.to_le_bytes();
+                    self.emit(&[opcode, lo_byte, hi_byte]);
+                }
+            },
+        }
+        Ok(())
+    }
+
+    // zero page forms win when the operand is already known to fit
+    fn pick_mode(&self, mnemonic: &str, operand: &Operand) -> Result<AddressingMode, String> {
+        let sized = |expression: &str, zero_page: AddressingMode, absolute: AddressingMode| -> Result<AddressingMode, String> {
+            let fits: bool = matches!(self.evaluate(expression)?, Some(value) if (0..=0xFF).contains(&value));
+            if has_mode(mnemonic, zero_page) && (fits || !has_mode(mnemonic, absolute)) {
+                return Ok(zero_page);
+            }
+            Ok(absolute)
+        };
+        match operand {
+            Operand::None if !has_mode(mnemonic, AddressingMode::Implied) => Ok(AddressingMode::Accumulator),
+            Operand::None => Ok(AddressingMode::Implied),
+            Operand::Accumulator => Ok(AddressingMode::Accumulator),
+            Operand::Immediate(_) => Ok(AddressingMode::Immediate),
+            Operand::Direct(_) if has_mode(mnemonic, AddressingMode::Relative) => Ok(AddressingMode::Relative),
+            Operand::Direct(expression) => sized(expression, AddressingMode::ZeroPage, AddressingMode::Absolute),
+            Operand::DirectX(expression) => sized(expression, AddressingMode::ZeroPageX, AddressingMode::AbsoluteX),
+            Operand::DirectY(expression) => sized(expression, AddressingMode::ZeroPageY, AddressingMode::AbsoluteY),
+            Operand::Indirect(_) if has_mode(mnemonic, AddressingMode::Indirect) => Ok(AddressingMode::Indirect),
+            Operand::Indirect(_) => Ok(AddressingMode::ZeroPageIndirect),
+            Operand::IndirectX(_) if has_mode(mnemonic, AddressingMode::AbsoluteXIndirect) => Ok(AddressingMode::AbsoluteXIndirect),
+            Operand::IndirectX(_) => Ok(AddressingMode::IndirectX),
+            Operand::IndirectY(_) => Ok(AddressingMode::IndirectY),
+            Operand::BitBranch(_, _) => Ok(AddressingMode::ZeroPageRelative),
+        }
+    }
+
+    // offset from the end of the instruction, range checked in pass 2
+    fn branch_offset(&self, target: &str, end: i32) -> Result<Byte, String> {
+        let target: i32 = match self.evaluate(target)? {
+            Some(target) => target,
+            None => return Ok(0),
+        };
+        let offset: i32 = target - end;
+        if !(-128..=127).contains(&offset) {
+            return Err(format!("branch target out of range ({} bytes)", offset));
+        }
+        Ok(offset as i8 as Byte)
+    }
+
+    /* expressions */
+
+    // None while a symbol is still undefined in pass 1
+    fn evaluate(&self, text: &str) -> Result<Option<i32>, String> {
+        let mut expression: Expression = Expression {
+            assembler: self,
+            chars: text.chars().collect(),
+            position: 0,
+        };
+        let value: Option<i32> = expression.binary(0)?;
+        expression.skip_whitespace();
+        if expression.position < expression.chars.len() {
+            return Err(format!("unexpected '{}' in expression {}", expression.chars[expression.position], text.trim()));
+        }
+        Ok(value)
+    }
+}
+
+fn parse_operand(text: &str) -> Operand {
+    // whitespace outside of literals carries no meaning in an operand
+    let mut operand: String = String::new();
+    let mut quote: Option<char> = None;
+    for c in text.trim().chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c.is_whitespace() => continue,
+            None => {}
+        }
+        operand.push(c);
+    }
+    let upper: String = operand.to_ascii_uppercase();
+
+    if operand.is_empty() {
+        return Operand::None;
+    }
+    if upper == "A" {
+        return Operand::Accumulator;
+    }
+    if let Some(value) = operand.strip_prefix('#') {
+        return Operand::Immediate(value.to_string());
+    }
+    if operand.starts_with('(') {
+        if upper.ends_with(",X)") {
+            return Operand::IndirectX(operand[1..operand.len() - 3].to_string());
+        }
+        if upper.ends_with("),Y") {
+            return Operand::IndirectY(operand[1..operand.len() - 3].to_string());
+        }
+        // "(a)" is indirect, "(a)+(b)" is just an expression
+        if operand.ends_with(')') && closing_parenthesis(&operand) == operand.len() - 1 {
+            return Operand::Indirect(operand[1..operand.len() - 1].to_string());
+        }
+    }
+    if upper.ends_with(",X") {
+        return Operand::DirectX(operand[..operand.len() - 2].to_string());
+    }
+    if upper.ends_with(",Y") {
+        return Operand::DirectY(operand[..operand.len() - 2].to_string());
+    }
+    let items: Vec<String> = split_list(&operand);
+    if items.len() == 2 {
+        return Operand::BitBranch(items[0].clone(), items[1].clone());
+    }
+    Operand::Direct(operand)
+}
+
+// index of the parenthesis closing the one at index 0
+fn closing_parenthesis(text: &str) -> usize {
+    let mut depth: i32 = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn byte(value: i32) -> Result<Byte, String> {
+    match value {
+        -128..=255 => Ok(value as Byte),
+        _ => Err(format!("value {} does not fit in a byte", value)),
+    }
+}
+
+fn zero_page(value: i32) -> Result<Byte, String> {
+    match value {
+        0..=255 => Ok(value as Byte),
+        _ => Err(format!("address {:#06x} is not on the zero page", value)),
+    }
+}
+
+fn word(value: i32) -> Result<Word, String> {
+    match value {
+        -32768..=65535 => Ok(value as Word),
+        _ => Err(format!("value {} does not fit in a word", value)),
+    }
+}
+
+/* EXPRESSIONS */
+
+// precedence climbing over | ^ & << >> + - * / %, with unary - ~ < > and parentheses
+struct Expression<'a> {
+    assembler: &'a Assembler,
+    chars: Vec<char>,
+    position: usize,
+}
+
+static BINARY_OPERATORS: [(&str, u8); 10] = [
+    ("|", 1), ("^", 2), ("&", 3), ("<<", 4), (">>", 4), ("+", 5), ("-", 5), ("*", 6), ("/", 6), ("%", 6),
+];
+
+impl Expression<'_> {
+
+    fn skip_whitespace(&mut self) {
+        while self.position < self.chars.len() && self.chars[self.position].is_whitespace() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn looking_at(&self, token: &str) -> bool {
+        token.chars().enumerate().all(|(offset, c)| self.chars.get(self.position + offset) == Some(&c))
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Option<i32>, String> {
+        let mut left: Option<i32> = self.unary()?;
+        loop {
+            self.skip_whitespace();
+            let operator = BINARY_OPERATORS.iter().find(|(token, precedence)| *precedence > min_precedence && self.looking_at(token));
+            let (token, precedence): (&str, u8) = match operator {
+                Some((token, precedence)) => (token, *precedence),
+                None => return Ok(left),
+            };
+            self.position += token.len();
+            let right: Option<i32> = self.binary(precedence)?;
+            left = match (left, right) {
+                (Some(left), Some(right)) => Some(apply(token, left, right)?),
+                _ => None,
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<Option<i32>, String> {
+        self.skip_whitespace();
+        let operator: char = match self.peek() {
+            Some(c) if "-~<>".contains(c) => c,
+            _ => return self.primary(),
+        };
+        self.position += 1;
+        let value: Option<i32> = self.unary()?;
+        Ok(value.map(|value| match operator {
+            '-' => -value,
+            '~' => !value,
+            '<' => value & 0xFF,
+            _ => (value >> 8) & 0xFF,
+        }))
+    }
+
+    fn primary(&mut self) -> Result<Option<i32>, String> {
+        self.skip_whitespace();
+        let start: usize = self.position;
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let value: Option<i32> = self.binary(0)?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    return Err("missing ')' in expression".to_string());
+                }
+                self.position += 1;
+                Ok(value)
+            }
+            Some('*') => {
+                self.position += 1;
+                Ok(Some(self.assembler.line_pc as i32))
+            }
+            Some('\'') => {
+                let value: char = *self.chars.get(start + 1).ok_or("unterminated character literal")?;
+                if self.chars.get(start + 2) != Some(&'\'') {
+                    return Err("unterminated character literal".to_string());
+                }
+                self.position += 3;
+                Ok(Some(value as i32))
+            }
+            Some('$') => self.number(16, 1),
+            Some('%') => self.number(2, 1),
+            Some('0') if self.looking_at("0x") || self.looking_at("0X") => self.number(16, 2),
+            Some(c) if c.is_ascii_digit() => self.number(10, 0),
+            Some(c) if is_symbol_start(c) => {
+                self.position += 1;
+                while self.peek().is_some_and(is_symbol_char) {
+                    self.position += 1;
+                }
+                let name: String = self.chars[start..self.position].iter().collect();
+                let name: String = self.assembler.qualify(&name);
+                match self.assembler.symbols.get(&name) {
+                    Some(value) => Ok(Some(*value as i32)),
+                    None if self.assembler.pass == 1 => Ok(None),
+                    None => Err(format!("undefined symbol {}", name)),
+                }
+            }
+            Some(c) => Err(format!("unexpected '{}' in expression", c)),
+            None => Err("missing operand".to_string()),
+        }
+    }
+
+    fn number(&mut self, radix: u32, prefix: usize) -> Result<Option<i32>, String> {
+        self.position += prefix;
+        let start: usize = self.position;
+        while self.peek().is_some_and(|c| c.is_digit(radix)) {
+            self.position += 1;
+        }
+        let digits: String = self.chars[start..self.position].iter().collect();
+        i32::from_str_radix(&digits, radix).map(Some).map_err(|_| format!("bad number '{}'", digits))
+    }
+}
+
+fn apply(operator: &str, left: i32, right: i32) -> Result<i32, String> {
+    Ok(match operator {
+        "|" => left | right,
+        "^" => left ^ right,
+        "&" => left & right,
+        "<<" => left.wrapping_shl(right as u32),
+        ">>" => left.wrapping_shr(right as u32),
+        "+" => left.wrapping_add(right),
+        "-" => left.wrapping_sub(right),
+        "*" => left.wrapping_mul(right),
+        _ if right == 0 => return Err("division by zero".to_string()),
+        "/" => left / right,
+        _ => left % right,
+    })
+}
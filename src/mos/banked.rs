@@ -0,0 +1,162 @@
+use super::{Bus, Byte, Word};
+
+/* BANKED MEMORY */
+
+// a bank switching layer on top of another bus: windows in the address space show one
+// bank of a ROM or RAM store that can be much larger than 64 KiB, and writes to the
+// mapper's control registers pick which bank each window shows
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum BankSource {
+    Rom,                            // read only
+    Ram,                            // battery / work RAM, writable
+}
+
+struct Window {
+    start: Word,
+    size: usize,
+    source: BankSource,
+    bank: usize,
+}
+
+// writes to start..=end select the bank shown in `window`
+struct BankRegister {
+    start: Word,
+    end: Word,
+    window: usize,
+}
+
+pub struct BankedMemory<B: Bus> {
+    pub inner: B,                   // everything outside the windows
+    rom: Vec<Byte>,
+    ram: Vec<Byte>,
+    windows: Vec<Window>,
+    registers: Vec<BankRegister>,
+}
+
+pub fn build_banked_memory<B: Bus>(inner: B, rom: Vec<Byte>) -> BankedMemory<B> {
+    BankedMemory {
+        inner,
+        rom,
+        ram: Vec::new(),
+        windows: Vec::new(),
+        registers: Vec::new(),
+    }
+}
+
+// UxROM style: 16 KiB switchable at 0x8000, the last 16 KiB fixed at 0xC000,
+// any write to 0x8000-0xFFFF selects the switchable bank
+pub fn build_switchable_16k<B: Bus>(inner: B, rom: Vec<Byte>) -> BankedMemory<B> {
+    let last_bank: usize = (rom.len() / 0x4000).saturating_sub(1);
+    build_banked_memory(inner, rom)
+        .window(0x8000, 0x4000, BankSource::Rom, 0)
+        .window(0xC000, 0x4000, BankSource::Rom, last_bank)
+        .register(0x8000, 0xFFFF, 0)
+}
+
+// four independently switched 8 KiB windows at 0x8000, 0xA000, 0xC000 and 0xE000,
+// writing registers[n] selects the bank shown in window n
+pub fn build_8k_x4<B: Bus>(inner: B, rom: Vec<Byte>, registers: [Word; 4]) -> BankedMemory<B> {
+    let mut banked: BankedMemory<B> = build_banked_memory(inner, rom);
+    for (window, register) in registers.iter().enumerate() {
+        banked = banked
+            .window(0x8000 + (window as Word) * 0x2000, 0x2000, BankSource::Rom, window)
+            .register(*register, *register, window);
+    }
+    banked
+}
+
+impl<B: Bus> BankedMemory<B> {
+
+    // backing store for BankSource::Ram windows
+    pub fn ram(mut self, size: usize) -> Self {
+        self.ram = vec![0; size];
+        self
+    }
+
+    pub fn window(mut self, start: Word, size: usize, source: BankSource, bank: usize) -> Self {
+        assert!(size > 0 && start as usize + size <= 0x10000, "bank window does not fit in the address space");
+        self.windows.push(Window { start, size, source, bank });
+        self
+    }
+
+    // generic register-to-bank table entry
+    pub fn register(mut self, start: Word, end: Word, window: usize) -> Self {
+        assert!(window < self.windows.len(), "bank register refers to a missing window");
+        self.registers.push(BankRegister { start, end, window });
+        self
+    }
+
+    pub fn bank(&self, window: usize) -> usize {
+        self.windows[window].bank
+    }
+
+    // out of range bank numbers wrap around, like a mapper ignoring the unused high bits
+    pub fn select_bank(&mut self, window: usize, bank: usize) {
+        let bank_count: usize = self.bank_count(window);
+        self.windows[window].bank = if bank_count == 0 { 0 } else { bank % bank_count };
+    }
+
+    pub fn bank_count(&self, window: usize) -> usize {
+        let window: &Window = &self.windows[window];
+        match window.source {
+            BankSource::Rom => self.rom.len() / window.size,
+            BankSource::Ram => self.ram.len() / window.size,
+        }
+    }
+
+    // window index and offset into its store for an address, if a window covers it
+    fn resolve(&self, address: Word) -> Option<(usize, usize)> {
+        self.windows.iter().position(|window| {
+            address >= window.start && (address as usize) < window.start as usize + window.size
+        }).map(|index| {
+            let window: &Window = &self.windows[index];
+            (index, window.bank * window.size + (address - window.start) as usize)
+        })
+    }
+
+    fn store(&self, window: usize) -> &Vec<Byte> {
+        match self.windows[window].source {
+            BankSource::Rom => &self.rom,
+            BankSource::Ram => &self.ram,
+        }
+    }
+}
+
+impl<B: Bus> Bus for BankedMemory<B> {
+    fn read(&mut self, address: Word) -> Byte {
+        match self.resolve(address) {
+            Some((window, offset)) => self.store(window).get(offset).copied().unwrap_or(0xFF),
+            None => self.inner.read(address),
+        }
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        let selected: Option<usize> = self.registers.iter()
+            .find(|register| address >= register.start && address <= register.end)
+            .map(|register| register.window);
+        if let Some(window) = selected {
+            self.select_bank(window, value as usize);
+            return;
+        }
+        match self.resolve(address) {
+            Some((window, offset)) => {
+                if self.windows[window].source == BankSource::Ram && offset < self.ram.len() {
+                    self.ram[offset] = value;
+                }
+            }
+            None => self.inner.write(address, value),
+        }
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        match self.resolve(address) {
+            Some((window, offset)) => self.store(window).get(offset).copied().unwrap_or(0xFF),
+            None => self.inner.peek(address),
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<Word> {
+        self.inner.take_fault()
+    }
+}
@@ -0,0 +1,274 @@
+use super::{Access, Bus, Byte, EmuError, Opcode, Opcodes, Word, CPU, STACK_PAGE};
+
+/* 65C02 INSTRUCTIONS */
+
+// the CMOS parts fill most of the NMOS holes with new instructions and turn the rest into
+// NOPs of fixed size and timing, so nothing is left undefined. The changed behaviour of
+// documented opcodes (JMP indirect, decimal flags, read-modify-write timing) lives with
+// those opcodes in execute_instruction.
+
+impl CPU {
+
+    // TSB: Z from A & M, then set the bits of A in M
+    fn test_and_set(&mut self, value: Byte) -> Byte {
+        self.ps_zero = ((self.r_a & value) == 0) as Byte;
+        value | self.r_a
+    }
+
+    // TRB: Z from A & M, then clear the bits of A in M
+    fn test_and_reset(&mut self, value: Byte) -> Byte {
+        self.ps_zero = ((self.r_a & value) == 0) as Byte;
+        value & !self.r_a
+    }
+
+    // (zp): the pointer is read from the zero page without any index
+    fn addr_zero_page_indirect<B: Bus>(&mut self, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (zero_page_address, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        CPU::read_word_zero_page(zero_page_address, bus, cycles)
+    }
+
+    pub(super) fn execute_cmos<B: Bus>(&mut self, instruction: Opcode, cycles: i32, bus: &mut B) -> Result<i32, EmuError> {
+
+        match instruction {
+
+            // BRA
+            CPU::BRA_RELATIVE => {
+                Ok(self.branch(true, bus, cycles))
+            }
+
+            // X / Y stack operations
+            CPU::PHX_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.push_byte(self.r_x, bus, cycles)
+            }
+            CPU::PHY_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.push_byte(self.r_y, bus, cycles)
+            }
+            CPU::PLX_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // before the sp increment
+                let (value, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+                self.r_x = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::PLY_IMPLIED => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(STACK_PAGE | self.sp as Word, bus, cycles); // before the sp increment
+                let (value, cycles): (Byte, i32) = self.pull_byte(bus, cycles)?;
+                self.r_y = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+
+            // STZ
+            CPU::STZ_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(CPU::write_byte(0x00, address, bus, cycles))
+            }
+            CPU::STZ_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(CPU::write_byte(0x00, address, bus, cycles))
+            }
+            CPU::STZ_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(CPU::write_byte(0x00, address, bus, cycles))
+            }
+            CPU::STZ_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(CPU::write_byte(0x00, address, bus, cycles))
+            }
+
+            // TSB / TRB
+            CPU::TSB_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::test_and_set))
+            }
+            CPU::TSB_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::test_and_set))
+            }
+            CPU::TRB_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::test_and_reset))
+            }
+            CPU::TRB_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::test_and_reset))
+            }
+
+            // (zp) addressing
+            CPU::ORA_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::AND_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.add_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+            CPU::STA_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                Ok(CPU::write_byte(self.r_a, address, bus, cycles))
+            }
+            CPU::LDA_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.r_a = value;
+                self.set_zero_negative_flags(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.compare(self.r_a, value);
+                Ok(cycles)
+            }
+            CPU::SBC_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indirect(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(self.decimal_cycle(bus, cycles))
+            }
+
+            // INC A / DEC A
+            CPU::INC_ACCUMULATOR => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = self.increment(self.r_a);
+                Ok(cycles)
+            }
+            CPU::DEC_ACCUMULATOR => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.r_a = self.decrement(self.r_a);
+                Ok(cycles)
+            }
+
+            // BIT
+            CPU::BIT_IMMEDIATE => {
+                // there is no memory operand to copy N and V from, only Z changes
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.ps_zero = ((self.r_a & value) == 0) as Byte;
+                Ok(cycles)
+            }
+            CPU::BIT_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+            CPU::BIT_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+
+            // JMP (abs,X)
+            CPU::JMP_ABSOLUTE_X_INDIRECT => {
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = self.dummy_read(self.pc.wrapping_sub(1), bus, cycles);
+                let (address, cycles): (Word, i32) = CPU::read_word(pointer.wrapping_add(self.r_x as Word), bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+
+            // Rockwell bit instructions, the bit number sits in bits 4-6 of the opcode
+            CPU::RMB0_ZERO_PAGE | CPU::RMB1_ZERO_PAGE | CPU::RMB2_ZERO_PAGE | CPU::RMB3_ZERO_PAGE
+            | CPU::RMB4_ZERO_PAGE | CPU::RMB5_ZERO_PAGE | CPU::RMB6_ZERO_PAGE | CPU::RMB7_ZERO_PAGE
+            | CPU::SMB0_ZERO_PAGE | CPU::SMB1_ZERO_PAGE | CPU::SMB2_ZERO_PAGE | CPU::SMB3_ZERO_PAGE
+            | CPU::SMB4_ZERO_PAGE | CPU::SMB5_ZERO_PAGE | CPU::SMB6_ZERO_PAGE | CPU::SMB7_ZERO_PAGE
+                if self.variant.has_bit_instructions() => {
+                let bit: Byte = 1 << ((instruction >> 4) & 0x07);
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                let cycles: i32 = self.dummy_read(address, bus, cycles);
+                let result: Byte = match instruction & 0x80 {
+                    0 => value & !bit,
+                    _ => value | bit,
+                };
+                Ok(CPU::write_byte(result, address, bus, cycles))
+            }
+            CPU::BBR0_ZERO_PAGE_RELATIVE | CPU::BBR1_ZERO_PAGE_RELATIVE | CPU::BBR2_ZERO_PAGE_RELATIVE | CPU::BBR3_ZERO_PAGE_RELATIVE
+            | CPU::BBR4_ZERO_PAGE_RELATIVE | CPU::BBR5_ZERO_PAGE_RELATIVE | CPU::BBR6_ZERO_PAGE_RELATIVE | CPU::BBR7_ZERO_PAGE_RELATIVE
+            | CPU::BBS0_ZERO_PAGE_RELATIVE | CPU::BBS1_ZERO_PAGE_RELATIVE | CPU::BBS2_ZERO_PAGE_RELATIVE | CPU::BBS3_ZERO_PAGE_RELATIVE
+            | CPU::BBS4_ZERO_PAGE_RELATIVE | CPU::BBS5_ZERO_PAGE_RELATIVE | CPU::BBS6_ZERO_PAGE_RELATIVE | CPU::BBS7_ZERO_PAGE_RELATIVE
+                if self.variant.has_bit_instructions() => {
+                let bit: Byte = 1 << ((instruction >> 4) & 0x07);
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                let cycles: i32 = self.dummy_read(address, bus, cycles);
+                let bit_set: bool = (value & bit) != 0;
+                Ok(self.branch(bit_set == ((instruction & 0x80) != 0), bus, cycles))
+            }
+
+            // WDC WAI / STP
+            CPU::WAI_IMPLIED if self.variant.has_wait_and_stop() => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.waiting = true;
+                Ok(cycles)
+            }
+            CPU::STP_IMPLIED if self.variant.has_wait_and_stop() => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                self.pc = self.pc.wrapping_sub(1);
+                self.stopped = true;
+                Ok(cycles)
+            }
+
+            // the remaining opcodes are NOPs, their size and timing follow the NMOS decode
+            0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => {
+                let (_value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                Ok(cycles)
+            }
+            0x44 => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+            0x54 | 0xD4 | 0xF4 => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+            0xDC | 0xFC => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+            0x5C => {
+                // eight cycles, the spare ones spent re-reading the operand address
+                let (address, mut cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                for _ in 0..5 {
+                    cycles = self.dummy_read(address, bus, cycles);
+                }
+                Ok(cycles)
+            }
+            // columns 3, 7, B and F left over are single cycle NOPs
+            _ if (instruction & 0x03) == 0x03 => {
+                Ok(cycles)
+            }
+
+            _ => {
+                Err(EmuError::IllegalOpcode { address: self.pc.wrapping_sub(1), opcode: instruction })
+            }
+        }
+
+    }
+}
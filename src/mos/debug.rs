@@ -0,0 +1,234 @@
+use std::collections::BTreeSet;
+
+use super::opcode_table::opcode_info;
+use super::{Access, Bus, BusCycle, Byte, EmuError, Word, CPU};
+
+/* BREAKPOINTS */
+
+// what a debugger frontend can ask the run loop to stop on. PC breakpoints, opcode breaks
+// and conditions are checked before each instruction, except the first one of a run so that
+// continuing from a breakpoint moves on. Opcode breaks wait while an interrupt is about to
+// be taken, the opcode at pc does not run next then. Watchpoints look at every bus access,
+// including the dummy accesses of cycle accurate mode, and stop once the accessing
+// instruction is done.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    A,
+    X,
+    Y,
+    SP,
+    PC,
+    P,                              // whole status byte, as pushed by PHP
+    Carry,
+    Zero,
+    Interrupt,
+    Decimal,
+    Overflow,
+    Negative,
+}
+
+impl Register {
+    pub fn value(self, cpu: &CPU) -> Word {
+        match self {
+            Register::A => cpu.r_a as Word,
+            Register::X => cpu.r_x as Word,
+            Register::Y => cpu.r_y as Word,
+            Register::SP => cpu.sp as Word,
+            Register::PC => cpu.pc,
+            Register::P => cpu.processor_status() as Word,
+            Register::Carry => cpu.ps_carry as Word,
+            Register::Zero => cpu.ps_zero as Word,
+            Register::Interrupt => cpu.ps_interrupt as Word,
+            Register::Decimal => cpu.ps_decimal as Word,
+            Register::Overflow => cpu.ps_overflow as Word,
+            Register::Negative => cpu.ps_negative as Word,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+// "A == $00", "Carry == 1", "SP < $10"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Condition {
+    pub register: Register,
+    pub comparison: Comparison,
+    pub value: Word,
+}
+
+impl Condition {
+    pub fn holds(&self, cpu: &CPU) -> bool {
+        let current: Word = self.register.value(cpu);
+        match self.comparison {
+            Comparison::Equal => current == self.value,
+            Comparison::NotEqual => current != self.value,
+            Comparison::Less => current < self.value,
+            Comparison::LessOrEqual => current <= self.value,
+            Comparison::Greater => current > self.value,
+            Comparison::GreaterOrEqual => current >= self.value,
+        }
+    }
+}
+
+// stops on reads and/or writes anywhere in start..=end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub start: Word,
+    pub end: Word,
+    pub read: bool,
+    pub write: bool,
+}
+
+impl Watchpoint {
+    fn matches(&self, cycle: &BusCycle) -> bool {
+        let watched: bool = match cycle.access {
+            Access::Read => self.read,
+            Access::Write => self.write,
+        };
+        watched && (self.start..=self.end).contains(&cycle.address)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Breakpoint { address: Word },                   // pc reached a breakpoint
+    Watchpoint { pc: Word, cycle: BusCycle },       // the instruction at pc touched a watched address
+    Opcode { address: Word, opcode: Byte },         // about to execute a watched or illegal opcode
+    Condition { address: Word, index: usize },      // conditions[index] holds with pc at address
+}
+
+pub struct Breakpoints {
+    pub addresses: BTreeSet<Word>,
+    pub watchpoints: Vec<Watchpoint>,
+    pub opcodes: BTreeSet<Byte>,
+    pub illegal_opcodes: bool,      // stop on any opcode undocumented on the cpu variant
+    pub conditions: Vec<Condition>,
+}
+
+pub fn build_breakpoints() -> Breakpoints {
+    Breakpoints {
+        addresses: BTreeSet::new(),
+        watchpoints: Vec::new(),
+        opcodes: BTreeSet::new(),
+        illegal_opcodes: false,
+        conditions: Vec::new(),
+    }
+}
+
+impl Breakpoints {
+
+    pub fn add_breakpoint(&mut self, address: Word) {
+        self.addresses.insert(address);
+    }
+
+    // true when there was a breakpoint at the address
+    pub fn remove_breakpoint(&mut self, address: Word) -> bool {
+        self.addresses.remove(&address)
+    }
+
+    pub fn watch(&mut self, start: Word, end: Word, read: bool, write: bool) {
+        self.watchpoints.push(Watchpoint { start, end, read, write });
+    }
+
+    pub fn break_on_opcode(&mut self, opcode: Byte) {
+        self.opcodes.insert(opcode);
+    }
+
+    pub fn add_condition(&mut self, condition: Condition) {
+        self.conditions.push(condition);
+    }
+
+    pub fn clear(&mut self) {
+        *self = build_breakpoints();
+    }
+
+    // the reason to stop before executing the instruction at pc, if any
+    fn check<B: Bus>(&self, cpu: &CPU, bus: &B) -> Option<StopReason> {
+        let address: Word = cpu.pc;
+        if self.addresses.contains(&address) {
+            return Some(StopReason::Breakpoint { address });
+        }
+        let opcode: Byte = bus.peek(address);
+        let watched: bool = self.opcodes.contains(&opcode) || (self.illegal_opcodes && !opcode_info(cpu.variant, opcode).documented);
+        if watched && !cpu.interrupt_pending() {
+            return Some(StopReason::Opcode { address, opcode });
+        }
+        self.conditions.iter().position(|condition| condition.holds(cpu))
+            .map(|index| StopReason::Condition { address, index })
+    }
+}
+
+// passes accesses through and remembers the first one a watchpoint matches
+struct WatchBus<'a, B: Bus> {
+    inner: &'a mut B,
+    watchpoints: &'a [Watchpoint],
+    hit: Option<BusCycle>,
+}
+
+impl<B: Bus> WatchBus<'_, B> {
+    fn record(&mut self, cycle: BusCycle) {
+        if self.hit.is_none() && self.watchpoints.iter().any(|watchpoint| watchpoint.matches(&cycle)) {
+            self.hit = Some(cycle);
+        }
+    }
+}
+
+impl<B: Bus> Bus for WatchBus<'_, B> {
+    fn read(&mut self, address: Word) -> Byte {
+        let value: Byte = self.inner.read(address);
+        self.record(BusCycle { address, value, access: Access::Read });
+        value
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        self.record(BusCycle { address, value, access: Access::Write });
+        self.inner.write(address, value);
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        self.inner.peek(address)
+    }
+
+    fn take_fault(&mut self) -> Option<Word> {
+        self.inner.take_fault()
+    }
+}
+
+impl CPU {
+
+    // runs until one of the breakpoints stops the cpu and returns why, errors and an
+    // exhausted cycle budget end the run the same way they end run_until_trap
+    pub fn run_until_break<B: Bus>(&mut self, bus: &mut B, breakpoints: &Breakpoints, max_cycles: u64) -> Result<StopReason, EmuError> {
+        let mut total_cycles: u64 = 0;
+        let mut first: bool = true;
+        while total_cycles < max_cycles {
+            if !first {
+                if let Some(reason) = breakpoints.check(self, bus) {
+                    return Ok(reason);
+                }
+            }
+            first = false;
+
+            let pc: Word = self.pc;
+            if breakpoints.watchpoints.is_empty() {
+                total_cycles += self.step(bus)? as u64;
+                continue;
+            }
+            let mut watch_bus = WatchBus { inner: &mut *bus, watchpoints: &breakpoints.watchpoints, hit: None };
+            total_cycles += self.step(&mut watch_bus)? as u64;
+            if let Some(cycle) = watch_bus.hit {
+                return Ok(StopReason::Watchpoint { pc, cycle });
+            }
+        }
+        Err(EmuError::CycleBudgetExhausted { cycles: max_cycles.min(i32::MAX as u64) as i32 })
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use super::opcode_table::{opcode_info, AddressingMode, OpcodeInfo};
+use super::{Bus, Byte, Variant, Word};
+
+/* DISASSEMBLER */
+
+// turns memory back into source the assembler reads, with addresses replaced by labels
+// where a symbol is known. Opcodes that are undocumented on the chosen variant are
+// flagged and printed with a leading '*', the way nestest.log marks them.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub address: Word,
+    pub bytes: Vec<Byte>,
+    pub info: OpcodeInfo,
+    pub operand: String,            // formatted operand, empty for implied instructions
+    pub target: Option<Word>,       // memory or branch address the operand names, if any
+}
+
+impl Instruction {
+
+    pub fn illegal(&self) -> bool {
+        !self.info.documented
+    }
+
+    // address of the following instruction
+    pub fn next(&self) -> Word {
+        self.address.wrapping_add(self.bytes.len() as Word)
+    }
+
+    // "LDA #$42", "*NOP $12"
+    pub fn text(&self) -> String {
+        let flag: &str = if self.illegal() { "*" } else { "" };
+        match self.operand.is_empty() {
+            true => format!("{}{}", flag, self.info.mnemonic),
+            false => format!("{}{} {}", flag, self.info.mnemonic, self.operand),
+        }
+    }
+}
+
+pub struct Disassembler {
+    pub variant: Variant,
+    pub symbols: BTreeMap<Word, String>,
+}
+
+pub fn build_disassembler(variant: Variant) -> Disassembler {
+    Disassembler {
+        variant,
+        symbols: BTreeMap::new(),
+    }
+}
+
+impl Disassembler {
+
+    // the first name given to an address is the one printed
+    pub fn add_symbol(&mut self, name: &str, address: Word) {
+        self.symbols.entry(address).or_insert_with(|| name.to_string());
+    }
+
+    // "name = $XXXX" lines as written by Assembly::symbol_file, anything else is skipped
+    pub fn load_symbols(&mut self, text: &str) {
+        for line in text.lines() {
+            let line: &str = line.split(';').next().unwrap_or("");
+            let (name, value): (&str, &str) = match line.split_once('=') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => continue,
+            };
+            let address: Option<Word> = match value.strip_prefix('$') {
+                Some(hex) => Word::from_str_radix(hex, 16).ok(),
+                None => value.parse::<Word>().ok(),
+            };
+            if let Some(address) = address {
+                self.add_symbol(name, address);
+            }
+        }
+    }
+
+    pub fn load_symbol_file(&mut self, path: &str) -> io::Result<()> {
+        let text: String = fs::read_to_string(path)?;
+        self.load_symbols(&text);
+        Ok(())
+    }
+
+    // decodes the instruction at `address`, reading memory through peek only
+    pub fn disassemble<B: Bus>(&self, bus: &B, address: Word) -> Instruction {
+        let info: OpcodeInfo = opcode_info(self.variant, bus.peek(address));
+        let bytes: Vec<Byte> = (0..info.size() as Word).map(|offset| bus.peek(address.wrapping_add(offset))).collect();
+        let byte: Word = bytes.get(1).copied().unwrap_or(0) as Word;
+        let word: Word = byte | ((bytes.get(2).copied().unwrap_or(0) as Word) << 8);
+        let next: Word = address.wrapping_add(bytes.len() as Word);
+        let relative: Word = next.wrapping_add(byte as Byte as i8 as Word);
+
+        let (operand, target): (String, Option<Word>) = match info.mode {
+            AddressingMode::Implied => (String::new(), None),
+            AddressingMode::Accumulator => ("A".to_string(), None),
+            AddressingMode::Immediate => (format!("#${:02X}", byte), None),
+            AddressingMode::ZeroPage => (self.zero_page(byte), Some(byte)),
+            AddressingMode::ZeroPageX => (format!("{},X", self.zero_page(byte)), Some(byte)),
+            AddressingMode::ZeroPageY => (format!("{},Y", self.zero_page(byte)), Some(byte)),
+            AddressingMode::Absolute => (self.absolute(word), Some(word)),
+            AddressingMode::AbsoluteX => (format!("{},X", self.absolute(word)), Some(word)),
+            AddressingMode::AbsoluteY => (format!("{},Y", self.absolute(word)), Some(word)),
+            AddressingMode::Indirect => (format!("({})", self.absolute(word)), Some(word)),
+            AddressingMode::IndirectX => (format!("({},X)", self.zero_page(byte)), Some(byte)),
+            AddressingMode::IndirectY => (format!("({}),Y", self.zero_page(byte)), Some(byte)),
+            AddressingMode::ZeroPageIndirect => (format!("({})", self.zero_page(byte)), Some(byte)),
+            AddressingMode::AbsoluteXIndirect => (format!("({},X)", self.absolute(word)), Some(word)),
+            AddressingMode::Relative => (self.absolute(relative), Some(relative)),
+            AddressingMode::ZeroPageRelative => {
+                // the branch offset is the third byte
+                let target: Word = next.wrapping_add((word >> 8) as Byte as i8 as Word);
+                (format!("{},{}", self.zero_page(byte), self.absolute(target)), Some(target))
+            }
+        };
+
+        Instruction {
+            address,
+            bytes,
+            info,
+            operand,
+            target,
+        }
+    }
+
+    // every instruction starting in start..=end
+    pub fn disassemble_range<B: Bus>(&self, bus: &B, start: Word, end: Word) -> Vec<Instruction> {
+        let mut instructions: Vec<Instruction> = Vec::new();
+        let mut position: u32 = start as u32;
+        while position <= end as u32 {
+            let instruction: Instruction = self.disassemble(bus, position as Word);
+            position += instruction.bytes.len() as u32;
+            instructions.push(instruction);
+        }
+        instructions
+    }
+
+    // "8000  A9 42     LDA #$42" lines, with a "label:" line ahead of every known address
+    pub fn listing<B: Bus>(&self, bus: &B, start: Word, end: Word) -> String {
+        let mut listing: String = String::new();
+        for instruction in self.disassemble_range(bus, start, end) {
+            if let Some(label) = self.symbols.get(&instruction.address) {
+                listing.push_str(&format!("{}:\n", label));
+            }
+            let bytes: Vec<String> = instruction.bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+            listing.push_str(&format!("{:04X}  {:<8}  {}\n", instruction.address, bytes.join(" "), instruction.text()));
+        }
+        listing
+    }
+
+    fn zero_page(&self, address: Word) -> String {
+        match self.symbols.get(&address) {
+            Some(label) => label.clone(),
+            None => format!("${:02X}", address),
+        }
+    }
+
+    fn absolute(&self, address: Word) -> String {
+        match self.symbols.get(&address) {
+            Some(label) => label.clone(),
+            None => format!("${:04X}", address),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::fs;
+use std::io;
+
+use super::{Bus, Byte, Word};
+
+/* MEMORY MAP */
+
+// regions are decoded in the order they were added, later regions shadow earlier ones
+// so an I/O hole can be punched into a larger RAM or ROM area
+
+static UNMAPPED: u8 = 0xFF;
+
+enum RegionKind {
+    Ram(Vec<Byte>),
+    Rom(Vec<Byte>),
+    Mirror { target: Word, size: usize },   // repeats `size` bytes starting at `target`
+    Unmapped,
+}
+
+struct Region {
+    start: Word,
+    kind: RegionKind,
+}
+
+pub struct MemoryMapBuilder {
+    regions: Vec<(Word, Word, RegionKind)>,
+    fault_on_unmapped: bool,
+}
+
+pub fn build_memory_map() -> MemoryMapBuilder {
+    MemoryMapBuilder {
+        regions: Vec::new(),
+        fault_on_unmapped: false,
+    }
+}
+
+impl MemoryMapBuilder {
+
+    // writable RAM covering start..=end, zero filled
+    pub fn ram(mut self, start: Word, end: Word) -> Self {
+        assert!(start <= end, "ram range {:#06x}..={:#06x} ends before it starts", start, end);
+        let size: usize = (end - start) as usize + 1;
+        self.regions.push((start, end, RegionKind::Ram(vec![0; size])));
+        self
+    }
+
+    // write-protected ROM holding `data` from `start` on
+    pub fn rom(mut self, start: Word, data: &[Byte]) -> Self {
+        assert!(!data.is_empty() && start as usize + data.len() <= 0x10000, "rom does not fit in the address space");
+        let end: Word = (start as usize + data.len() - 1) as Word;
+        self.regions.push((start, end, RegionKind::Rom(data.to_vec())));
+        self
+    }
+
+    pub fn rom_file(self, start: Word, path: &str) -> io::Result<Self> {
+        let data: Vec<Byte> = fs::read(path)?;
+        Ok(self.rom(start, &data))
+    }
+
+    // start..=end repeats the `size` bytes at `target`, e.g. the NES mirrors
+    // its 2 KiB of RAM with mirror(0x0800, 0x1FFF, 0x0000, 0x0800)
+    pub fn mirror(mut self, start: Word, end: Word, target: Word, size: usize) -> Self {
+        assert!(start <= end, "mirror range {:#06x}..={:#06x} ends before it starts", start, end);
+        assert!(size > 0, "mirrored range cannot be empty");
+        self.regions.push((start, end, RegionKind::Mirror { target, size }));
+        self
+    }
+
+    // reads return the open bus value (the last byte seen on the data bus), writes are dropped
+    pub fn unmapped(mut self, start: Word, end: Word) -> Self {
+        assert!(start <= end, "unmapped range {:#06x}..={:#06x} ends before it starts", start, end);
+        self.regions.push((start, end, RegionKind::Unmapped));
+        self
+    }
+
+    // report accesses to unmapped addresses as EmuError::BusFault instead of reading open bus
+    pub fn fault_on_unmapped(mut self, enabled: bool) -> Self {
+        self.fault_on_unmapped = enabled;
+        self
+    }
+
+    pub fn build(self) -> MemoryMap {
+        assert!(self.regions.len() < UNMAPPED as usize, "too many regions in memory map");
+        let mut decode: Vec<u8> = vec![UNMAPPED; 0x10000];
+        let mut regions: Vec<Region> = Vec::new();
+        for (index, (start, end, kind)) in self.regions.into_iter().enumerate() {
+            for address in start..=end {
+                decode[address as usize] = index as u8;
+            }
+            regions.push(Region { start, kind });
+        }
+        MemoryMap {
+            regions,
+            decode,
+            open_bus: 0,
+            fault_on_unmapped: self.fault_on_unmapped,
+            fault: None,
+        }
+    }
+}
+
+pub struct MemoryMap {
+    regions: Vec<Region>,
+    decode: Vec<u8>,                // region index for every address
+    open_bus: Byte,                 // last value driven on the data bus
+    fault_on_unmapped: bool,
+    fault: Option<Word>,
+}
+
+impl MemoryMap {
+
+    // follows a mirror to the region backing it, returns the region index and offset
+    fn resolve(&self, address: Word) -> Option<(usize, usize)> {
+        let index: u8 = self.decode[address as usize];
+        if index == UNMAPPED {
+            return None;
+        }
+        let region: &Region = &self.regions[index as usize];
+        match region.kind {
+            RegionKind::Ram(_) | RegionKind::Rom(_) => Some((index as usize, (address - region.start) as usize)),
+            RegionKind::Mirror { target, size } => {
+                let mirrored: Word = target.wrapping_add(((address - region.start) as usize % size) as Word);
+                let target_index: u8 = self.decode[mirrored as usize];
+                if target_index == UNMAPPED {
+                    return None;
+                }
+                let target_region: &Region = &self.regions[target_index as usize];
+                match target_region.kind {
+                    RegionKind::Ram(_) | RegionKind::Rom(_) => Some((target_index as usize, (mirrored - target_region.start) as usize)),
+                    // mirrors of mirrors and of holes are not followed
+                    _ => None,
+                }
+            }
+            RegionKind::Unmapped => None,
+        }
+    }
+
+    fn unmapped_access(&mut self, address: Word) {
+        if self.fault_on_unmapped && self.fault.is_none() {
+            self.fault = Some(address);
+        }
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: Word) -> Byte {
+        match self.resolve(address) {
+            Some((index, offset)) => {
+                let value: Byte = match &self.regions[index].kind {
+                    RegionKind::Ram(data) | RegionKind::Rom(data) => data[offset],
+                    _ => self.open_bus,
+                };
+                self.open_bus = value;
+                value
+            }
+            None => {
+                self.unmapped_access(address);
+                self.open_bus
+            }
+        }
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        self.open_bus = value;
+        match self.resolve(address) {
+            Some((index, offset)) => {
+                // ROM ignores writes
+                if let RegionKind::Ram(data) = &mut self.regions[index].kind {
+                    data[offset] = value;
+                }
+            }
+            None => self.unmapped_access(address),
+        }
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        match self.resolve(address) {
+            Some((index, offset)) => match &self.regions[index].kind {
+                RegionKind::Ram(data) | RegionKind::Rom(data) => data[offset],
+                _ => self.open_bus,
+            },
+            None => self.open_bus,
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<Word> {
+        self.fault.take()
+    }
+}
@@ -0,0 +1,541 @@
+use std::fs;
+
+use super::asm::assemble;
+use super::debug::{build_breakpoints, Breakpoints, Comparison, Condition, Register, StopReason};
+use super::disasm::{build_disassembler, Disassembler, Instruction};
+use super::{build_cpu, build_memory, Access, Byte, EmuError, Opcodes, Variant, Word, CPU, MEMORY};
+
+/* MACHINE LANGUAGE MONITOR */
+
+// a WozMon / VICE style monitor over a CPU and 64 KiB of MEMORY. Every command line gives
+// back the text to print, the binary only reads lines and prints. Numbers are hex, with or
+// without a leading '$'. Commands come first, so a one letter address like D has to be
+// written 0D to be examined.
+
+static HELP: &str = "\
+m [start [end]]              dump memory
+start[.end]                  dump memory (WozMon)
+start: bytes                 deposit bytes (WozMon)
+> start bytes                deposit bytes
+d [start [end]]              disassemble
+a start [instruction]        assemble ($ for hex), without an instruction one per line until an empty line
+r [reg=value ...]            show or set a x y sp pc p and the flags n v d i z c
+z [count]                    step instructions
+n                            step over a subroutine call
+g [start]                    run until a breakpoint
+break [start]                set or list breakpoints
+break op <opcode>            stop before an opcode, 'break illegal' before any illegal one
+watch [r|w|rw] start [end]   stop after an access to start..=end
+cond <reg> <op> <value>      stop when a register compares true (== != < <= > >=)
+delete [start]               remove a breakpoint, everything without an address
+l <file> <start>             load a file into memory
+s <file> <start> <end>       save memory to a file
+x                            quit
+";
+
+// how long 'g' runs before giving up on reaching a breakpoint
+static RUN_CYCLES: u64 = 20_000_000;
+
+pub struct Monitor {
+    pub cpu: CPU,
+    pub mem: MEMORY,
+    pub breakpoints: Breakpoints,
+    pub disassembler: Disassembler,
+    pub running: bool,              // false once the user quits
+    pub assembling: Option<Word>,   // address of the next line in assemble mode
+    dump_next: Word,                // where a bare 'm' or 'd' continues
+    disasm_next: Word,
+}
+
+pub fn build_monitor(variant: Variant) -> Monitor {
+    Monitor {
+        cpu: build_cpu(variant),
+        mem: build_memory(),
+        breakpoints: build_breakpoints(),
+        disassembler: build_disassembler(variant),
+        running: true,
+        assembling: None,
+        dump_next: 0,
+        disasm_next: 0,
+    }
+}
+
+fn parse_number(text: &str) -> Result<Word, String> {
+    let digits: &str = text.strip_prefix('$').unwrap_or(text);
+    Word::from_str_radix(digits, 16).map_err(|_| format!("bad number {}", text))
+}
+
+fn parse_byte(text: &str) -> Result<Byte, String> {
+    parse_byte_value(parse_number(text)?)
+}
+
+fn parse_byte_value(value: Word) -> Result<Byte, String> {
+    match value {
+        value if value <= 0xFF => Ok(value as Byte),
+        _ => Err(format!("{:X} does not fit in a byte", value)),
+    }
+}
+
+fn parse_register(name: &str) -> Result<Register, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "a" => Ok(Register::A),
+        "x" => Ok(Register::X),
+        "y" => Ok(Register::Y),
+        "sp" => Ok(Register::SP),
+        "pc" => Ok(Register::PC),
+        "p" => Ok(Register::P),
+        "c" => Ok(Register::Carry),
+        "z" => Ok(Register::Zero),
+        "i" => Ok(Register::Interrupt),
+        "d" => Ok(Register::Decimal),
+        "v" => Ok(Register::Overflow),
+        "n" => Ok(Register::Negative),
+        _ => Err(format!("unknown register {}", name)),
+    }
+}
+
+fn parse_comparison(text: &str) -> Result<Comparison, String> {
+    match text {
+        "==" | "=" => Ok(Comparison::Equal),
+        "!=" => Ok(Comparison::NotEqual),
+        "<" => Ok(Comparison::Less),
+        "<=" => Ok(Comparison::LessOrEqual),
+        ">" => Ok(Comparison::Greater),
+        ">=" => Ok(Comparison::GreaterOrEqual),
+        _ => Err(format!("unknown comparison {}", text)),
+    }
+}
+
+impl Monitor {
+
+    // "> " normally, ".0402 " while assembling
+    pub fn prompt(&self) -> String {
+        match self.assembling {
+            Some(address) => format!(".{:04X} ", address),
+            None => "> ".to_string(),
+        }
+    }
+
+    // runs one line and returns what it prints, errors come back as "? message"
+    pub fn command(&mut self, line: &str) -> String {
+        let result: Result<String, String> = match self.assembling {
+            Some(address) => self.assemble_mode(address, line.trim()),
+            None => self.dispatch(line.trim()),
+        };
+        match result {
+            Ok(text) => text,
+            Err(message) => format!("? {}\n", message),
+        }
+    }
+
+    fn dispatch(&mut self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (name, args): (&str, &[&str]) = match words.split_first() {
+            Some((name, args)) => (name, args),
+            None => return Ok(String::new()),
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "?" | "help" => Ok(HELP.to_string()),
+            "x" | "q" | "quit" => {
+                self.running = false;
+                Ok(String::new())
+            }
+            "m" => self.dump_command(args),
+            ">" => self.deposit(args),
+            "d" => self.disassemble_command(args),
+            "a" => self.assemble_command(line, args),
+            "r" => self.registers_command(args),
+            "z" => self.step_command(args),
+            "n" => self.next_command(),
+            "g" => self.go_command(args),
+            "break" => self.break_command(args),
+            "watch" => self.watch_command(args),
+            "cond" => self.condition_command(args),
+            "delete" => self.delete_command(args),
+            "l" => self.load_command(args),
+            "s" => self.save_command(args),
+            _ => self.wozmon(line),
+        }
+    }
+
+    /* MEMORY */
+
+    // "0400: A9 42" deposits, "0400" and "0400.040F" dump
+    fn wozmon(&mut self, line: &str) -> Result<String, String> {
+        if let Some((address, bytes)) = line.split_once(':') {
+            let mut args: Vec<&str> = vec![address.trim()];
+            args.extend(bytes.split_whitespace());
+            return self.deposit(&args);
+        }
+        match line.split_once('.') {
+            Some((start, end)) => self.dump(parse_number(start.trim())?, parse_number(end.trim())?),
+            None if !line.contains(char::is_whitespace) => {
+                let address: Word = parse_number(line).map_err(|_| format!("unknown command {}", line))?;
+                self.dump(address, address)
+            }
+            None => Err(format!("unknown command {}", line)),
+        }
+    }
+
+    fn dump_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let start: Word = match args.first() {
+            Some(text) => parse_number(text)?,
+            None => self.dump_next,
+        };
+        let end: Word = match args.get(1) {
+            Some(text) => parse_number(text)?,
+            None => start.saturating_add(0x7F),
+        };
+        self.dump(start, end)
+    }
+
+    // 16 bytes a line with their ASCII next to them
+    fn dump(&mut self, start: Word, end: Word) -> Result<String, String> {
+        if end < start {
+            return Err(format!("{:04X} is below {:04X}", end, start));
+        }
+        let mut text: String = String::new();
+        let mut line_start: u32 = start as u32;
+        while line_start <= end as u32 {
+            let line_end: u32 = (line_start + 15).min(end as u32);
+            let bytes: &[Byte] = &self.mem.memory[line_start as usize..=line_end as usize];
+            let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+            let ascii: String = bytes.iter()
+                .map(|&byte| if (0x20..0x7F).contains(&byte) { byte as char } else { '.' })
+                .collect();
+            text.push_str(&format!("{:04X}: {:<47}  {}\n", line_start, hex.join(" "), ascii));
+            line_start += 16;
+        }
+        self.dump_next = end.wrapping_add(1);
+        Ok(text)
+    }
+
+    fn deposit(&mut self, args: &[&str]) -> Result<String, String> {
+        let (start, bytes): (&&str, &[&str]) = args.split_first().ok_or("deposit needs an address")?;
+        let start: Word = parse_number(start)?;
+        let values: Vec<Byte> = bytes.iter().map(|text| parse_byte(text)).collect::<Result<_, _>>()?;
+        for (offset, value) in values.iter().enumerate() {
+            self.mem.memory[start.wrapping_add(offset as Word) as usize] = *value;
+        }
+        Ok(String::new())
+    }
+
+    /* DISASSEMBLER / ASSEMBLER */
+
+    fn disassemble_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let start: Word = match args.first() {
+            Some(text) => parse_number(text)?,
+            None => self.disasm_next,
+        };
+        let mut text: String = String::new();
+        let mut address: Word = start;
+        match args.get(1) {
+            Some(end) => {
+                let end: Word = parse_number(end)?;
+                for instruction in self.disassembler.disassemble_range(&self.mem, start, end) {
+                    text.push_str(&self.format_instruction(&instruction));
+                    address = instruction.next();
+                }
+            }
+            None => {
+                for _ in 0..16 {
+                    let instruction: Instruction = self.disassembler.disassemble(&self.mem, address);
+                    text.push_str(&self.format_instruction(&instruction));
+                    address = instruction.next();
+                }
+            }
+        }
+        self.disasm_next = address;
+        Ok(text)
+    }
+
+    fn format_instruction(&self, instruction: &Instruction) -> String {
+        let bytes: Vec<String> = instruction.bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+        let marker: char = if self.breakpoints.addresses.contains(&instruction.address) { '*' } else { ' ' };
+        format!("{}{:04X}  {:<8}  {}\n", marker, instruction.address, bytes.join(" "), instruction.text())
+    }
+
+    // "a 0400 LDA #$42" assembles one line, "a 0400" switches to assemble mode
+    fn assemble_command(&mut self, line: &str, args: &[&str]) -> Result<String, String> {
+        let address: Word = parse_number(args.first().ok_or("assemble needs an address")?)?;
+        if args.len() == 1 {
+            self.assembling = Some(address);
+            return Ok(String::new());
+        }
+        // everything after the address is the instruction
+        let instruction: &str = line[1..].trim_start()[args[0].len()..].trim();
+        self.assemble_line(address, instruction)?;
+        Ok(String::new())
+    }
+
+    fn assemble_mode(&mut self, address: Word, line: &str) -> Result<String, String> {
+        if line.is_empty() {
+            self.assembling = None;
+            return Ok(String::new());
+        }
+        let next: Word = self.assemble_line(address, line)?;
+        self.assembling = Some(next);
+        Ok(String::new())
+    }
+
+    // assembles a single instruction or directive at address, returns the address after it
+    fn assemble_line(&mut self, address: Word, instruction: &str) -> Result<Word, String> {
+        // indented, so the assembler does not take the mnemonic for a label
+        let source: String = format!("* = ${:04X}\n    {}\n", address, instruction);
+        let assembly = assemble(&source).map_err(|err| err.message)?;
+        assembly.load(&mut self.mem);
+        let size: usize = assembly.segments.iter().map(|segment| segment.bytes.len()).sum();
+        self.disasm_next = address;
+        Ok(address.wrapping_add(size as Word))
+    }
+
+    /* REGISTERS */
+
+    fn registers_command(&mut self, args: &[&str]) -> Result<String, String> {
+        for assignment in args {
+            let (name, value): (&str, &str) = assignment.split_once('=')
+                .ok_or_else(|| format!("expected register=value, got {}", assignment))?;
+            let register: Register = parse_register(name)?;
+            let value: Word = parse_number(value)?;
+            let flag: Byte = (value != 0) as Byte;
+            match register {
+                Register::PC => self.cpu.pc = value,
+                Register::A => self.cpu.r_a = parse_byte_value(value)?,
+                Register::X => self.cpu.r_x = parse_byte_value(value)?,
+                Register::Y => self.cpu.r_y = parse_byte_value(value)?,
+                Register::SP => self.cpu.sp = parse_byte_value(value)?,
+                Register::P => self.cpu.set_processor_status(parse_byte_value(value)?),
+                Register::Carry => self.cpu.ps_carry = flag,
+                Register::Zero => self.cpu.ps_zero = flag,
+                Register::Interrupt => self.cpu.ps_interrupt = flag,
+                Register::Decimal => self.cpu.ps_decimal = flag,
+                Register::Overflow => self.cpu.ps_overflow = flag,
+                Register::Negative => self.cpu.ps_negative = flag,
+            }
+        }
+        Ok(self.registers())
+    }
+
+    // "PC:0400 A:00 X:00 Y:00 SP:FF P:24 NV-BDIZC:00100100"
+    pub fn registers(&self) -> String {
+        let status: Byte = self.cpu.processor_status();
+        format!(
+            "PC:{:04X} A:{:02X} X:{:02X} Y:{:02X} SP:{:02X} P:{:02X} NV-BDIZC:{:08b}\n",
+            self.cpu.pc, self.cpu.r_a, self.cpu.r_x, self.cpu.r_y, self.cpu.sp, status, status
+        )
+    }
+
+    // the next instruction and the registers, shown after every step or stop
+    fn where_we_are(&mut self) -> String {
+        let instruction: Instruction = self.disassembler.disassemble(&self.mem, self.cpu.pc);
+        self.disasm_next = instruction.next();
+        format!("{}{}", self.format_instruction(&instruction), self.registers())
+    }
+
+    /* EXECUTION */
+
+    fn step_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let count: Word = match args.first() {
+            Some(text) => parse_number(text)?,
+            None => 1,
+        };
+        for _ in 0..count {
+            self.cpu.step(&mut self.mem).map_err(|err| self.stopped_by_error(err))?;
+        }
+        Ok(self.where_we_are())
+    }
+
+    // runs a JSR until it returns, anything else is a single step
+    fn next_command(&mut self) -> Result<String, String> {
+        if self.mem.memory[self.cpu.pc as usize] != CPU::JSR_ABSOLUTE {
+            return self.step_command(&[]);
+        }
+        let return_address: Word = self.cpu.pc.wrapping_add(3);
+        let added: bool = !self.breakpoints.addresses.contains(&return_address);
+        self.breakpoints.add_breakpoint(return_address);
+        let result: Result<StopReason, EmuError> = self.cpu.run_until_break(&mut self.mem, &self.breakpoints, RUN_CYCLES);
+        if added {
+            self.breakpoints.remove_breakpoint(return_address);
+        }
+        match result {
+            Ok(StopReason::Breakpoint { address }) if address == return_address => Ok(self.where_we_are()),
+            other => self.report(other),
+        }
+    }
+
+    fn go_command(&mut self, args: &[&str]) -> Result<String, String> {
+        if let Some(text) = args.first() {
+            self.cpu.pc = parse_number(text)?;
+        }
+        let result: Result<StopReason, EmuError> = self.cpu.run_until_break(&mut self.mem, &self.breakpoints, RUN_CYCLES);
+        self.report(result)
+    }
+
+    fn report(&mut self, result: Result<StopReason, EmuError>) -> Result<String, String> {
+        let reason: String = match result {
+            Ok(StopReason::Breakpoint { address }) => format!("break at {:04X}", address),
+            Ok(StopReason::Watchpoint { pc, cycle }) => match cycle.access {
+                Access::Read => format!("watch: read {:02X} from {:04X} at {:04X}", cycle.value, cycle.address, pc),
+                Access::Write => format!("watch: write {:02X} to {:04X} at {:04X}", cycle.value, cycle.address, pc),
+            },
+            Ok(StopReason::Opcode { address, opcode }) => format!("opcode {:02X} at {:04X}", opcode, address),
+            Ok(StopReason::Condition { address, index }) => {
+                format!("condition {} at {:04X}", self.format_condition(&self.breakpoints.conditions[index]), address)
+            }
+            Err(err) => self.stopped_by_error(err),
+        };
+        Ok(format!("{}\n{}", reason, self.where_we_are()))
+    }
+
+    fn stopped_by_error(&self, err: EmuError) -> String {
+        format!("stopped at {:04X}: {}", self.cpu.pc, err)
+    }
+
+    /* BREAKPOINTS */
+
+    fn break_command(&mut self, args: &[&str]) -> Result<String, String> {
+        match args {
+            [] => Ok(self.list_breakpoints()),
+            ["illegal"] => {
+                self.breakpoints.illegal_opcodes = true;
+                Ok(String::new())
+            }
+            ["op", opcode] => {
+                self.breakpoints.break_on_opcode(parse_byte(opcode)?);
+                Ok(String::new())
+            }
+            [address] => {
+                self.breakpoints.add_breakpoint(parse_number(address)?);
+                Ok(String::new())
+            }
+            _ => Err("usage: break [start] | break op <opcode> | break illegal".to_string()),
+        }
+    }
+
+    fn watch_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let (read, write, range): (bool, bool, &[&str]) = match args.first() {
+            Some(&"r") => (true, false, &args[1..]),
+            Some(&"w") => (false, true, &args[1..]),
+            Some(&"rw") => (true, true, &args[1..]),
+            _ => (true, true, args),
+        };
+        let start: Word = parse_number(range.first().ok_or("watch needs an address")?)?;
+        let end: Word = match range.get(1) {
+            Some(text) => parse_number(text)?,
+            None => start,
+        };
+        self.breakpoints.watch(start, end, read, write);
+        Ok(String::new())
+    }
+
+    fn condition_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let [register, comparison, value] = args else {
+            return Err("usage: cond <reg> <op> <value>".to_string());
+        };
+        self.breakpoints.add_condition(Condition {
+            register: parse_register(register)?,
+            comparison: parse_comparison(comparison)?,
+            value: parse_number(value)?,
+        });
+        Ok(String::new())
+    }
+
+    fn delete_command(&mut self, args: &[&str]) -> Result<String, String> {
+        match args.first() {
+            Some(text) => {
+                let address: Word = parse_number(text)?;
+                match self.breakpoints.remove_breakpoint(address) {
+                    true => Ok(String::new()),
+                    false => Err(format!("no breakpoint at {:04X}", address)),
+                }
+            }
+            None => {
+                self.breakpoints.clear();
+                Ok(String::new())
+            }
+        }
+    }
+
+    fn list_breakpoints(&self) -> String {
+        let mut text: String = String::new();
+        for address in &self.breakpoints.addresses {
+            text.push_str(&format!("break {:04X}\n", address));
+        }
+        for opcode in &self.breakpoints.opcodes {
+            text.push_str(&format!("break op {:02X}\n", opcode));
+        }
+        if self.breakpoints.illegal_opcodes {
+            text.push_str("break illegal\n");
+        }
+        for watchpoint in &self.breakpoints.watchpoints {
+            let access: &str = match (watchpoint.read, watchpoint.write) {
+                (true, false) => "r",
+                (false, true) => "w",
+                _ => "rw",
+            };
+            text.push_str(&format!("watch {} {:04X} {:04X}\n", access, watchpoint.start, watchpoint.end));
+        }
+        for condition in &self.breakpoints.conditions {
+            text.push_str(&format!("cond {}\n", self.format_condition(condition)));
+        }
+        text
+    }
+
+    fn format_condition(&self, condition: &Condition) -> String {
+        let register: &str = match condition.register {
+            Register::A => "a",
+            Register::X => "x",
+            Register::Y => "y",
+            Register::SP => "sp",
+            Register::PC => "pc",
+            Register::P => "p",
+            Register::Carry => "c",
+            Register::Zero => "z",
+            Register::Interrupt => "i",
+            Register::Decimal => "d",
+            Register::Overflow => "v",
+            Register::Negative => "n",
+        };
+        let comparison: &str = match condition.comparison {
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+        };
+        format!("{} {} {:02X}", register, comparison, condition.value)
+    }
+
+    /* FILES */
+
+    fn load_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let [file, start] = args else {
+            return Err("usage: l <file> <start>".to_string());
+        };
+        let file: &str = file.trim_matches('"');
+        let start: Word = parse_number(start)?;
+        let data: Vec<Byte> = fs::read(file).map_err(|err| format!("cannot read {}: {}", file, err))?;
+        if start as usize + data.len() > self.mem.memory.len() {
+            return Err(format!("{} does not fit at {:04X}", file, start));
+        }
+        self.mem.memory[start as usize..start as usize + data.len()].copy_from_slice(&data);
+        match data.is_empty() {
+            true => Ok(format!("loaded 0 bytes at {:04X}\n", start)),
+            false => Ok(format!("loaded {:04X}-{:04X}\n", start, start as usize + data.len() - 1)),
+        }
+    }
+
+    fn save_command(&mut self, args: &[&str]) -> Result<String, String> {
+        let [file, start, end] = args else {
+            return Err("usage: s <file> <start> <end>".to_string());
+        };
+        let file: &str = file.trim_matches('"');
+        let (start, end): (Word, Word) = (parse_number(start)?, parse_number(end)?);
+        if end < start {
+            return Err(format!("{:04X} is below {:04X}", end, start));
+        }
+        fs::write(file, &self.mem.memory[start as usize..=end as usize]).map_err(|err| format!("cannot write {}: {}", file, err))?;
+        Ok(format!("saved {:04X}-{:04X}\n", start, end))
+    }
+}
@@ -0,0 +1,616 @@
+use super::{Opcode, Variant};
+
+/* OPCODE TABLE */
+
+// what every opcode is on each cpu family: mnemonic, addressing mode and cycle count.
+// This is the reference the assembler encodes from, the disassembler decodes with and
+// the executor is checked against (see the opcode_table tests).
+//
+// cycles is the base count: no page crossing, branch not taken, binary mode (the 65C02
+// takes one more in decimal mode). A JAM never completes, its count is 0. page_cross is
+// added when an indexed address lands on another page, or when a taken branch does;
+// taking a branch costs one cycle on its own (BRA has it in its base count).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressingMode {
+    Implied,
+    Accumulator,
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Indirect,               // JMP ($1234)
+    IndirectX,              // ($12,X)
+    IndirectY,              // ($12),Y
+    ZeroPageIndirect,       // 65C02 ($12)
+    AbsoluteXIndirect,      // 65C02 JMP ($1234,X)
+    Relative,
+    ZeroPageRelative,       // Rockwell BBRn / BBSn $12, target
+}
+
+impl AddressingMode {
+
+    // operand bytes following the opcode
+    pub fn operand_size(self) -> u8 {
+        match self {
+            AddressingMode::Implied | AddressingMode::Accumulator => 0,
+            AddressingMode::Immediate | AddressingMode::ZeroPage | AddressingMode::ZeroPageX | AddressingMode::ZeroPageY => 1,
+            AddressingMode::IndirectX | AddressingMode::IndirectY | AddressingMode::ZeroPageIndirect | AddressingMode::Relative => 1,
+            AddressingMode::Absolute | AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => 2,
+            AddressingMode::Indirect | AddressingMode::AbsoluteXIndirect | AddressingMode::ZeroPageRelative => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeInfo {
+    pub mnemonic: &'static str,
+    pub mode: AddressingMode,
+    pub cycles: u8,
+    pub page_cross: u8,
+    pub documented: bool,           // false for undocumented NMOS opcodes and the 65C02 NOP fillers
+}
+
+impl OpcodeInfo {
+
+    // instruction length in bytes, opcode included
+    pub fn size(&self) -> u8 {
+        1 + self.mode.operand_size()
+    }
+}
+
+const fn op(mnemonic: &'static str, mode: AddressingMode, cycles: u8, page_cross: u8) -> OpcodeInfo {
+    OpcodeInfo { mnemonic, mode, cycles, page_cross, documented: true }
+}
+
+const fn undocumented(mnemonic: &'static str, mode: AddressingMode, cycles: u8, page_cross: u8) -> OpcodeInfo {
+    OpcodeInfo { mnemonic, mode, cycles, page_cross, documented: false }
+}
+
+// single byte, single cycle NOP the 65C02 runs for the columns it leaves unused
+static CMOS_NOP: OpcodeInfo = undocumented("NOP", AddressingMode::Implied, 1, 0);
+
+pub fn opcode_info(variant: Variant, opcode: Opcode) -> OpcodeInfo {
+    match variant {
+        Variant::Nmos6502 | Variant::Ricoh2A03 => NMOS_OPCODES[opcode as usize],
+        // the x7 and xF columns hold the Rockwell bit instructions
+        _ if opcode & 0x07 == 0x07 && !variant.has_bit_instructions() => CMOS_NOP,
+        _ if (opcode == 0xCB || opcode == 0xDB) && !variant.has_wait_and_stop() => CMOS_NOP,
+        _ => CMOS_OPCODES[opcode as usize],
+    }
+}
+
+// the opcode for a mnemonic and mode on any member of the family: documented instructions
+// of every variant first, then the undocumented NMOS ones (the first of any aliases)
+pub fn find_opcode(mnemonic: &str, mode: AddressingMode) -> Option<Opcode> {
+    let matches = |info: &OpcodeInfo| info.mode == mode && info.mnemonic.eq_ignore_ascii_case(mnemonic);
+    let documented: Option<usize> = NMOS_OPCODES.iter().chain(CMOS_OPCODES.iter()).position(|info| info.documented && matches(info));
+    let index: Option<usize> = documented.or_else(|| NMOS_OPCODES.iter().position(matches));
+    index.map(|index| (index % 256) as Opcode)
+}
+
+pub fn is_mnemonic(mnemonic: &str) -> bool {
+    NMOS_OPCODES.iter().chain(CMOS_OPCODES.iter()).any(|info| info.mnemonic.eq_ignore_ascii_case(mnemonic))
+}
+
+pub static NMOS_OPCODES: [OpcodeInfo; 256] = [
+    op("BRK", AddressingMode::Implied, 7, 0),                           // 0x00
+    op("ORA", AddressingMode::IndirectX, 6, 0),                         // 0x01
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x02
+    undocumented("SLO", AddressingMode::IndirectX, 8, 0),               // 0x03
+    undocumented("NOP", AddressingMode::ZeroPage, 3, 0),                // 0x04
+    op("ORA", AddressingMode::ZeroPage, 3, 0),                          // 0x05
+    op("ASL", AddressingMode::ZeroPage, 5, 0),                          // 0x06
+    undocumented("SLO", AddressingMode::ZeroPage, 5, 0),                // 0x07
+    op("PHP", AddressingMode::Implied, 3, 0),                           // 0x08
+    op("ORA", AddressingMode::Immediate, 2, 0),                         // 0x09
+    op("ASL", AddressingMode::Accumulator, 2, 0),                       // 0x0A
+    undocumented("ANC", AddressingMode::Immediate, 2, 0),               // 0x0B
+    undocumented("NOP", AddressingMode::Absolute, 4, 0),                // 0x0C
+    op("ORA", AddressingMode::Absolute, 4, 0),                          // 0x0D
+    op("ASL", AddressingMode::Absolute, 6, 0),                          // 0x0E
+    undocumented("SLO", AddressingMode::Absolute, 6, 0),                // 0x0F
+    op("BPL", AddressingMode::Relative, 2, 1),                          // 0x10
+    op("ORA", AddressingMode::IndirectY, 5, 1),                         // 0x11
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x12
+    undocumented("SLO", AddressingMode::IndirectY, 8, 0),               // 0x13
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0x14
+    op("ORA", AddressingMode::ZeroPageX, 4, 0),                         // 0x15
+    op("ASL", AddressingMode::ZeroPageX, 6, 0),                         // 0x16
+    undocumented("SLO", AddressingMode::ZeroPageX, 6, 0),               // 0x17
+    op("CLC", AddressingMode::Implied, 2, 0),                           // 0x18
+    op("ORA", AddressingMode::AbsoluteY, 4, 1),                         // 0x19
+    undocumented("NOP", AddressingMode::Implied, 2, 0),                 // 0x1A
+    undocumented("SLO", AddressingMode::AbsoluteY, 7, 0),               // 0x1B
+    undocumented("NOP", AddressingMode::AbsoluteX, 4, 1),               // 0x1C
+    op("ORA", AddressingMode::AbsoluteX, 4, 1),                         // 0x1D
+    op("ASL", AddressingMode::AbsoluteX, 7, 0),                         // 0x1E
+    undocumented("SLO", AddressingMode::AbsoluteX, 7, 0),               // 0x1F
+    op("JSR", AddressingMode::Absolute, 6, 0),                          // 0x20
+    op("AND", AddressingMode::IndirectX, 6, 0),                         // 0x21
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x22
+    undocumented("RLA", AddressingMode::IndirectX, 8, 0),               // 0x23
+    op("BIT", AddressingMode::ZeroPage, 3, 0),                          // 0x24
+    op("AND", AddressingMode::ZeroPage, 3, 0),                          // 0x25
+    op("ROL", AddressingMode::ZeroPage, 5, 0),                          // 0x26
+    undocumented("RLA", AddressingMode::ZeroPage, 5, 0),                // 0x27
+    op("PLP", AddressingMode::Implied, 4, 0),                           // 0x28
+    op("AND", AddressingMode::Immediate, 2, 0),                         // 0x29
+    op("ROL", AddressingMode::Accumulator, 2, 0),                       // 0x2A
+    undocumented("ANC", AddressingMode::Immediate, 2, 0),               // 0x2B
+    op("BIT", AddressingMode::Absolute, 4, 0),                          // 0x2C
+    op("AND", AddressingMode::Absolute, 4, 0),                          // 0x2D
+    op("ROL", AddressingMode::Absolute, 6, 0),                          // 0x2E
+    undocumented("RLA", AddressingMode::Absolute, 6, 0),                // 0x2F
+    op("BMI", AddressingMode::Relative, 2, 1),                          // 0x30
+    op("AND", AddressingMode::IndirectY, 5, 1),                         // 0x31
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x32
+    undocumented("RLA", AddressingMode::IndirectY, 8, 0),               // 0x33
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0x34
+    op("AND", AddressingMode::ZeroPageX, 4, 0),                         // 0x35
+    op("ROL", AddressingMode::ZeroPageX, 6, 0),                         // 0x36
+    undocumented("RLA", AddressingMode::ZeroPageX, 6, 0),               // 0x37
+    op("SEC", AddressingMode::Implied, 2, 0),                           // 0x38
+    op("AND", AddressingMode::AbsoluteY, 4, 1),                         // 0x39
+    undocumented("NOP", AddressingMode::Implied, 2, 0),                 // 0x3A
+    undocumented("RLA", AddressingMode::AbsoluteY, 7, 0),               // 0x3B
+    undocumented("NOP", AddressingMode::AbsoluteX, 4, 1),               // 0x3C
+    op("AND", AddressingMode::AbsoluteX, 4, 1),                         // 0x3D
+    op("ROL", AddressingMode::AbsoluteX, 7, 0),                         // 0x3E
+    undocumented("RLA", AddressingMode::AbsoluteX, 7, 0),               // 0x3F
+    op("RTI", AddressingMode::Implied, 6, 0),                           // 0x40
+    op("EOR", AddressingMode::IndirectX, 6, 0),                         // 0x41
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x42
+    undocumented("SRE", AddressingMode::IndirectX, 8, 0),               // 0x43
+    undocumented("NOP", AddressingMode::ZeroPage, 3, 0),                // 0x44
+    op("EOR", AddressingMode::ZeroPage, 3, 0),                          // 0x45
+    op("LSR", AddressingMode::ZeroPage, 5, 0),                          // 0x46
+    undocumented("SRE", AddressingMode::ZeroPage, 5, 0),                // 0x47
+    op("PHA", AddressingMode::Implied, 3, 0),                           // 0x48
+    op("EOR", AddressingMode::Immediate, 2, 0),                         // 0x49
+    op("LSR", AddressingMode::Accumulator, 2, 0),                       // 0x4A
+    undocumented("ALR", AddressingMode::Immediate, 2, 0),               // 0x4B
+    op("JMP", AddressingMode::Absolute, 3, 0),                          // 0x4C
+    op("EOR", AddressingMode::Absolute, 4, 0),                          // 0x4D
+    op("LSR", AddressingMode::Absolute, 6, 0),                          // 0x4E
+    undocumented("SRE", AddressingMode::Absolute, 6, 0),                // 0x4F
+    op("BVC", AddressingMode::Relative, 2, 1),                          // 0x50
+    op("EOR", AddressingMode::IndirectY, 5, 1),                         // 0x51
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x52
+    undocumented("SRE", AddressingMode::IndirectY, 8, 0),               // 0x53
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0x54
+    op("EOR", AddressingMode::ZeroPageX, 4, 0),                         // 0x55
+    op("LSR", AddressingMode::ZeroPageX, 6, 0),                         // 0x56
+    undocumented("SRE", AddressingMode::ZeroPageX, 6, 0),               // 0x57
+    op("CLI", AddressingMode::Implied, 2, 0),                           // 0x58
+    op("EOR", AddressingMode::AbsoluteY, 4, 1),                         // 0x59
+    undocumented("NOP", AddressingMode::Implied, 2, 0),                 // 0x5A
+    undocumented("SRE", AddressingMode::AbsoluteY, 7, 0),               // 0x5B
+    undocumented("NOP", AddressingMode::AbsoluteX, 4, 1),               // 0x5C
+    op("EOR", AddressingMode::AbsoluteX, 4, 1),                         // 0x5D
+    op("LSR", AddressingMode::AbsoluteX, 7, 0),                         // 0x5E
+    undocumented("SRE", AddressingMode::AbsoluteX, 7, 0),               // 0x5F
+    op("RTS", AddressingMode::Implied, 6, 0),                           // 0x60
+    op("ADC", AddressingMode::IndirectX, 6, 0),                         // 0x61
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x62
+    undocumented("RRA", AddressingMode::IndirectX, 8, 0),               // 0x63
+    undocumented("NOP", AddressingMode::ZeroPage, 3, 0),                // 0x64
+    op("ADC", AddressingMode::ZeroPage, 3, 0),                          // 0x65
+    op("ROR", AddressingMode::ZeroPage, 5, 0),                          // 0x66
+    undocumented("RRA", AddressingMode::ZeroPage, 5, 0),                // 0x67
+    op("PLA", AddressingMode::Implied, 4, 0),                           // 0x68
+    op("ADC", AddressingMode::Immediate, 2, 0),                         // 0x69
+    op("ROR", AddressingMode::Accumulator, 2, 0),                       // 0x6A
+    undocumented("ARR", AddressingMode::Immediate, 2, 0),               // 0x6B
+    op("JMP", AddressingMode::Indirect, 5, 0),                          // 0x6C
+    op("ADC", AddressingMode::Absolute, 4, 0),                          // 0x6D
+    op("ROR", AddressingMode::Absolute, 6, 0),                          // 0x6E
+    undocumented("RRA", AddressingMode::Absolute, 6, 0),                // 0x6F
+    op("BVS", AddressingMode::Relative, 2, 1),                          // 0x70
+    op("ADC", AddressingMode::IndirectY, 5, 1),                         // 0x71
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x72
+    undocumented("RRA", AddressingMode::IndirectY, 8, 0),               // 0x73
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0x74
+    op("ADC", AddressingMode::ZeroPageX, 4, 0),                         // 0x75
+    op("ROR", AddressingMode::ZeroPageX, 6, 0),                         // 0x76
+    undocumented("RRA", AddressingMode::ZeroPageX, 6, 0),               // 0x77
+    op("SEI", AddressingMode::Implied, 2, 0),                           // 0x78
+    op("ADC", AddressingMode::AbsoluteY, 4, 1),                         // 0x79
+    undocumented("NOP", AddressingMode::Implied, 2, 0),                 // 0x7A
+    undocumented("RRA", AddressingMode::AbsoluteY, 7, 0),               // 0x7B
+    undocumented("NOP", AddressingMode::AbsoluteX, 4, 1),               // 0x7C
+    op("ADC", AddressingMode::AbsoluteX, 4, 1),                         // 0x7D
+    op("ROR", AddressingMode::AbsoluteX, 7, 0),                         // 0x7E
+    undocumented("RRA", AddressingMode::AbsoluteX, 7, 0),               // 0x7F
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x80
+    op("STA", AddressingMode::IndirectX, 6, 0),                         // 0x81
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x82
+    undocumented("SAX", AddressingMode::IndirectX, 6, 0),               // 0x83
+    op("STY", AddressingMode::ZeroPage, 3, 0),                          // 0x84
+    op("STA", AddressingMode::ZeroPage, 3, 0),                          // 0x85
+    op("STX", AddressingMode::ZeroPage, 3, 0),                          // 0x86
+    undocumented("SAX", AddressingMode::ZeroPage, 3, 0),                // 0x87
+    op("DEY", AddressingMode::Implied, 2, 0),                           // 0x88
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x89
+    op("TXA", AddressingMode::Implied, 2, 0),                           // 0x8A
+    undocumented("XAA", AddressingMode::Immediate, 2, 0),               // 0x8B
+    op("STY", AddressingMode::Absolute, 4, 0),                          // 0x8C
+    op("STA", AddressingMode::Absolute, 4, 0),                          // 0x8D
+    op("STX", AddressingMode::Absolute, 4, 0),                          // 0x8E
+    undocumented("SAX", AddressingMode::Absolute, 4, 0),                // 0x8F
+    op("BCC", AddressingMode::Relative, 2, 1),                          // 0x90
+    op("STA", AddressingMode::IndirectY, 6, 0),                         // 0x91
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0x92
+    undocumented("AHX", AddressingMode::IndirectY, 6, 0),               // 0x93
+    op("STY", AddressingMode::ZeroPageX, 4, 0),                         // 0x94
+    op("STA", AddressingMode::ZeroPageX, 4, 0),                         // 0x95
+    op("STX", AddressingMode::ZeroPageY, 4, 0),                         // 0x96
+    undocumented("SAX", AddressingMode::ZeroPageY, 4, 0),               // 0x97
+    op("TYA", AddressingMode::Implied, 2, 0),                           // 0x98
+    op("STA", AddressingMode::AbsoluteY, 5, 0),                         // 0x99
+    op("TXS", AddressingMode::Implied, 2, 0),                           // 0x9A
+    undocumented("TAS", AddressingMode::AbsoluteY, 5, 0),               // 0x9B
+    undocumented("SHY", AddressingMode::AbsoluteX, 5, 0),               // 0x9C
+    op("STA", AddressingMode::AbsoluteX, 5, 0),                         // 0x9D
+    undocumented("SHX", AddressingMode::AbsoluteY, 5, 0),               // 0x9E
+    undocumented("AHX", AddressingMode::AbsoluteY, 5, 0),               // 0x9F
+    op("LDY", AddressingMode::Immediate, 2, 0),                         // 0xA0
+    op("LDA", AddressingMode::IndirectX, 6, 0),                         // 0xA1
+    op("LDX", AddressingMode::Immediate, 2, 0),                         // 0xA2
+    undocumented("LAX", AddressingMode::IndirectX, 6, 0),               // 0xA3
+    op("LDY", AddressingMode::ZeroPage, 3, 0),                          // 0xA4
+    op("LDA", AddressingMode::ZeroPage, 3, 0),                          // 0xA5
+    op("LDX", AddressingMode::ZeroPage, 3, 0),                          // 0xA6
+    undocumented("LAX", AddressingMode::ZeroPage, 3, 0),                // 0xA7
+    op("TAY", AddressingMode::Implied, 2, 0),                           // 0xA8
+    op("LDA", AddressingMode::Immediate, 2, 0),                         // 0xA9
+    op("TAX", AddressingMode::Implied, 2, 0),                           // 0xAA
+    undocumented("LXA", AddressingMode::Immediate, 2, 0),               // 0xAB
+    op("LDY", AddressingMode::Absolute, 4, 0),                          // 0xAC
+    op("LDA", AddressingMode::Absolute, 4, 0),                          // 0xAD
+    op("LDX", AddressingMode::Absolute, 4, 0),                          // 0xAE
+    undocumented("LAX", AddressingMode::Absolute, 4, 0),                // 0xAF
+    op("BCS", AddressingMode::Relative, 2, 1),                          // 0xB0
+    op("LDA", AddressingMode::IndirectY, 5, 1),                         // 0xB1
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0xB2
+    undocumented("LAX", AddressingMode::IndirectY, 5, 1),               // 0xB3
+    op("LDY", AddressingMode::ZeroPageX, 4, 0),                         // 0xB4
+    op("LDA", AddressingMode::ZeroPageX, 4, 0),                         // 0xB5
+    op("LDX", AddressingMode::ZeroPageY, 4, 0),                         // 0xB6
+    undocumented("LAX", AddressingMode::ZeroPageY, 4, 0),               // 0xB7
+    op("CLV", AddressingMode::Implied, 2, 0),                           // 0xB8
+    op("LDA", AddressingMode::AbsoluteY, 4, 1),                         // 0xB9
+    op("TSX", AddressingMode::Implied, 2, 0),                           // 0xBA
+    undocumented("LAS", AddressingMode::AbsoluteY, 4, 1),               // 0xBB
+    op("LDY", AddressingMode::AbsoluteX, 4, 1),                         // 0xBC
+    op("LDA", AddressingMode::AbsoluteX, 4, 1),                         // 0xBD
+    op("LDX", AddressingMode::AbsoluteY, 4, 1),                         // 0xBE
+    undocumented("LAX", AddressingMode::AbsoluteY, 4, 1),               // 0xBF
+    op("CPY", AddressingMode::Immediate, 2, 0),                         // 0xC0
+    op("CMP", AddressingMode::IndirectX, 6, 0),                         // 0xC1
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0xC2
+    undocumented("DCP", AddressingMode::IndirectX, 8, 0),               // 0xC3
+    op("CPY", AddressingMode::ZeroPage, 3, 0),                          // 0xC4
+    op("CMP", AddressingMode::ZeroPage, 3, 0),                          // 0xC5
+    op("DEC", AddressingMode::ZeroPage, 5, 0),                          // 0xC6
+    undocumented("DCP", AddressingMode::ZeroPage, 5, 0),                // 0xC7
+    op("INY", AddressingMode::Implied, 2, 0),                           // 0xC8
+    op("CMP", AddressingMode::Immediate, 2, 0),                         // 0xC9
+    op("DEX", AddressingMode::Implied, 2, 0),                           // 0xCA
+    undocumented("SBX", AddressingMode::Immediate, 2, 0),               // 0xCB
+    op("CPY", AddressingMode::Absolute, 4, 0),                          // 0xCC
+    op("CMP", AddressingMode::Absolute, 4, 0),                          // 0xCD
+    op("DEC", AddressingMode::Absolute, 6, 0),                          // 0xCE
+    undocumented("DCP", AddressingMode::Absolute, 6, 0),                // 0xCF
+    op("BNE", AddressingMode::Relative, 2, 1),                          // 0xD0
+    op("CMP", AddressingMode::IndirectY, 5, 1),                         // 0xD1
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0xD2
+    undocumented("DCP", AddressingMode::IndirectY, 8, 0),               // 0xD3
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0xD4
+    op("CMP", AddressingMode::ZeroPageX, 4, 0),                         // 0xD5
+    op("DEC", AddressingMode::ZeroPageX, 6, 0),                         // 0xD6
+    undocumented("DCP", AddressingMode::ZeroPageX, 6, 0),               // 0xD7
+    op("CLD", AddressingMode::Implied, 2, 0),                           // 0xD8
+    op("CMP", AddressingMode::AbsoluteY, 4, 1),                         // 0xD9
+    undocumented("NOP", AddressingMode::Implied, 2, 0),                 // 0xDA
+    undocumented("DCP", AddressingMode::AbsoluteY, 7, 0),               // 0xDB
+    undocumented("NOP", AddressingMode::AbsoluteX, 4, 1),               // 0xDC
+    op("CMP", AddressingMode::AbsoluteX, 4, 1),                         // 0xDD
+    op("DEC", AddressingMode::AbsoluteX, 7, 0),                         // 0xDE
+    undocumented("DCP", AddressingMode::AbsoluteX, 7, 0),               // 0xDF
+    op("CPX", AddressingMode::Immediate, 2, 0),                         // 0xE0
+    op("SBC", AddressingMode::IndirectX, 6, 0),                         // 0xE1
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0xE2
+    undocumented("ISC", AddressingMode::IndirectX, 8, 0),               // 0xE3
+    op("CPX", AddressingMode::ZeroPage, 3, 0),                          // 0xE4
+    op("SBC", AddressingMode::ZeroPage, 3, 0),                          // 0xE5
+    op("INC", AddressingMode::ZeroPage, 5, 0),                          // 0xE6
+    undocumented("ISC", AddressingMode::ZeroPage, 5, 0),                // 0xE7
+    op("INX", AddressingMode::Implied, 2, 0),                           // 0xE8
+    op("SBC", AddressingMode::Immediate, 2, 0),                         // 0xE9
+    op("NOP", AddressingMode::Implied, 2, 0),                           // 0xEA
+    undocumented("USBC", AddressingMode::Immediate, 2, 0),              // 0xEB
+    op("CPX", AddressingMode::Absolute, 4, 0),                          // 0xEC
+    op("SBC", AddressingMode::Absolute, 4, 0),                          // 0xED
+    op("INC", AddressingMode::Absolute, 6, 0),                          // 0xEE
+    undocumented("ISC", AddressingMode::Absolute, 6, 0),                // 0xEF
+    op("BEQ", AddressingMode::Relative, 2, 1),                          // 0xF0
+    op("SBC", AddressingMode::IndirectY, 5, 1),                         // 0xF1
+    undocumented("JAM", AddressingMode::Implied, 0, 0),                 // 0xF2
+    undocumented("ISC", AddressingMode::IndirectY, 8, 0),               // 0xF3
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0xF4
+    op("SBC", AddressingMode::ZeroPageX, 4, 0),                         // 0xF5
+    op("INC", AddressingMode::ZeroPageX, 6, 0),                         // 0xF6
+    undocumented("ISC", AddressingMode::ZeroPageX, 6, 0),               // 0xF7
+    op("SED", AddressingMode::Implied, 2, 0),                           // 0xF8
+    op("SBC", AddressingMode::AbsoluteY, 4, 1),                         // 0xF9
+    undocumented("NOP", AddressingMode::Implied, 2, 0),                 // 0xFA
+    undocumented("ISC", AddressingMode::AbsoluteY, 7, 0),               // 0xFB
+    undocumented("NOP", AddressingMode::AbsoluteX, 4, 1),               // 0xFC
+    op("SBC", AddressingMode::AbsoluteX, 4, 1),                         // 0xFD
+    op("INC", AddressingMode::AbsoluteX, 7, 0),                         // 0xFE
+    undocumented("ISC", AddressingMode::AbsoluteX, 7, 0),               // 0xFF
+];
+
+// the WDC 65C02: every Rockwell bit instruction plus WAI and STP
+pub static CMOS_OPCODES: [OpcodeInfo; 256] = [
+    op("BRK", AddressingMode::Implied, 7, 0),                           // 0x00
+    op("ORA", AddressingMode::IndirectX, 6, 0),                         // 0x01
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x02
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x03
+    op("TSB", AddressingMode::ZeroPage, 5, 0),                          // 0x04
+    op("ORA", AddressingMode::ZeroPage, 3, 0),                          // 0x05
+    op("ASL", AddressingMode::ZeroPage, 5, 0),                          // 0x06
+    op("RMB0", AddressingMode::ZeroPage, 5, 0),                         // 0x07
+    op("PHP", AddressingMode::Implied, 3, 0),                           // 0x08
+    op("ORA", AddressingMode::Immediate, 2, 0),                         // 0x09
+    op("ASL", AddressingMode::Accumulator, 2, 0),                       // 0x0A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x0B
+    op("TSB", AddressingMode::Absolute, 6, 0),                          // 0x0C
+    op("ORA", AddressingMode::Absolute, 4, 0),                          // 0x0D
+    op("ASL", AddressingMode::Absolute, 6, 0),                          // 0x0E
+    op("BBR0", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x0F
+    op("BPL", AddressingMode::Relative, 2, 1),                          // 0x10
+    op("ORA", AddressingMode::IndirectY, 5, 1),                         // 0x11
+    op("ORA", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0x12
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x13
+    op("TRB", AddressingMode::ZeroPage, 5, 0),                          // 0x14
+    op("ORA", AddressingMode::ZeroPageX, 4, 0),                         // 0x15
+    op("ASL", AddressingMode::ZeroPageX, 6, 0),                         // 0x16
+    op("RMB1", AddressingMode::ZeroPage, 5, 0),                         // 0x17
+    op("CLC", AddressingMode::Implied, 2, 0),                           // 0x18
+    op("ORA", AddressingMode::AbsoluteY, 4, 1),                         // 0x19
+    op("INC", AddressingMode::Accumulator, 2, 0),                       // 0x1A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x1B
+    op("TRB", AddressingMode::Absolute, 6, 0),                          // 0x1C
+    op("ORA", AddressingMode::AbsoluteX, 4, 1),                         // 0x1D
+    op("ASL", AddressingMode::AbsoluteX, 6, 1),                         // 0x1E
+    op("BBR1", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x1F
+    op("JSR", AddressingMode::Absolute, 6, 0),                          // 0x20
+    op("AND", AddressingMode::IndirectX, 6, 0),                         // 0x21
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x22
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x23
+    op("BIT", AddressingMode::ZeroPage, 3, 0),                          // 0x24
+    op("AND", AddressingMode::ZeroPage, 3, 0),                          // 0x25
+    op("ROL", AddressingMode::ZeroPage, 5, 0),                          // 0x26
+    op("RMB2", AddressingMode::ZeroPage, 5, 0),                         // 0x27
+    op("PLP", AddressingMode::Implied, 4, 0),                           // 0x28
+    op("AND", AddressingMode::Immediate, 2, 0),                         // 0x29
+    op("ROL", AddressingMode::Accumulator, 2, 0),                       // 0x2A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x2B
+    op("BIT", AddressingMode::Absolute, 4, 0),                          // 0x2C
+    op("AND", AddressingMode::Absolute, 4, 0),                          // 0x2D
+    op("ROL", AddressingMode::Absolute, 6, 0),                          // 0x2E
+    op("BBR2", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x2F
+    op("BMI", AddressingMode::Relative, 2, 1),                          // 0x30
+    op("AND", AddressingMode::IndirectY, 5, 1),                         // 0x31
+    op("AND", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0x32
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x33
+    op("BIT", AddressingMode::ZeroPageX, 4, 0),                         // 0x34
+    op("AND", AddressingMode::ZeroPageX, 4, 0),                         // 0x35
+    op("ROL", AddressingMode::ZeroPageX, 6, 0),                         // 0x36
+    op("RMB3", AddressingMode::ZeroPage, 5, 0),                         // 0x37
+    op("SEC", AddressingMode::Implied, 2, 0),                           // 0x38
+    op("AND", AddressingMode::AbsoluteY, 4, 1),                         // 0x39
+    op("DEC", AddressingMode::Accumulator, 2, 0),                       // 0x3A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x3B
+    op("BIT", AddressingMode::AbsoluteX, 4, 1),                         // 0x3C
+    op("AND", AddressingMode::AbsoluteX, 4, 1),                         // 0x3D
+    op("ROL", AddressingMode::AbsoluteX, 6, 1),                         // 0x3E
+    op("BBR3", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x3F
+    op("RTI", AddressingMode::Implied, 6, 0),                           // 0x40
+    op("EOR", AddressingMode::IndirectX, 6, 0),                         // 0x41
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x42
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x43
+    undocumented("NOP", AddressingMode::ZeroPage, 3, 0),                // 0x44
+    op("EOR", AddressingMode::ZeroPage, 3, 0),                          // 0x45
+    op("LSR", AddressingMode::ZeroPage, 5, 0),                          // 0x46
+    op("RMB4", AddressingMode::ZeroPage, 5, 0),                         // 0x47
+    op("PHA", AddressingMode::Implied, 3, 0),                           // 0x48
+    op("EOR", AddressingMode::Immediate, 2, 0),                         // 0x49
+    op("LSR", AddressingMode::Accumulator, 2, 0),                       // 0x4A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x4B
+    op("JMP", AddressingMode::Absolute, 3, 0),                          // 0x4C
+    op("EOR", AddressingMode::Absolute, 4, 0),                          // 0x4D
+    op("LSR", AddressingMode::Absolute, 6, 0),                          // 0x4E
+    op("BBR4", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x4F
+    op("BVC", AddressingMode::Relative, 2, 1),                          // 0x50
+    op("EOR", AddressingMode::IndirectY, 5, 1),                         // 0x51
+    op("EOR", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0x52
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x53
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0x54
+    op("EOR", AddressingMode::ZeroPageX, 4, 0),                         // 0x55
+    op("LSR", AddressingMode::ZeroPageX, 6, 0),                         // 0x56
+    op("RMB5", AddressingMode::ZeroPage, 5, 0),                         // 0x57
+    op("CLI", AddressingMode::Implied, 2, 0),                           // 0x58
+    op("EOR", AddressingMode::AbsoluteY, 4, 1),                         // 0x59
+    op("PHY", AddressingMode::Implied, 3, 0),                           // 0x5A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x5B
+    undocumented("NOP", AddressingMode::Absolute, 8, 0),                // 0x5C
+    op("EOR", AddressingMode::AbsoluteX, 4, 1),                         // 0x5D
+    op("LSR", AddressingMode::AbsoluteX, 6, 1),                         // 0x5E
+    op("BBR5", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x5F
+    op("RTS", AddressingMode::Implied, 6, 0),                           // 0x60
+    op("ADC", AddressingMode::IndirectX, 6, 0),                         // 0x61
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x62
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x63
+    op("STZ", AddressingMode::ZeroPage, 3, 0),                          // 0x64
+    op("ADC", AddressingMode::ZeroPage, 3, 0),                          // 0x65
+    op("ROR", AddressingMode::ZeroPage, 5, 0),                          // 0x66
+    op("RMB6", AddressingMode::ZeroPage, 5, 0),                         // 0x67
+    op("PLA", AddressingMode::Implied, 4, 0),                           // 0x68
+    op("ADC", AddressingMode::Immediate, 2, 0),                         // 0x69
+    op("ROR", AddressingMode::Accumulator, 2, 0),                       // 0x6A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x6B
+    op("JMP", AddressingMode::Indirect, 6, 0),                          // 0x6C
+    op("ADC", AddressingMode::Absolute, 4, 0),                          // 0x6D
+    op("ROR", AddressingMode::Absolute, 6, 0),                          // 0x6E
+    op("BBR6", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x6F
+    op("BVS", AddressingMode::Relative, 2, 1),                          // 0x70
+    op("ADC", AddressingMode::IndirectY, 5, 1),                         // 0x71
+    op("ADC", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0x72
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x73
+    op("STZ", AddressingMode::ZeroPageX, 4, 0),                         // 0x74
+    op("ADC", AddressingMode::ZeroPageX, 4, 0),                         // 0x75
+    op("ROR", AddressingMode::ZeroPageX, 6, 0),                         // 0x76
+    op("RMB7", AddressingMode::ZeroPage, 5, 0),                         // 0x77
+    op("SEI", AddressingMode::Implied, 2, 0),                           // 0x78
+    op("ADC", AddressingMode::AbsoluteY, 4, 1),                         // 0x79
+    op("PLY", AddressingMode::Implied, 4, 0),                           // 0x7A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x7B
+    op("JMP", AddressingMode::AbsoluteXIndirect, 6, 0),                 // 0x7C
+    op("ADC", AddressingMode::AbsoluteX, 4, 1),                         // 0x7D
+    op("ROR", AddressingMode::AbsoluteX, 6, 1),                         // 0x7E
+    op("BBR7", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x7F
+    op("BRA", AddressingMode::Relative, 3, 1),                          // 0x80
+    op("STA", AddressingMode::IndirectX, 6, 0),                         // 0x81
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0x82
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x83
+    op("STY", AddressingMode::ZeroPage, 3, 0),                          // 0x84
+    op("STA", AddressingMode::ZeroPage, 3, 0),                          // 0x85
+    op("STX", AddressingMode::ZeroPage, 3, 0),                          // 0x86
+    op("SMB0", AddressingMode::ZeroPage, 5, 0),                         // 0x87
+    op("DEY", AddressingMode::Implied, 2, 0),                           // 0x88
+    op("BIT", AddressingMode::Immediate, 2, 0),                         // 0x89
+    op("TXA", AddressingMode::Implied, 2, 0),                           // 0x8A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x8B
+    op("STY", AddressingMode::Absolute, 4, 0),                          // 0x8C
+    op("STA", AddressingMode::Absolute, 4, 0),                          // 0x8D
+    op("STX", AddressingMode::Absolute, 4, 0),                          // 0x8E
+    op("BBS0", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x8F
+    op("BCC", AddressingMode::Relative, 2, 1),                          // 0x90
+    op("STA", AddressingMode::IndirectY, 6, 0),                         // 0x91
+    op("STA", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0x92
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x93
+    op("STY", AddressingMode::ZeroPageX, 4, 0),                         // 0x94
+    op("STA", AddressingMode::ZeroPageX, 4, 0),                         // 0x95
+    op("STX", AddressingMode::ZeroPageY, 4, 0),                         // 0x96
+    op("SMB1", AddressingMode::ZeroPage, 5, 0),                         // 0x97
+    op("TYA", AddressingMode::Implied, 2, 0),                           // 0x98
+    op("STA", AddressingMode::AbsoluteY, 5, 0),                         // 0x99
+    op("TXS", AddressingMode::Implied, 2, 0),                           // 0x9A
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0x9B
+    op("STZ", AddressingMode::Absolute, 4, 0),                          // 0x9C
+    op("STA", AddressingMode::AbsoluteX, 5, 0),                         // 0x9D
+    op("STZ", AddressingMode::AbsoluteX, 5, 0),                         // 0x9E
+    op("BBS1", AddressingMode::ZeroPageRelative, 5, 1),                 // 0x9F
+    op("LDY", AddressingMode::Immediate, 2, 0),                         // 0xA0
+    op("LDA", AddressingMode::IndirectX, 6, 0),                         // 0xA1
+    op("LDX", AddressingMode::Immediate, 2, 0),                         // 0xA2
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xA3
+    op("LDY", AddressingMode::ZeroPage, 3, 0),                          // 0xA4
+    op("LDA", AddressingMode::ZeroPage, 3, 0),                          // 0xA5
+    op("LDX", AddressingMode::ZeroPage, 3, 0),                          // 0xA6
+    op("SMB2", AddressingMode::ZeroPage, 5, 0),                         // 0xA7
+    op("TAY", AddressingMode::Implied, 2, 0),                           // 0xA8
+    op("LDA", AddressingMode::Immediate, 2, 0),                         // 0xA9
+    op("TAX", AddressingMode::Implied, 2, 0),                           // 0xAA
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xAB
+    op("LDY", AddressingMode::Absolute, 4, 0),                          // 0xAC
+    op("LDA", AddressingMode::Absolute, 4, 0),                          // 0xAD
+    op("LDX", AddressingMode::Absolute, 4, 0),                          // 0xAE
+    op("BBS2", AddressingMode::ZeroPageRelative, 5, 1),                 // 0xAF
+    op("BCS", AddressingMode::Relative, 2, 1),                          // 0xB0
+    op("LDA", AddressingMode::IndirectY, 5, 1),                         // 0xB1
+    op("LDA", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0xB2
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xB3
+    op("LDY", AddressingMode::ZeroPageX, 4, 0),                         // 0xB4
+    op("LDA", AddressingMode::ZeroPageX, 4, 0),                         // 0xB5
+    op("LDX", AddressingMode::ZeroPageY, 4, 0),                         // 0xB6
+    op("SMB3", AddressingMode::ZeroPage, 5, 0),                         // 0xB7
+    op("CLV", AddressingMode::Implied, 2, 0),                           // 0xB8
+    op("LDA", AddressingMode::AbsoluteY, 4, 1),                         // 0xB9
+    op("TSX", AddressingMode::Implied, 2, 0),                           // 0xBA
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xBB
+    op("LDY", AddressingMode::AbsoluteX, 4, 1),                         // 0xBC
+    op("LDA", AddressingMode::AbsoluteX, 4, 1),                         // 0xBD
+    op("LDX", AddressingMode::AbsoluteY, 4, 1),                         // 0xBE
+    op("BBS3", AddressingMode::ZeroPageRelative, 5, 1),                 // 0xBF
+    op("CPY", AddressingMode::Immediate, 2, 0),                         // 0xC0
+    op("CMP", AddressingMode::IndirectX, 6, 0),                         // 0xC1
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0xC2
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xC3
+    op("CPY", AddressingMode::ZeroPage, 3, 0),                          // 0xC4
+    op("CMP", AddressingMode::ZeroPage, 3, 0),                          // 0xC5
+    op("DEC", AddressingMode::ZeroPage, 5, 0),                          // 0xC6
+    op("SMB4", AddressingMode::ZeroPage, 5, 0),                         // 0xC7
+    op("INY", AddressingMode::Implied, 2, 0),                           // 0xC8
+    op("CMP", AddressingMode::Immediate, 2, 0),                         // 0xC9
+    op("DEX", AddressingMode::Implied, 2, 0),                           // 0xCA
+    op("WAI", AddressingMode::Implied, 3, 0),                           // 0xCB
+    op("CPY", AddressingMode::Absolute, 4, 0),                          // 0xCC
+    op("CMP", AddressingMode::Absolute, 4, 0),                          // 0xCD
+    op("DEC", AddressingMode::Absolute, 6, 0),                          // 0xCE
+    op("BBS4", AddressingMode::ZeroPageRelative, 5, 1),                 // 0xCF
+    op("BNE", AddressingMode::Relative, 2, 1),                          // 0xD0
+    op("CMP", AddressingMode::IndirectY, 5, 1),                         // 0xD1
+    op("CMP", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0xD2
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xD3
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0xD4
+    op("CMP", AddressingMode::ZeroPageX, 4, 0),                         // 0xD5
+    op("DEC", AddressingMode::ZeroPageX, 6, 0),                         // 0xD6
+    op("SMB5", AddressingMode::ZeroPage, 5, 0),                         // 0xD7
+    op("CLD", AddressingMode::Implied, 2, 0),                           // 0xD8
+    op("CMP", AddressingMode::AbsoluteY, 4, 1),                         // 0xD9
+    op("PHX", AddressingMode::Implied, 3, 0),                           // 0xDA
+    op("STP", AddressingMode::Implied, 3, 0),                           // 0xDB
+    undocumented("NOP", AddressingMode::Absolute, 4, 0),                // 0xDC
+    op("CMP", AddressingMode::AbsoluteX, 4, 1),                         // 0xDD
+    op("DEC", AddressingMode::AbsoluteX, 7, 0),                         // 0xDE
+    op("BBS5", AddressingMode::ZeroPageRelative, 5, 1),                 // 0xDF
+    op("CPX", AddressingMode::Immediate, 2, 0),                         // 0xE0
+    op("SBC", AddressingMode::IndirectX, 6, 0),                         // 0xE1
+    undocumented("NOP", AddressingMode::Immediate, 2, 0),               // 0xE2
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xE3
+    op("CPX", AddressingMode::ZeroPage, 3, 0),                          // 0xE4
+    op("SBC", AddressingMode::ZeroPage, 3, 0),                          // 0xE5
+    op("INC", AddressingMode::ZeroPage, 5, 0),                          // 0xE6
+    op("SMB6", AddressingMode::ZeroPage, 5, 0),                         // 0xE7
+    op("INX", AddressingMode::Implied, 2, 0),                           // 0xE8
+    op("SBC", AddressingMode::Immediate, 2, 0),                         // 0xE9
+    op("NOP", AddressingMode::Implied, 2, 0),                           // 0xEA
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xEB
+    op("CPX", AddressingMode::Absolute, 4, 0),                          // 0xEC
+    op("SBC", AddressingMode::Absolute, 4, 0),                          // 0xED
+    op("INC", AddressingMode::Absolute, 6, 0),                          // 0xEE
+    op("BBS6", AddressingMode::ZeroPageRelative, 5, 1),                 // 0xEF
+    op("BEQ", AddressingMode::Relative, 2, 1),                          // 0xF0
+    op("SBC", AddressingMode::IndirectY, 5, 1),                         // 0xF1
+    op("SBC", AddressingMode::ZeroPageIndirect, 5, 0),                  // 0xF2
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xF3
+    undocumented("NOP", AddressingMode::ZeroPageX, 4, 0),               // 0xF4
+    op("SBC", AddressingMode::ZeroPageX, 4, 0),                         // 0xF5
+    op("INC", AddressingMode::ZeroPageX, 6, 0),                         // 0xF6
+    op("SMB7", AddressingMode::ZeroPage, 5, 0),                         // 0xF7
+    op("SED", AddressingMode::Implied, 2, 0),                           // 0xF8
+    op("SBC", AddressingMode::AbsoluteY, 4, 1),                         // 0xF9
+    op("PLX", AddressingMode::Implied, 4, 0),                           // 0xFA
+    undocumented("NOP", AddressingMode::Implied, 1, 0),                 // 0xFB
+    undocumented("NOP", AddressingMode::Absolute, 4, 0),                // 0xFC
+    op("SBC", AddressingMode::AbsoluteX, 4, 1),                         // 0xFD
+    op("INC", AddressingMode::AbsoluteX, 7, 0),                         // 0xFE
+    op("BBS7", AddressingMode::ZeroPageRelative, 5, 1),                 // 0xFF
+];
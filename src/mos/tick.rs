@@ -0,0 +1,111 @@
+use super::{Access, Bus, BusCycle, Byte, EmuError, Word, CPU};
+
+/* PER-CYCLE EXECUTION */
+
+// tick() advances the cpu by one clock. Instructions are written as whole functions, so an
+// instruction in flight is resumed by running it again from the registers it started with:
+// cycles already clocked are replayed from a log without touching the bus, the next cycle is
+// performed for real, and whatever comes after it runs speculatively against peek() with its
+// writes dropped. The new register state is only committed once the final cycle has happened.
+
+#[derive(Clone)]
+pub(super) struct TickState {
+    start: CPU,                     // registers at the start of the instruction
+    cycles: Vec<BusCycle>,          // bus accesses made so far
+}
+
+struct ReplayBus<'a, B: Bus> {
+    inner: &'a mut B,
+    cycles: &'a mut Vec<BusCycle>,
+    position: usize,                // accesses made by the current run
+    target: usize,                  // index of the access performed for real
+}
+
+impl<B: Bus> Bus for ReplayBus<'_, B> {
+    fn read(&mut self, address: Word) -> Byte {
+        let position: usize = self.position;
+        self.position += 1;
+        if position < self.target {
+            return self.cycles[position].value;
+        }
+        if position == self.target {
+            let value: Byte = self.inner.read(address);
+            self.cycles.push(BusCycle { address, value, access: Access::Read });
+            return value;
+        }
+        self.inner.peek(address)
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        let position: usize = self.position;
+        self.position += 1;
+        if position == self.target {
+            self.inner.write(address, value);
+            self.cycles.push(BusCycle { address, value, access: Access::Write });
+        }
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        self.inner.peek(address)
+    }
+}
+
+impl CPU {
+
+    // advances exactly one clock cycle and returns the bus access made during it; on an error
+    // the cpu is left at the start of the instruction that failed
+    pub fn tick<B: Bus>(&mut self, bus: &mut B) -> Result<BusCycle, EmuError> {
+        let mut state: Box<TickState> = match self.tick_state.take() {
+            Some(state) => state,
+            None => Box::new(TickState { start: self.clone(), cycles: Vec::new() }),
+        };
+        let target: usize = state.cycles.len();
+
+        let mut scratch: CPU = state.start.clone();
+        scratch.cycle_accurate = true;
+        let mut replay: ReplayBus<B> = ReplayBus { inner: bus, cycles: &mut state.cycles, position: 0, target };
+        let result: Result<i32, EmuError> = scratch.execute_instruction(0, &mut replay);
+        let position: usize = replay.position;
+
+        // an error raised after a later cycle's access belongs to that later cycle
+        if let Err(err) = result {
+            if position <= target + 1 {
+                // a jam is the one error that leaves its mark on the cpu
+                self.jammed = matches!(err, EmuError::Jammed { .. });
+                return Err(err);
+            }
+        }
+        if let Some(address) = bus.take_fault() {
+            return Err(EmuError::BusFault { address });
+        }
+
+        let cycle: BusCycle = state.cycles[target];
+        if result.is_ok() && position == target + 1 {
+            // last cycle: commit, keeping interrupt lines that changed while the instruction ran
+            let nmi_latched: bool = self.nmi_pending && !state.start.nmi_pending;
+            scratch.irq_line = self.irq_line;
+            scratch.nmi_line = self.nmi_line;
+            scratch.nmi_pending |= nmi_latched;
+            scratch.cycle_accurate = self.cycle_accurate;
+            *self = scratch;
+        } else {
+            self.tick_state = Some(state);
+        }
+        Ok(cycle)
+    }
+
+    // true between instructions, false while tick() is part way through one
+    pub fn at_instruction_boundary(&self) -> bool {
+        self.tick_state.is_none()
+    }
+
+    // clocks the rest of an instruction started with tick(), returns the cycles it took
+    pub(super) fn finish_instruction<B: Bus>(&mut self, bus: &mut B) -> Result<i32, EmuError> {
+        let mut cycles: i32 = 0;
+        while !self.at_instruction_boundary() {
+            self.tick(bus)?;
+            cycles += 1;
+        }
+        Ok(cycles)
+    }
+}
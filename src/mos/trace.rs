@@ -0,0 +1,135 @@
+use std::io;
+
+use super::disasm::{build_disassembler, Disassembler, Instruction};
+use super::opcode_table::AddressingMode;
+use super::{Bus, Byte, EmuError, Variant, Word, CPU};
+
+/* TRACER */
+
+// writes one line per instruction in the nestest.log layout, so a run can be diffed line
+// by line against reference logs of other emulators:
+//
+// C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD CYC:7
+//
+// the state is the one before the instruction executes, memory operands show the effective
+// address and the value found there. There is no PPU, so the PPU column of NES logs is left
+// out and has to be cut from a reference log before comparing.
+
+pub struct Tracer<W: io::Write> {
+    pub disassembler: Disassembler,
+    pub output: W,
+    pub cycles: u64,                // running total printed as CYC, nestest starts at 7
+}
+
+pub fn build_tracer<W: io::Write>(variant: Variant, output: W) -> Tracer<W> {
+    Tracer {
+        disassembler: build_disassembler(variant),
+        output,
+        cycles: 0,
+    }
+}
+
+impl<W: io::Write> Tracer<W> {
+
+    // logs the instruction at pc, then executes it
+    pub fn step<B: Bus>(&mut self, cpu: &mut CPU, bus: &mut B) -> Result<i32, EmuError> {
+        let line: String = self.trace_line(cpu, bus);
+        // a broken log is no reason to stop the emulation
+        let _ = writeln!(self.output, "{}", line);
+        let cycles: i32 = cpu.step(bus)?;
+        self.cycles += cycles as u64;
+        Ok(cycles)
+    }
+
+    // traces up to `instructions` instructions, returns the cycles they took
+    pub fn run<B: Bus>(&mut self, cpu: &mut CPU, bus: &mut B, instructions: usize) -> Result<u64, EmuError> {
+        let start: u64 = self.cycles;
+        for _ in 0..instructions {
+            self.step(cpu, bus)?;
+        }
+        Ok(self.cycles - start)
+    }
+
+    // the log line for the instruction at pc, memory is only peeked at
+    pub fn trace_line<B: Bus>(&self, cpu: &CPU, bus: &B) -> String {
+        let instruction: Instruction = self.disassembler.disassemble(bus, cpu.pc);
+        let bytes: Vec<String> = instruction.bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+        let flag: char = if instruction.illegal() { '*' } else { ' ' };
+        let text: String = format!("{} {}{}", nestest_mnemonic(instruction.info.mnemonic), instruction.operand, self.annotation(&instruction, cpu, bus));
+        // the B flag only exists on the stack, nestest prints the status with it clear
+        let status: Byte = (cpu.processor_status() | 0x20) & !0x10;
+        format!(
+            "{:04X}  {:<8} {}{:<32}A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+            cpu.pc, bytes.join(" "), flag, text.trim_end(), cpu.r_a, cpu.r_x, cpu.r_y, status, cpu.sp, self.cycles
+        )
+    }
+
+    // " @ 0300 = 89" and friends: the effective address and the value the operand names
+    fn annotation<B: Bus>(&self, instruction: &Instruction, cpu: &CPU, bus: &B) -> String {
+        let byte: Byte = instruction.bytes.get(1).copied().unwrap_or(0);
+        let word: Word = byte as Word | (instruction.bytes.get(2).copied().unwrap_or(0) as Word) << 8;
+        let jump: bool = matches!(instruction.info.mnemonic, "JMP" | "JSR");
+        match instruction.info.mode {
+            AddressingMode::ZeroPage => format!(" = {:02X}", bus.peek(byte as Word)),
+            AddressingMode::Absolute if jump => String::new(),
+            AddressingMode::Absolute => format!(" = {:02X}", bus.peek(word)),
+            AddressingMode::ZeroPageX => {
+                let address: Word = byte.wrapping_add(cpu.r_x) as Word;
+                format!(" @ {:02X} = {:02X}", address, bus.peek(address))
+            }
+            AddressingMode::ZeroPageY => {
+                let address: Word = byte.wrapping_add(cpu.r_y) as Word;
+                format!(" @ {:02X} = {:02X}", address, bus.peek(address))
+            }
+            AddressingMode::AbsoluteX => {
+                let address: Word = word.wrapping_add(cpu.r_x as Word);
+                format!(" @ {:04X} = {:02X}", address, bus.peek(address))
+            }
+            AddressingMode::AbsoluteY => {
+                let address: Word = word.wrapping_add(cpu.r_y as Word);
+                format!(" @ {:04X} = {:02X}", address, bus.peek(address))
+            }
+            AddressingMode::Indirect => {
+                // the NMOS part does not carry into the high byte of the pointer
+                let high: Word = match self.disassembler.variant.is_cmos() {
+                    true => word.wrapping_add(1),
+                    false => (word & 0xFF00) | (word.wrapping_add(1) & 0x00FF),
+                };
+                format!(" = {:04X}", bus.peek(word) as Word | (bus.peek(high) as Word) << 8)
+            }
+            AddressingMode::IndirectX => {
+                let pointer: Byte = byte.wrapping_add(cpu.r_x);
+                let address: Word = peek_zero_page_word(bus, pointer);
+                format!(" @ {:02X} = {:04X} = {:02X}", pointer, address, bus.peek(address))
+            }
+            AddressingMode::IndirectY => {
+                let base: Word = peek_zero_page_word(bus, byte);
+                let address: Word = base.wrapping_add(cpu.r_y as Word);
+                format!(" = {:04X} @ {:04X} = {:02X}", base, address, bus.peek(address))
+            }
+            AddressingMode::ZeroPageIndirect => {
+                let address: Word = peek_zero_page_word(bus, byte);
+                format!(" = {:04X} = {:02X}", address, bus.peek(address))
+            }
+            AddressingMode::AbsoluteXIndirect => {
+                let pointer: Word = word.wrapping_add(cpu.r_x as Word);
+                format!(" = {:04X}", bus.peek(pointer) as Word | (bus.peek(pointer.wrapping_add(1)) as Word) << 8)
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+// nestest.log names two of the illegal opcodes differently from the opcode table
+fn nestest_mnemonic(mnemonic: &str) -> &str {
+    match mnemonic {
+        "ISC" => "ISB",
+        "USBC" => "SBC",
+        _ => mnemonic,
+    }
+}
+
+// pointer stored in the zero page, wrapping from 0xFF to 0x00 like the cpu does
+fn peek_zero_page_word<B: Bus>(bus: &B, address: Byte) -> Word {
+    bus.peek(address as Word) as Word | (bus.peek(address.wrapping_add(1) as Word) as Word) << 8
+}
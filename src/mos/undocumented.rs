@@ -0,0 +1,463 @@
+use super::{Access, Bus, Byte, EmuError, Opcode, Opcodes, Word, CPU};
+
+/* UNDOCUMENTED NMOS OPCODES */
+
+// the NMOS decoder has no "invalid" entries: every opcode left out of the documented set
+// still drives the ALU and the bus. Most combine a read-modify-write with an ALU operation
+// (SLO = ASL + ORA, DCP = DEC + CMP, ...) and run with the cycle counts of the documented
+// instructions they are built from. The unstable ones depend on analog effects, so their
+// magic constants are configurable on the cpu.
+
+impl CPU {
+
+    /* combined read-modify-write operations */
+
+    // SLO: ASL the operand, then ORA it into A
+    fn shift_left_or(&mut self, value: Byte) -> Byte {
+        let result: Byte = self.shift_left(value);
+        self.logical_or(result);
+        result
+    }
+
+    // RLA: ROL the operand, then AND it into A
+    fn rotate_left_and(&mut self, value: Byte) -> Byte {
+        let result: Byte = self.rotate_left(value);
+        self.logical_and(result);
+        result
+    }
+
+    // SRE: LSR the operand, then EOR it into A
+    fn shift_right_exclusive_or(&mut self, value: Byte) -> Byte {
+        let result: Byte = self.shift_right(value);
+        self.exclusive_or(result);
+        result
+    }
+
+    // RRA: ROR the operand, then ADC it with the carry the rotate shifted out
+    fn rotate_right_add(&mut self, value: Byte) -> Byte {
+        let result: Byte = self.rotate_right(value);
+        self.add_with_carry(result);
+        result
+    }
+
+    // DCP: DEC the operand, then CMP A against it
+    fn decrement_compare(&mut self, value: Byte) -> Byte {
+        let result: Byte = value.wrapping_sub(1);
+        self.compare(self.r_a, result);
+        result
+    }
+
+    // ISC: INC the operand, then SBC it from A
+    fn increment_subtract(&mut self, value: Byte) -> Byte {
+        let result: Byte = value.wrapping_add(1);
+        self.subtract_with_carry(result);
+        result
+    }
+
+    fn load_accumulator_and_x(&mut self, value: Byte) {
+        self.r_a = value;
+        self.r_x = value;
+        self.set_zero_negative_flags(value);
+    }
+
+    // ARR: AND then ROR A, with C and V taken from bits 6 and 5 of the result;
+    // in decimal mode the NMOS part runs a BCD fixup on the rotated value, the 2A03
+    // has no decimal mode and always takes the binary path
+    fn and_rotate_right(&mut self, value: Byte) {
+        let anded: Byte = self.r_a & value;
+        let result: Byte = (anded >> 1) | (self.ps_carry << 7);
+
+        if self.ps_decimal == 0 || !self.variant.has_decimal_mode() {
+            self.r_a = result;
+            self.set_zero_negative_flags(result);
+            self.ps_carry = (result >> 6) & 1;
+            self.ps_overflow = ((result >> 6) ^ (result >> 5)) & 1;
+            return;
+        }
+
+        self.ps_negative = self.ps_carry;
+        self.ps_zero = (result == 0) as Byte;
+        self.ps_overflow = (((anded ^ result) & 0x40) != 0) as Byte;
+        let mut adjusted: Byte = result;
+        if (anded & 0x0F) + (anded & 0x01) > 0x05 {
+            adjusted = (adjusted & 0xF0) | (adjusted.wrapping_add(0x06) & 0x0F);
+        }
+        if (anded >> 4) + ((anded >> 4) & 0x01) > 0x05 {
+            self.ps_carry = 1;
+            adjusted = adjusted.wrapping_add(0x60);
+        } else {
+            self.ps_carry = 0;
+        }
+        self.r_a = adjusted;
+    }
+
+    // AHX / TAS / SHX / SHY: the value is ANDed with the high byte of the base address + 1,
+    // and when the index crosses a page that same value replaces the high byte of the target
+    fn store_and_high_byte<B: Bus>(&mut self, base: Word, index: Byte, value: Byte, bus: &mut B, cycles: i32) -> i32 {
+        let indexed_address: Word = base.wrapping_add(index as Word);
+        let cycles: i32 = self.dummy_read(CPU::unfixed_address(base, indexed_address), bus, cycles);
+        let mut value: Byte = value;
+        if self.sh_and_high_byte {
+            value &= ((base >> 8) as Byte).wrapping_add(1);
+        }
+        let mut address: Word = indexed_address;
+        if CPU::page_crossed(base, indexed_address) {
+            address = ((value as Word) << 8) | (indexed_address & 0x00FF);
+        }
+        CPU::write_byte(value, address, bus, cycles)
+    }
+
+    pub(super) fn execute_undocumented<B: Bus>(&mut self, instruction: Opcode, cycles: i32, bus: &mut B) -> Result<i32, EmuError> {
+
+        match instruction {
+
+            // SLO
+            CPU::SLO_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            CPU::SLO_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            CPU::SLO_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            CPU::SLO_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            CPU::SLO_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            CPU::SLO_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            CPU::SLO_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_left_or))
+            }
+            // RLA
+            CPU::RLA_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            CPU::RLA_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            CPU::RLA_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            CPU::RLA_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            CPU::RLA_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            CPU::RLA_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            CPU::RLA_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_left_and))
+            }
+            // SRE
+            CPU::SRE_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            CPU::SRE_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            CPU::SRE_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            CPU::SRE_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            CPU::SRE_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            CPU::SRE_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            CPU::SRE_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::shift_right_exclusive_or))
+            }
+            // RRA
+            CPU::RRA_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            CPU::RRA_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            CPU::RRA_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            CPU::RRA_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            CPU::RRA_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            CPU::RRA_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            CPU::RRA_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::rotate_right_add))
+            }
+            // DCP
+            CPU::DCP_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            CPU::DCP_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            CPU::DCP_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            CPU::DCP_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            CPU::DCP_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            CPU::DCP_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            CPU::DCP_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::decrement_compare))
+            }
+            // ISC
+            CPU::ISC_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+            CPU::ISC_ZERO_PAGE_X => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+            CPU::ISC_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+            CPU::ISC_ABSOLUTE_X => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+            CPU::ISC_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+            CPU::ISC_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+            CPU::ISC_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU::increment_subtract))
+            }
+
+            // LAX
+            CPU::LAX_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.load_accumulator_and_x(value);
+                Ok(cycles)
+            }
+            CPU::LAX_ZERO_PAGE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_y);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.load_accumulator_and_x(value);
+                Ok(cycles)
+            }
+            CPU::LAX_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.load_accumulator_and_x(value);
+                Ok(cycles)
+            }
+            CPU::LAX_ABSOLUTE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.load_accumulator_and_x(value);
+                Ok(cycles)
+            }
+            CPU::LAX_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.load_accumulator_and_x(value);
+                Ok(cycles)
+            }
+            CPU::LAX_INDIRECT_Y => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.load_accumulator_and_x(value);
+                Ok(cycles)
+            }
+
+            // SAX: stores A & X, flags untouched
+            CPU::SAX_ZERO_PAGE => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                Ok(CPU::write_byte(self.r_a & self.r_x, address, bus, cycles))
+            }
+            CPU::SAX_ZERO_PAGE_Y => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_y);
+                Ok(CPU::write_byte(self.r_a & self.r_x, address, bus, cycles))
+            }
+            CPU::SAX_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(CPU::write_byte(self.r_a & self.r_x, address, bus, cycles))
+            }
+            CPU::SAX_INDIRECT_X => {
+                let (address, cycles): (Word, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(CPU::write_byte(self.r_a & self.r_x, address, bus, cycles))
+            }
+
+            // immediate logic
+            CPU::ANC_IMMEDIATE | 0x2B => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.logical_and(value);
+                self.ps_carry = self.ps_negative;
+                Ok(cycles)
+            }
+            CPU::ALR_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.r_a = self.shift_right(self.r_a & value);
+                Ok(cycles)
+            }
+            CPU::ARR_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.and_rotate_right(value);
+                Ok(cycles)
+            }
+            CPU::SBX_IMMEDIATE => {
+                // X = (A & X) - #imm, flags as CMP and never in decimal
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                let anded: Byte = self.r_a & self.r_x;
+                self.compare(anded, value);
+                self.r_x = anded.wrapping_sub(value);
+                Ok(cycles)
+            }
+            CPU::USBC_IMMEDIATE => {
+                // identical to SBC #imm
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+
+            // unstable
+            CPU::XAA_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.r_a = (self.r_a | self.xaa_magic) & self.r_x & value;
+                self.set_zero_negative_flags(self.r_a);
+                Ok(cycles)
+            }
+            CPU::LXA_IMMEDIATE => {
+                let (value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.load_accumulator_and_x((self.r_a | self.lxa_magic) & value);
+                Ok(cycles)
+            }
+            CPU::AHX_ABSOLUTE_Y => {
+                let (base, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.store_and_high_byte(base, self.r_y, self.r_a & self.r_x, bus, cycles))
+            }
+            CPU::AHX_INDIRECT_Y => {
+                let (zero_page_address, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                let (base, cycles): (Word, i32) = CPU::read_word_zero_page(zero_page_address, bus, cycles);
+                Ok(self.store_and_high_byte(base, self.r_y, self.r_a & self.r_x, bus, cycles))
+            }
+            CPU::TAS_ABSOLUTE_Y => {
+                let (base, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                self.sp = self.r_a & self.r_x;
+                Ok(self.store_and_high_byte(base, self.r_y, self.sp, bus, cycles))
+            }
+            CPU::SHY_ABSOLUTE_X => {
+                let (base, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.store_and_high_byte(base, self.r_x, self.r_y, bus, cycles))
+            }
+            CPU::SHX_ABSOLUTE_Y => {
+                let (base, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.store_and_high_byte(base, self.r_y, self.r_x, bus, cycles))
+            }
+            CPU::LAS_ABSOLUTE_Y => {
+                // A = X = SP = M & SP
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                self.sp &= value;
+                self.load_accumulator_and_x(self.sp);
+                Ok(cycles)
+            }
+
+            // multi-byte NOPs read their operand and throw it away
+            0x1A | 0x3A | 0x5A | 0x7A | 0xDA | 0xFA => {
+                let cycles: i32 = self.dummy_read(self.pc, bus, cycles);
+                Ok(cycles)
+            }
+            CPU::NOP_IMMEDIATE | 0x82 | 0x89 | 0xC2 | 0xE2 => {
+                let (_value, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                Ok(cycles)
+            }
+            CPU::NOP_ZERO_PAGE | 0x44 | 0x64 => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page(bus, cycles);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+            CPU::NOP_ZERO_PAGE_X | 0x34 | 0x54 | 0x74 | 0xD4 | 0xF4 => {
+                let (address, cycles): (Word, i32) = self.addr_zero_page_indexed(bus, cycles, self.r_x);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+            CPU::NOP_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+            CPU::NOP_ABSOLUTE_X | 0x3C | 0x5C | 0x7C | 0xDC | 0xFC => {
+                let (address, cycles): (Word, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (_value, cycles): (Byte, i32) = CPU::read_byte(address, bus, cycles);
+                Ok(cycles)
+            }
+
+            // JAM / KIL: the cpu stops fetching with pc left on the opcode
+            CPU::JAM_IMPLIED | 0x12 | 0x22 | 0x32 | 0x42 | 0x52 | 0x62 | 0x72 | 0x92 | 0xB2 | 0xD2 | 0xF2 => {
+                self.pc = self.pc.wrapping_sub(1);
+                self.jammed = true;
+                Err(EmuError::Jammed { address: self.pc, opcode: instruction })
+            }
+
+            _ => {
+                Err(EmuError::IllegalOpcode { address: self.pc.wrapping_sub(1), opcode: instruction })
+            }
+        }
+
+    }
+}
@@ -0,0 +1,2382 @@
+use super::{Access, Bus, Byte, EmuError, Opcode, Opcodes, Word, CPU};
+
+/* 65C816 / 65802 */
+
+// the 16-bit member of the family. It resets into emulation mode, where it behaves as a
+// 65C02, and XCE switches it to native mode with 16-bit A (M flag clear) and X/Y (X flag
+// clear), a relocatable direct page, a 16-bit stack pointer and 24-bit addresses built
+// from the program and data bank registers. The 65802 is the same core on a 16-bit
+// address bus: run it on any 64 KiB Bus and the bank byte is simply not wired.
+//
+// timing follows the datasheet counts (extra cycles for 16-bit data, a direct page off
+// a page boundary and index page crossings); internal cycles are not put on the bus.
+
+type Long = u32;                // 3 bytes: 0x000000
+
+// native mode vectors, emulation mode shares the 6502 ones
+pub static NATIVE_COP_VECTOR: Word = 0xFFE4;
+pub static NATIVE_BRK_VECTOR: Word = 0xFFE6;
+pub static NATIVE_NMI_VECTOR: Word = 0xFFEA;
+pub static NATIVE_IRQ_VECTOR: Word = 0xFFEE;
+pub static EMULATION_COP_VECTOR: Word = 0xFFF4;
+
+/* LONG BUS */
+
+// the 24-bit address space: bank byte in bits 16-23
+pub trait LongBus {
+    fn read_long(&mut self, address: Long) -> Byte;
+    fn write_long(&mut self, address: Long, value: Byte);
+    fn peek_long(&self, address: Long) -> Byte;
+}
+
+// a 64 KiB bus has no bank lines, every bank sees the same memory (65802 wiring)
+impl<B: Bus> LongBus for B {
+    fn read_long(&mut self, address: Long) -> Byte {
+        self.read(address as Word)
+    }
+
+    fn write_long(&mut self, address: Long, value: Byte) {
+        self.write(address as Word, value)
+    }
+
+    fn peek_long(&self, address: Long) -> Byte {
+        self.peek(address as Word)
+    }
+}
+
+// flat RAM covering a number of 64 KiB banks, addresses past the end wrap around
+pub struct LongMemory {
+    pub memory: Vec<Byte>,
+}
+
+impl LongMemory {
+    fn index(&self, address: Long) -> usize {
+        (address & 0x00FF_FFFF) as usize % self.memory.len()
+    }
+}
+
+impl LongBus for LongMemory {
+    fn read_long(&mut self, address: Long) -> Byte {
+        self.memory[self.index(address)]
+    }
+
+    fn write_long(&mut self, address: Long, value: Byte) {
+        let index: usize = self.index(address);
+        self.memory[index] = value;
+    }
+
+    fn peek_long(&self, address: Long) -> Byte {
+        self.memory[self.index(address)]
+    }
+}
+
+pub fn build_long_memory(banks: usize) -> LongMemory {
+    LongMemory {
+        memory: vec![0; banks.clamp(1, 256) * 0x10000],
+    }
+}
+
+/* CPU */
+
+// opcodes the 65816 adds on top of the 65C02 set, the shared ones are on Opcodes
+pub trait Opcodes65816 {
+    // ORA
+    const ORA_STACK_RELATIVE: Opcode;
+    const ORA_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const ORA_ABSOLUTE_LONG: Opcode;
+    const ORA_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const ORA_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const ORA_ABSOLUTE_LONG_X: Opcode;
+    // AND
+    const AND_STACK_RELATIVE: Opcode;
+    const AND_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const AND_ABSOLUTE_LONG: Opcode;
+    const AND_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const AND_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const AND_ABSOLUTE_LONG_X: Opcode;
+    // EOR
+    const EOR_STACK_RELATIVE: Opcode;
+    const EOR_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const EOR_ABSOLUTE_LONG: Opcode;
+    const EOR_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const EOR_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const EOR_ABSOLUTE_LONG_X: Opcode;
+    // ADC
+    const ADC_STACK_RELATIVE: Opcode;
+    const ADC_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const ADC_ABSOLUTE_LONG: Opcode;
+    const ADC_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const ADC_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const ADC_ABSOLUTE_LONG_X: Opcode;
+    // STA
+    const STA_STACK_RELATIVE: Opcode;
+    const STA_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const STA_ABSOLUTE_LONG: Opcode;
+    const STA_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const STA_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const STA_ABSOLUTE_LONG_X: Opcode;
+    // LDA
+    const LDA_STACK_RELATIVE: Opcode;
+    const LDA_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const LDA_ABSOLUTE_LONG: Opcode;
+    const LDA_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const LDA_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const LDA_ABSOLUTE_LONG_X: Opcode;
+    // CMP
+    const CMP_STACK_RELATIVE: Opcode;
+    const CMP_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const CMP_ABSOLUTE_LONG: Opcode;
+    const CMP_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const CMP_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const CMP_ABSOLUTE_LONG_X: Opcode;
+    // SBC
+    const SBC_STACK_RELATIVE: Opcode;
+    const SBC_ZERO_PAGE_INDIRECT_LONG: Opcode;
+    const SBC_ABSOLUTE_LONG: Opcode;
+    const SBC_STACK_RELATIVE_INDIRECT_Y: Opcode;
+    const SBC_ZERO_PAGE_INDIRECT_LONG_Y: Opcode;
+    const SBC_ABSOLUTE_LONG_X: Opcode;
+    // register transfers
+    const TCS_IMPLIED: Opcode;
+    const TSC_IMPLIED: Opcode;
+    const TCD_IMPLIED: Opcode;
+    const TDC_IMPLIED: Opcode;
+    const TXY_IMPLIED: Opcode;
+    const TYX_IMPLIED: Opcode;
+    const XBA_IMPLIED: Opcode;
+    const XCE_IMPLIED: Opcode;
+    // status
+    const REP_IMMEDIATE: Opcode;
+    const SEP_IMMEDIATE: Opcode;
+    // stack operations
+    const PHB_IMPLIED: Opcode;
+    const PLB_IMPLIED: Opcode;
+    const PHD_IMPLIED: Opcode;
+    const PLD_IMPLIED: Opcode;
+    const PHK_IMPLIED: Opcode;
+    const PEA_ABSOLUTE: Opcode;
+    const PEI_ZERO_PAGE_INDIRECT: Opcode;
+    const PER_RELATIVE_LONG: Opcode;
+    // jumps and branches
+    const BRL_RELATIVE_LONG: Opcode;
+    const JML_ABSOLUTE_LONG: Opcode;
+    const JML_ABSOLUTE_INDIRECT_LONG: Opcode;
+    const JSL_ABSOLUTE_LONG: Opcode;
+    const JSR_ABSOLUTE_X_INDIRECT: Opcode;
+    const RTL_IMPLIED: Opcode;
+    // block moves
+    const MVN_BLOCK_MOVE: Opcode;
+    const MVP_BLOCK_MOVE: Opcode;
+    // COP / WDM
+    const COP_IMMEDIATE: Opcode;
+    const WDM_IMMEDIATE: Opcode;
+}
+
+impl Opcodes65816 for CPU65816 {
+    // ORA
+    const ORA_STACK_RELATIVE: Opcode = 0x03;
+    const ORA_ZERO_PAGE_INDIRECT_LONG: Opcode = 0x07;
+    const ORA_ABSOLUTE_LONG: Opcode = 0x0F;
+    const ORA_STACK_RELATIVE_INDIRECT_Y: Opcode = 0x13;
+    const ORA_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0x17;
+    const ORA_ABSOLUTE_LONG_X: Opcode = 0x1F;
+    // AND
+    const AND_STACK_RELATIVE: Opcode = 0x23;
+    const AND_ZERO_PAGE_INDIRECT_LONG: Opcode = 0x27;
+    const AND_ABSOLUTE_LONG: Opcode = 0x2F;
+    const AND_STACK_RELATIVE_INDIRECT_Y: Opcode = 0x33;
+    const AND_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0x37;
+    const AND_ABSOLUTE_LONG_X: Opcode = 0x3F;
+    // EOR
+    const EOR_STACK_RELATIVE: Opcode = 0x43;
+    const EOR_ZERO_PAGE_INDIRECT_LONG: Opcode = 0x47;
+    const EOR_ABSOLUTE_LONG: Opcode = 0x4F;
+    const EOR_STACK_RELATIVE_INDIRECT_Y: Opcode = 0x53;
+    const EOR_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0x57;
+    const EOR_ABSOLUTE_LONG_X: Opcode = 0x5F;
+    // ADC
+    const ADC_STACK_RELATIVE: Opcode = 0x63;
+    const ADC_ZERO_PAGE_INDIRECT_LONG: Opcode = 0x67;
+    const ADC_ABSOLUTE_LONG: Opcode = 0x6F;
+    const ADC_STACK_RELATIVE_INDIRECT_Y: Opcode = 0x73;
+    const ADC_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0x77;
+    const ADC_ABSOLUTE_LONG_X: Opcode = 0x7F;
+    // STA
+    const STA_STACK_RELATIVE: Opcode = 0x83;
+    const STA_ZERO_PAGE_INDIRECT_LONG: Opcode = 0x87;
+    const STA_ABSOLUTE_LONG: Opcode = 0x8F;
+    const STA_STACK_RELATIVE_INDIRECT_Y: Opcode = 0x93;
+    const STA_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0x97;
+    const STA_ABSOLUTE_LONG_X: Opcode = 0x9F;
+    // LDA
+    const LDA_STACK_RELATIVE: Opcode = 0xA3;
+    const LDA_ZERO_PAGE_INDIRECT_LONG: Opcode = 0xA7;
+    const LDA_ABSOLUTE_LONG: Opcode = 0xAF;
+    const LDA_STACK_RELATIVE_INDIRECT_Y: Opcode = 0xB3;
+    const LDA_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0xB7;
+    const LDA_ABSOLUTE_LONG_X: Opcode = 0xBF;
+    // CMP
+    const CMP_STACK_RELATIVE: Opcode = 0xC3;
+    const CMP_ZERO_PAGE_INDIRECT_LONG: Opcode = 0xC7;
+    const CMP_ABSOLUTE_LONG: Opcode = 0xCF;
+    const CMP_STACK_RELATIVE_INDIRECT_Y: Opcode = 0xD3;
+    const CMP_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0xD7;
+    const CMP_ABSOLUTE_LONG_X: Opcode = 0xDF;
+    // SBC
+    const SBC_STACK_RELATIVE: Opcode = 0xE3;
+    const SBC_ZERO_PAGE_INDIRECT_LONG: Opcode = 0xE7;
+    const SBC_ABSOLUTE_LONG: Opcode = 0xEF;
+    const SBC_STACK_RELATIVE_INDIRECT_Y: Opcode = 0xF3;
+    const SBC_ZERO_PAGE_INDIRECT_LONG_Y: Opcode = 0xF7;
+    const SBC_ABSOLUTE_LONG_X: Opcode = 0xFF;
+    // register transfers
+    const TCS_IMPLIED: Opcode = 0x1B;
+    const TSC_IMPLIED: Opcode = 0x3B;
+    const TCD_IMPLIED: Opcode = 0x5B;
+    const TDC_IMPLIED: Opcode = 0x7B;
+    const TXY_IMPLIED: Opcode = 0x9B;
+    const TYX_IMPLIED: Opcode = 0xBB;
+    const XBA_IMPLIED: Opcode = 0xEB;
+    const XCE_IMPLIED: Opcode = 0xFB;
+    // status
+    const REP_IMMEDIATE: Opcode = 0xC2;
+    const SEP_IMMEDIATE: Opcode = 0xE2;
+    // stack operations
+    const PHB_IMPLIED: Opcode = 0x8B;
+    const PLB_IMPLIED: Opcode = 0xAB;
+    const PHD_IMPLIED: Opcode = 0x0B;
+    const PLD_IMPLIED: Opcode = 0x2B;
+    const PHK_IMPLIED: Opcode = 0x4B;
+    const PEA_ABSOLUTE: Opcode = 0xF4;
+    const PEI_ZERO_PAGE_INDIRECT: Opcode = 0xD4;
+    const PER_RELATIVE_LONG: Opcode = 0x62;
+    // jumps and branches
+    const BRL_RELATIVE_LONG: Opcode = 0x82;
+    const JML_ABSOLUTE_LONG: Opcode = 0x5C;
+    const JML_ABSOLUTE_INDIRECT_LONG: Opcode = 0xDC;
+    const JSL_ABSOLUTE_LONG: Opcode = 0x22;
+    const JSR_ABSOLUTE_X_INDIRECT: Opcode = 0xFC;
+    const RTL_IMPLIED: Opcode = 0x6B;
+    // block moves
+    const MVN_BLOCK_MOVE: Opcode = 0x54;
+    const MVP_BLOCK_MOVE: Opcode = 0x44;
+    // COP / WDM
+    const COP_IMMEDIATE: Opcode = 0x02;
+    const WDM_IMMEDIATE: Opcode = 0x42;
+}
+
+#[derive(Clone)]
+pub struct CPU65816 {
+
+    // counters, pointers & banks
+    pub pc: Word,                   // program counter
+    pub sp: Word,                   // stack pointer, pinned to page 0x01 in emulation mode
+    pub pbr: Byte,                  // program bank register
+    pub dbr: Byte,                  // data bank register
+    pub d: Word,                    // direct page register
+
+    // registers
+    pub r_a: Word,                  // C accumulator: A in the low byte, B in the high byte
+    pub r_x: Word,                  // X register, high byte held at 0 while the X flag is set
+    pub r_y: Word,                  // Y register, high byte held at 0 while the X flag is set
+
+    // processor status bit fields
+    pub ps_carry: Byte,             // carry bit
+    pub ps_zero: Byte,              // zero bit
+    pub ps_interrupt: Byte,         // interrupt bit
+    pub ps_decimal: Byte,           // decimal mode bit
+    pub ps_index: Byte,             // X: 8-bit index registers
+    pub ps_memory: Byte,            // M: 8-bit accumulator and memory
+    pub ps_overflow: Byte,          // overflow bit
+    pub ps_negative: Byte,          // negative value bit
+    pub ps_emulation: Byte,         // E: hidden flag, swapped with carry by XCE
+
+    // interrupt lines
+    pub irq_line: bool,             // IRQ is level triggered: serviced while held and I is clear
+    pub nmi_line: bool,             // NMI is edge triggered: only a false -> true transition counts
+    pub nmi_pending: bool,          // latched NMI edge waiting for the next instruction boundary
+
+    // halt states
+    pub waiting: bool,              // WAI: idle until an interrupt line is asserted
+    pub stopped: bool,              // STP: clock halted, only a reset recovers it
+
+}
+
+impl CPU65816 {
+
+    // RESET always lands in emulation mode with 8-bit registers and the direct page at 0
+    pub fn reset_cpu<B: LongBus>(&mut self, bus: &mut B) {
+        let (reset_address, _): (Word, i32) = CPU65816::read_bank_zero_word(super::RESET_VECTOR, bus, 0);
+        self.pc = reset_address;
+        self.pbr = 0;
+        self.dbr = 0;
+        self.d = 0;
+        self.sp = 0x01FD;
+        self.r_x &= 0x00FF;
+        self.r_y &= 0x00FF;
+        self.ps_emulation = 1;
+        self.ps_memory = 1;
+        self.ps_index = 1;
+        self.ps_decimal = 0;
+        self.ps_interrupt = 1;
+        self.nmi_pending = false;
+        self.waiting = false;
+        self.stopped = false;
+    }
+
+    pub fn set_irq_line(&mut self, level: bool) {
+        self.irq_line = level;
+    }
+
+    pub fn set_nmi_line(&mut self, level: bool) {
+        if level && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = level;
+    }
+
+    // bits 4 and 5 are X and M in native mode; in emulation mode both read as set,
+    // which is what PHP and BRK push there for B and the unused bit
+    pub fn processor_status(&self) -> Byte {
+        (self.ps_negative << 7)
+            | (self.ps_overflow << 6)
+            | (self.ps_memory << 5)
+            | (self.ps_index << 4)
+            | (self.ps_decimal << 3)
+            | (self.ps_interrupt << 2)
+            | (self.ps_zero << 1)
+            | self.ps_carry
+    }
+
+    pub fn set_processor_status(&mut self, status: Byte) {
+        self.ps_negative = (status >> 7) & 1;
+        self.ps_overflow = (status >> 6) & 1;
+        self.ps_decimal = (status >> 3) & 1;
+        self.ps_interrupt = (status >> 2) & 1;
+        self.ps_zero = (status >> 1) & 1;
+        self.ps_carry = status & 1;
+        if self.ps_emulation == 0 {
+            self.ps_memory = (status >> 5) & 1;
+            self.ps_index = (status >> 4) & 1;
+        }
+        // 8-bit index registers lose their high byte
+        if self.ps_index == 1 {
+            self.r_x &= 0x00FF;
+            self.r_y &= 0x00FF;
+        }
+    }
+
+    // 24-bit address of the next instruction byte
+    pub fn program_address(&self) -> Long {
+        ((self.pbr as Long) << 16) | self.pc as Long
+    }
+
+    fn accumulator_wide(&self) -> bool {
+        self.ps_memory == 0
+    }
+
+    fn index_wide(&self) -> bool {
+        self.ps_index == 0
+    }
+
+    /* bus access */
+
+    fn fetch_byte<B: LongBus>(&mut self, bus: &mut B, mut cycles: i32) -> (Byte, i32) {
+        let byte: Byte = bus.read_long(self.program_address());
+        self.pc = self.pc.wrapping_add(1); // the program counter never carries into the bank
+        cycles -= 1;
+        (byte, cycles)
+    }
+
+    fn fetch_word<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let (hi_byte, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        (((hi_byte as Word) << 8) | lo_byte as Word, cycles)
+    }
+
+    fn fetch_long<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (word, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+        let (bank, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        (((bank as Long) << 16) | word as Long, cycles)
+    }
+
+    // immediate operands are one or two bytes depending on the register they load
+    fn fetch_immediate<B: LongBus>(&mut self, wide: bool, bus: &mut B, cycles: i32) -> (Word, i32) {
+        match wide {
+            true => self.fetch_word(bus, cycles),
+            false => {
+                let (byte, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                (byte as Word, cycles)
+            }
+        }
+    }
+
+    fn read_byte<B: LongBus>(address: Long, bus: &mut B, mut cycles: i32) -> (Byte, i32) {
+        let byte: Byte = bus.read_long(address & 0x00FF_FFFF);
+        cycles -= 1;
+        (byte, cycles)
+    }
+
+    fn write_byte<B: LongBus>(value: Byte, address: Long, bus: &mut B, mut cycles: i32) -> i32 {
+        bus.write_long(address & 0x00FF_FFFF, value);
+        cycles -= 1;
+        cycles
+    }
+
+    // 16-bit data may straddle a bank boundary, the high byte comes from the next bank
+    fn read_data<B: LongBus>(address: Long, wide: bool, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles): (Byte, i32) = CPU65816::read_byte(address, bus, cycles);
+        if !wide {
+            return (lo_byte as Word, cycles);
+        }
+        let (hi_byte, cycles): (Byte, i32) = CPU65816::read_byte(address.wrapping_add(1), bus, cycles);
+        (((hi_byte as Word) << 8) | lo_byte as Word, cycles)
+    }
+
+    fn write_data<B: LongBus>(value: Word, address: Long, wide: bool, bus: &mut B, cycles: i32) -> i32 {
+        let cycles: i32 = CPU65816::write_byte(value as Byte, address, bus, cycles);
+        if !wide {
+            return cycles;
+        }
+        CPU65816::write_byte((value >> 8) as Byte, address.wrapping_add(1), bus, cycles)
+    }
+
+    // vectors and pointers in bank 0 wrap at the end of the bank
+    fn read_bank_zero_word<B: LongBus>(address: Word, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles): (Byte, i32) = CPU65816::read_byte(address as Long, bus, cycles);
+        let (hi_byte, cycles): (Byte, i32) = CPU65816::read_byte(address.wrapping_add(1) as Long, bus, cycles);
+        (((hi_byte as Word) << 8) | lo_byte as Word, cycles)
+    }
+
+    fn internal_cycle(cycles: i32) -> i32 {
+        cycles - 1
+    }
+
+    /* direct page */
+
+    // in emulation mode a page aligned direct page behaves like the zero page and wraps
+    // inside its page, anywhere else it is a plain 16-bit offset into bank 0
+    fn direct_address(&self, offset: Word) -> Word {
+        if self.ps_emulation == 1 && (self.d & 0x00FF) == 0 {
+            return self.d | (offset & 0x00FF);
+        }
+        self.d.wrapping_add(offset)
+    }
+
+    // one extra cycle whenever the direct page is not page aligned
+    fn direct_cycle(&self, cycles: i32) -> i32 {
+        if (self.d & 0x00FF) != 0 {
+            return cycles - 1;
+        }
+        cycles
+    }
+
+    fn read_direct_word<B: LongBus>(&self, offset: Word, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles): (Byte, i32) = CPU65816::read_byte(self.direct_address(offset) as Long, bus, cycles);
+        let (hi_byte, cycles): (Byte, i32) = CPU65816::read_byte(self.direct_address(offset.wrapping_add(1)) as Long, bus, cycles);
+        (((hi_byte as Word) << 8) | lo_byte as Word, cycles)
+    }
+
+    fn read_direct_long<B: LongBus>(&self, offset: Word, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (word, cycles): (Word, i32) = self.read_direct_word(offset, bus, cycles);
+        let (bank, cycles): (Byte, i32) = CPU65816::read_byte(self.direct_address(offset.wrapping_add(2)) as Long, bus, cycles);
+        (((bank as Long) << 16) | word as Long, cycles)
+    }
+
+    fn data_bank_address(&self, address: Word) -> Long {
+        ((self.dbr as Long) << 16) | address as Long
+    }
+
+    fn page_crossed(address: Long, indexed_address: Long) -> bool {
+        (address & 0xFFFF00) != (indexed_address & 0xFFFF00)
+    }
+
+    /* addressing modes: resolve the 24-bit effective address of the operand */
+
+    fn addr_direct<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = self.direct_cycle(cycles);
+        (self.direct_address(offset as Word) as Long, cycles)
+    }
+
+    fn addr_direct_indexed<B: LongBus>(&mut self, bus: &mut B, cycles: i32, index: Word) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = self.direct_cycle(cycles);
+        let cycles: i32 = CPU65816::internal_cycle(cycles);
+        (self.direct_address((offset as Word).wrapping_add(index)) as Long, cycles)
+    }
+
+    fn addr_absolute<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+        (self.data_bank_address(address), cycles)
+    }
+
+    // indexing carries into the bank byte; 16-bit index registers always pay the extra cycle
+    fn addr_absolute_indexed<B: LongBus>(&mut self, bus: &mut B, cycles: i32, index: Word, access: Access) -> (Long, i32) {
+        let (address, mut cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+        let indexed_address: Long = (address + index as Long) & 0x00FF_FFFF;
+        if access == Access::Write || self.index_wide() || CPU65816::page_crossed(address, indexed_address) {
+            cycles = CPU65816::internal_cycle(cycles);
+        }
+        (indexed_address, cycles)
+    }
+
+    fn addr_absolute_long<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        self.fetch_long(bus, cycles)
+    }
+
+    fn addr_absolute_long_indexed<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (address, cycles): (Long, i32) = self.fetch_long(bus, cycles);
+        ((address + self.r_x as Long) & 0x00FF_FFFF, cycles)
+    }
+
+    // (dp,X)
+    fn addr_indirect_x<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = self.direct_cycle(cycles);
+        let cycles: i32 = CPU65816::internal_cycle(cycles);
+        let (pointer, cycles): (Word, i32) = self.read_direct_word((offset as Word).wrapping_add(self.r_x), bus, cycles);
+        (self.data_bank_address(pointer), cycles)
+    }
+
+    // (dp),Y
+    fn addr_indirect_y<B: LongBus>(&mut self, bus: &mut B, cycles: i32, access: Access) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = self.direct_cycle(cycles);
+        let (pointer, mut cycles): (Word, i32) = self.read_direct_word(offset as Word, bus, cycles);
+        let address: Long = self.data_bank_address(pointer);
+        let indexed_address: Long = (address + self.r_y as Long) & 0x00FF_FFFF;
+        if access == Access::Write || self.index_wide() || CPU65816::page_crossed(address, indexed_address) {
+            cycles = CPU65816::internal_cycle(cycles);
+        }
+        (indexed_address, cycles)
+    }
+
+    // (dp)
+    fn addr_direct_indirect<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = self.direct_cycle(cycles);
+        let (pointer, cycles): (Word, i32) = self.read_direct_word(offset as Word, bus, cycles);
+        (self.data_bank_address(pointer), cycles)
+    }
+
+    // [dp]
+    fn addr_direct_indirect_long<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = self.direct_cycle(cycles);
+        self.read_direct_long(offset as Word, bus, cycles)
+    }
+
+    // [dp],Y
+    fn addr_direct_indirect_long_y<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+        ((address + self.r_y as Long) & 0x00FF_FFFF, cycles)
+    }
+
+    // sr: offset from the stack pointer, always in bank 0
+    fn addr_stack_relative<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let cycles: i32 = CPU65816::internal_cycle(cycles);
+        (self.sp.wrapping_add(offset as Word) as Long, cycles)
+    }
+
+    // (sr),Y
+    fn addr_stack_relative_indirect_y<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Long, i32) {
+        let (pointer_address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+        let (pointer, cycles): (Word, i32) = CPU65816::read_bank_zero_word(pointer_address as Word, bus, cycles);
+        let cycles: i32 = CPU65816::internal_cycle(cycles);
+        ((self.data_bank_address(pointer) + self.r_y as Long) & 0x00FF_FFFF, cycles)
+    }
+
+    /* flags and ALU, sized by the M flag (accumulator, memory) or the X flag (index) */
+
+    fn set_zero_negative_flags(&mut self, value: Word, wide: bool) {
+        match wide {
+            true => {
+                self.ps_zero = (value == 0) as Byte;
+                self.ps_negative = (value >> 15) as Byte;
+            }
+            false => {
+                self.ps_zero = ((value & 0x00FF) == 0) as Byte;
+                self.ps_negative = ((value >> 7) & 1) as Byte;
+            }
+        }
+    }
+
+    // an 8-bit result leaves the hidden B accumulator alone
+    fn set_accumulator(&mut self, value: Word) {
+        let wide: bool = self.accumulator_wide();
+        match wide {
+            true => self.r_a = value,
+            false => self.r_a = (self.r_a & 0xFF00) | (value & 0x00FF),
+        }
+        self.set_zero_negative_flags(value, wide);
+    }
+
+    fn set_index(&mut self, value: Word) -> Word {
+        let wide: bool = self.index_wide();
+        let value: Word = match wide {
+            true => value,
+            false => value & 0x00FF,
+        };
+        self.set_zero_negative_flags(value, wide);
+        value
+    }
+
+    fn logical_and(&mut self, value: Word) {
+        self.set_accumulator(self.r_a & value);
+    }
+
+    fn logical_or(&mut self, value: Word) {
+        self.set_accumulator(self.r_a | value);
+    }
+
+    fn exclusive_or(&mut self, value: Word) {
+        self.set_accumulator(self.r_a ^ value);
+    }
+
+    fn load_accumulator(&mut self, value: Word) {
+        self.set_accumulator(value);
+    }
+
+    // ADC: binary or BCD, two or four digits wide
+    fn add_with_carry(&mut self, value: Word) {
+        let (mask, sign, digits): (u32, u32, u32) = match self.accumulator_wide() {
+            true => (0xFFFF, 0x8000, 4),
+            false => (0x00FF, 0x0080, 2),
+        };
+        let accumulator: u32 = self.r_a as u32 & mask;
+        let operand: u32 = value as u32 & mask;
+        let mut carry: u32 = self.ps_carry as u32;
+        let mut result: u32 = 0;
+
+        if self.ps_decimal == 1 {
+            for digit in 0..digits {
+                let shift: u32 = digit * 4;
+                let mut sum: u32 = ((accumulator >> shift) & 0x0F) + ((operand >> shift) & 0x0F) + carry;
+                carry = (sum > 0x09) as u32;
+                if carry == 1 {
+                    sum = (sum + 0x06) & 0x0F;
+                }
+                result |= sum << shift;
+            }
+        } else {
+            let sum: u32 = accumulator + operand + carry;
+            carry = (sum > mask) as u32;
+            result = sum & mask;
+        }
+
+        self.ps_overflow = ((!(accumulator ^ operand) & (accumulator ^ result) & sign) != 0) as Byte;
+        self.ps_carry = carry as Byte;
+        self.set_accumulator(result as Word);
+    }
+
+    // SBC: binary or BCD, two or four digits wide
+    fn subtract_with_carry(&mut self, value: Word) {
+        let (mask, sign, digits): (u32, u32, u32) = match self.accumulator_wide() {
+            true => (0xFFFF, 0x8000, 4),
+            false => (0x00FF, 0x0080, 2),
+        };
+        let accumulator: u32 = self.r_a as u32 & mask;
+        let operand: u32 = value as u32 & mask;
+        let mut result: u32 = 0;
+
+        if self.ps_decimal == 1 {
+            let mut borrow: i32 = 1 - self.ps_carry as i32;
+            for digit in 0..digits {
+                let shift: u32 = digit * 4;
+                let mut difference: i32 = ((accumulator >> shift) & 0x0F) as i32 - ((operand >> shift) & 0x0F) as i32 - borrow;
+                borrow = (difference < 0) as i32;
+                if borrow == 1 {
+                    difference += 10;
+                }
+                result |= ((difference as u32) & 0x0F) << shift;
+            }
+            self.ps_carry = (borrow == 0) as Byte;
+        } else {
+            let difference: u32 = accumulator + (!operand & mask) + self.ps_carry as u32;
+            self.ps_carry = (difference > mask) as Byte;
+            result = difference & mask;
+        }
+
+        self.ps_overflow = (((accumulator ^ operand) & (accumulator ^ result) & sign) != 0) as Byte;
+        self.set_accumulator(result as Word);
+    }
+
+    fn compare(&mut self, register: Word, value: Word, wide: bool) {
+        let mask: Word = match wide {
+            true => 0xFFFF,
+            false => 0x00FF,
+        };
+        self.ps_carry = ((register & mask) >= (value & mask)) as Byte;
+        self.set_zero_negative_flags((register & mask).wrapping_sub(value & mask), wide);
+    }
+
+    fn compare_accumulator(&mut self, value: Word) {
+        self.compare(self.r_a, value, self.accumulator_wide());
+    }
+
+    fn compare_x(&mut self, value: Word) {
+        self.compare(self.r_x, value, self.index_wide());
+    }
+
+    fn compare_y(&mut self, value: Word) {
+        self.compare(self.r_y, value, self.index_wide());
+    }
+
+    // BIT: Z from A & M, N and V copied from the top two bits of the operand
+    fn bit_test(&mut self, value: Word) {
+        let wide: bool = self.accumulator_wide();
+        let top: u32 = if wide { 15 } else { 7 };
+        self.ps_zero = match wide {
+            true => (self.r_a & value) == 0,
+            false => (self.r_a & value & 0x00FF) == 0,
+        } as Byte;
+        self.ps_negative = ((value >> top) & 1) as Byte;
+        self.ps_overflow = ((value >> (top - 1)) & 1) as Byte;
+    }
+
+    fn top_bit_shift(&self) -> u32 {
+        if self.accumulator_wide() { 15 } else { 7 }
+    }
+
+    fn shift_left(&mut self, value: Word) -> Word {
+        self.ps_carry = ((value >> self.top_bit_shift()) & 1) as Byte;
+        let result: Word = value << 1;
+        self.set_zero_negative_flags(result, self.accumulator_wide());
+        result
+    }
+
+    fn shift_right(&mut self, value: Word) -> Word {
+        let value: Word = if self.accumulator_wide() { value } else { value & 0x00FF };
+        self.ps_carry = (value & 1) as Byte;
+        let result: Word = value >> 1;
+        self.set_zero_negative_flags(result, self.accumulator_wide());
+        result
+    }
+
+    fn rotate_left(&mut self, value: Word) -> Word {
+        let result: Word = (value << 1) | self.ps_carry as Word;
+        self.ps_carry = ((value >> self.top_bit_shift()) & 1) as Byte;
+        self.set_zero_negative_flags(result, self.accumulator_wide());
+        result
+    }
+
+    fn rotate_right(&mut self, value: Word) -> Word {
+        let value: Word = if self.accumulator_wide() { value } else { value & 0x00FF };
+        let result: Word = (value >> 1) | ((self.ps_carry as Word) << self.top_bit_shift());
+        self.ps_carry = (value & 1) as Byte;
+        self.set_zero_negative_flags(result, self.accumulator_wide());
+        result
+    }
+
+    fn increment(&mut self, value: Word) -> Word {
+        let result: Word = value.wrapping_add(1);
+        self.set_zero_negative_flags(result, self.accumulator_wide());
+        result
+    }
+
+    fn decrement(&mut self, value: Word) -> Word {
+        let result: Word = value.wrapping_sub(1);
+        self.set_zero_negative_flags(result, self.accumulator_wide());
+        result
+    }
+
+    // TSB / TRB: Z from A & M, then set or clear the bits of A in M
+    fn test_and_set(&mut self, value: Word) -> Word {
+        self.ps_zero = (self.r_a & value & self.accumulator_mask() == 0) as Byte;
+        value | self.r_a
+    }
+
+    fn test_and_reset(&mut self, value: Word) -> Word {
+        self.ps_zero = (self.r_a & value & self.accumulator_mask() == 0) as Byte;
+        value & !self.r_a
+    }
+
+    fn accumulator_mask(&self) -> Word {
+        if self.accumulator_wide() { 0xFFFF } else { 0x00FF }
+    }
+
+    // read, modify during an internal cycle, write back; 16-bit memory costs two more cycles
+    fn read_modify_write<B: LongBus>(&mut self, address: Long, bus: &mut B, cycles: i32, operation: fn(&mut CPU65816, Word) -> Word) -> i32 {
+        let wide: bool = self.accumulator_wide();
+        let (value, cycles): (Word, i32) = CPU65816::read_data(address, wide, bus, cycles);
+        let cycles: i32 = CPU65816::internal_cycle(cycles);
+        let result: Word = operation(self, value);
+        CPU65816::write_data(result, address, wide, bus, cycles)
+    }
+
+    fn modify_accumulator(&mut self, cycles: i32, operation: fn(&mut CPU65816, Word) -> Word) -> i32 {
+        let result: Word = operation(self, self.r_a);
+        match self.accumulator_wide() {
+            true => self.r_a = result,
+            false => self.r_a = (self.r_a & 0xFF00) | (result & 0x00FF),
+        }
+        CPU65816::internal_cycle(cycles)
+    }
+
+    // 8-bit relative branch inside the program bank, a page crossing only costs a cycle
+    // in emulation mode
+    fn branch<B: LongBus>(&mut self, condition: bool, bus: &mut B, cycles: i32) -> i32 {
+        let (offset, mut cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        if condition {
+            let target: Word = self.pc.wrapping_add(offset as i8 as Word);
+            cycles = CPU65816::internal_cycle(cycles);
+            if self.ps_emulation == 1 && (self.pc & 0xFF00) != (target & 0xFF00) {
+                cycles = CPU65816::internal_cycle(cycles);
+            }
+            self.pc = target;
+        }
+        cycles
+    }
+
+    /* stack: always in bank 0, confined to page 0x01 in emulation mode */
+
+    fn push_byte<B: LongBus>(&mut self, value: Byte, bus: &mut B, cycles: i32) -> i32 {
+        let cycles: i32 = CPU65816::write_byte(value, self.sp as Long, bus, cycles);
+        self.sp = match self.ps_emulation {
+            1 => 0x0100 | (self.sp.wrapping_sub(1) & 0x00FF),
+            _ => self.sp.wrapping_sub(1),
+        };
+        cycles
+    }
+
+    fn pull_byte<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Byte, i32) {
+        self.sp = match self.ps_emulation {
+            1 => 0x0100 | (self.sp.wrapping_add(1) & 0x00FF),
+            _ => self.sp.wrapping_add(1),
+        };
+        CPU65816::read_byte(self.sp as Long, bus, cycles)
+    }
+
+    fn push_word<B: LongBus>(&mut self, value: Word, bus: &mut B, cycles: i32) -> i32 {
+        let cycles: i32 = self.push_byte((value >> 8) as Byte, bus, cycles);
+        self.push_byte(value as Byte, bus, cycles)
+    }
+
+    fn pull_word<B: LongBus>(&mut self, bus: &mut B, cycles: i32) -> (Word, i32) {
+        let (lo_byte, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+        let (hi_byte, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+        (((hi_byte as Word) << 8) | lo_byte as Word, cycles)
+    }
+
+    fn push_data<B: LongBus>(&mut self, value: Word, wide: bool, bus: &mut B, cycles: i32) -> i32 {
+        match wide {
+            true => self.push_word(value, bus, cycles),
+            false => self.push_byte(value as Byte, bus, cycles),
+        }
+    }
+
+    fn pull_data<B: LongBus>(&mut self, wide: bool, bus: &mut B, cycles: i32) -> (Word, i32) {
+        match wide {
+            true => self.pull_word(bus, cycles),
+            false => {
+                let (byte, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+                (byte as Word, cycles)
+            }
+        }
+    }
+
+    /* interrupts */
+
+    // native mode also pushes the program bank; the handler always runs in bank 0 in binary
+    fn interrupt<B: LongBus>(&mut self, native_vector: Word, emulation_vector: Word, status: Byte, bus: &mut B, cycles: i32) -> i32 {
+        let mut cycles: i32 = cycles;
+        if self.ps_emulation == 0 {
+            cycles = self.push_byte(self.pbr, bus, cycles);
+        }
+        let cycles: i32 = self.push_word(self.pc, bus, cycles);
+        let cycles: i32 = self.push_byte(status, bus, cycles);
+        self.ps_interrupt = 1;
+        self.ps_decimal = 0;
+        self.pbr = 0;
+        let vector: Word = if self.ps_emulation == 1 { emulation_vector } else { native_vector };
+        let (handler_address, cycles): (Word, i32) = CPU65816::read_bank_zero_word(vector, bus, cycles);
+        self.pc = handler_address;
+        cycles
+    }
+
+    // hardware interrupts push B clear in emulation mode so the handler can tell them from BRK
+    fn hardware_interrupt<B: LongBus>(&mut self, native_vector: Word, emulation_vector: Word, bus: &mut B, cycles: i32) -> i32 {
+        let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+        let mut status: Byte = self.processor_status();
+        if self.ps_emulation == 1 {
+            status &= !0x10;
+        }
+        self.interrupt(native_vector, emulation_vector, status, bus, cycles)
+    }
+
+    /* register width changes */
+
+    // XCE: swap carry and E; entering emulation forces 8-bit registers and pins the stack
+    fn exchange_carry_emulation(&mut self) {
+        std::mem::swap(&mut self.ps_carry, &mut self.ps_emulation);
+        if self.ps_emulation == 1 {
+            self.ps_memory = 1;
+            self.ps_index = 1;
+            self.r_x &= 0x00FF;
+            self.r_y &= 0x00FF;
+            self.sp = 0x0100 | (self.sp & 0x00FF);
+        }
+    }
+
+    // MVN / MVP: one byte per pass, the opcode is refetched until C wraps to 0xFFFF
+    fn block_move<B: LongBus>(&mut self, step: Word, bus: &mut B, cycles: i32) -> i32 {
+        let (destination_bank, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        let (source_bank, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+        self.dbr = destination_bank;
+        let source: Long = ((source_bank as Long) << 16) | self.r_x as Long;
+        let destination: Long = ((destination_bank as Long) << 16) | self.r_y as Long;
+        let (value, cycles): (Byte, i32) = CPU65816::read_byte(source, bus, cycles);
+        let cycles: i32 = CPU65816::write_byte(value, destination, bus, cycles);
+        let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+        let index_mask: Word = if self.index_wide() { 0xFFFF } else { 0x00FF };
+        self.r_x = self.r_x.wrapping_add(step) & index_mask;
+        self.r_y = self.r_y.wrapping_add(step) & index_mask;
+        self.r_a = self.r_a.wrapping_sub(1);
+        if self.r_a != 0xFFFF {
+            self.pc = self.pc.wrapping_sub(3);
+        }
+        cycles
+    }
+
+    // runs whole instructions until the cycle budget is used up and returns the cycles spent
+    pub fn execute<B: LongBus>(&mut self, mut cycles: i32, bus: &mut B) -> Result<i32, EmuError> {
+        let requested_cycles: i32 = cycles;
+        while cycles > 0 {
+            cycles = self.execute_instruction(cycles, bus)?;
+        }
+        Ok(requested_cycles - cycles)
+    }
+
+    // runs exactly one instruction (or interrupt entry) and returns the cycles it took
+    pub fn step<B: LongBus>(&mut self, bus: &mut B) -> Result<i32, EmuError> {
+        let cycles: i32 = self.execute_instruction(0, bus)?;
+        Ok(-cycles)
+    }
+
+    fn execute_instruction<B: LongBus>(&mut self, cycles: i32, bus: &mut B) -> Result<i32, EmuError> {
+
+        if self.stopped {
+            return Err(EmuError::Stopped { address: self.pc });
+        }
+        if self.waiting {
+            if !self.nmi_pending && !self.irq_line {
+                return Ok(CPU65816::internal_cycle(cycles));
+            }
+            self.waiting = false;
+        }
+
+        // interrupts are polled between instructions, NMI wins over IRQ
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            return Ok(self.hardware_interrupt(NATIVE_NMI_VECTOR, super::NMI_VECTOR, bus, cycles));
+        }
+        if self.irq_line && self.ps_interrupt == 0 {
+            return Ok(self.hardware_interrupt(NATIVE_IRQ_VECTOR, super::IRQ_VECTOR, bus, cycles));
+        }
+
+        let (instruction, cycles): (Opcode, i32) = self.fetch_byte(bus, cycles);
+
+        match instruction {
+
+            // LDA
+            CPU::LDA_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::LDA_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::LDA_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::LDA_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::LDA_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::LDA_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::LDA_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::LDA_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.load_accumulator(value);
+                Ok(cycles)
+            }
+            // LDX
+            CPU::LDX_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.index_wide(), bus, cycles);
+                self.r_x = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_x = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ZERO_PAGE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_y);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_x = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_x = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDX_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_x = self.set_index(value);
+                Ok(cycles)
+            }
+            // LDY
+            CPU::LDY_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.index_wide(), bus, cycles);
+                self.r_y = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_y = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_y = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_y = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::LDY_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.r_y = self.set_index(value);
+                Ok(cycles)
+            }
+            // STA
+            CPU::STA_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Write);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Write);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STA_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU65816::STA_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU65816::STA_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU65816::STA_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU65816::STA_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU65816::STA_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU65816::STA_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                Ok(CPU65816::write_data(self.r_a, address, self.accumulator_wide(), bus, cycles))
+            }
+            // STX
+            CPU::STX_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(CPU65816::write_data(self.r_x, address, self.index_wide(), bus, cycles))
+            }
+            CPU::STX_ZERO_PAGE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_y);
+                Ok(CPU65816::write_data(self.r_x, address, self.index_wide(), bus, cycles))
+            }
+            CPU::STX_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(CPU65816::write_data(self.r_x, address, self.index_wide(), bus, cycles))
+            }
+            // STY
+            CPU::STY_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(CPU65816::write_data(self.r_y, address, self.index_wide(), bus, cycles))
+            }
+            CPU::STY_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(CPU65816::write_data(self.r_y, address, self.index_wide(), bus, cycles))
+            }
+            CPU::STY_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(CPU65816::write_data(self.r_y, address, self.index_wide(), bus, cycles))
+            }
+            // STZ
+            CPU::STZ_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(CPU65816::write_data(0x0000, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STZ_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(CPU65816::write_data(0x0000, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STZ_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(CPU65816::write_data(0x0000, address, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::STZ_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(CPU65816::write_data(0x0000, address, self.accumulator_wide(), bus, cycles))
+            }
+            // ADC
+            CPU::ADC_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::ADC_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::ADC_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::ADC_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::ADC_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::ADC_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::ADC_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::ADC_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.add_with_carry(value);
+                Ok(cycles)
+            }
+            // SBC
+            CPU::SBC_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU::SBC_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::SBC_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::SBC_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::SBC_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::SBC_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::SBC_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            CPU65816::SBC_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.subtract_with_carry(value);
+                Ok(cycles)
+            }
+            // AND
+            CPU::AND_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU::AND_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU65816::AND_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU65816::AND_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU65816::AND_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU65816::AND_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU65816::AND_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            CPU65816::AND_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_and(value);
+                Ok(cycles)
+            }
+            // ORA
+            CPU::ORA_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU::ORA_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU65816::ORA_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU65816::ORA_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU65816::ORA_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU65816::ORA_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU65816::ORA_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            CPU65816::ORA_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.logical_or(value);
+                Ok(cycles)
+            }
+            // EOR
+            CPU::EOR_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU::EOR_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU65816::EOR_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU65816::EOR_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU65816::EOR_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU65816::EOR_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU65816::EOR_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            CPU65816::EOR_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.exclusive_or(value);
+                Ok(cycles)
+            }
+            // CMP
+            CPU::CMP_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ABSOLUTE_Y => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_y, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_INDIRECT_X => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_x(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_indirect_y(bus, cycles, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::CMP_ZERO_PAGE_INDIRECT => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::CMP_ZERO_PAGE_INDIRECT_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::CMP_ZERO_PAGE_INDIRECT_LONG_Y => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indirect_long_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::CMP_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::CMP_ABSOLUTE_LONG_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_long_indexed(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::CMP_STACK_RELATIVE => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            CPU65816::CMP_STACK_RELATIVE_INDIRECT_Y => {
+                let (address, cycles): (Long, i32) = self.addr_stack_relative_indirect_y(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.compare_accumulator(value);
+                Ok(cycles)
+            }
+            // BIT
+            CPU::BIT_IMMEDIATE => {
+                // only Z changes, there is no memory operand to copy N and V from
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.accumulator_wide(), bus, cycles);
+                self.ps_zero = (self.r_a & value & self.accumulator_mask() == 0) as Byte;
+                Ok(cycles)
+            }
+            CPU::BIT_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+            CPU::BIT_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+            CPU::BIT_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+            CPU::BIT_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Read);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.accumulator_wide(), bus, cycles);
+                self.bit_test(value);
+                Ok(cycles)
+            }
+            // CPX / CPY
+            CPU::CPX_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.index_wide(), bus, cycles);
+                self.compare_x(value);
+                Ok(cycles)
+            }
+            CPU::CPX_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.compare_x(value);
+                Ok(cycles)
+            }
+            CPU::CPX_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.compare_x(value);
+                Ok(cycles)
+            }
+            CPU::CPY_IMMEDIATE => {
+                let (value, cycles): (Word, i32) = self.fetch_immediate(self.index_wide(), bus, cycles);
+                self.compare_y(value);
+                Ok(cycles)
+            }
+            CPU::CPY_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.compare_y(value);
+                Ok(cycles)
+            }
+            CPU::CPY_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                let (value, cycles): (Word, i32) = CPU65816::read_data(address, self.index_wide(), bus, cycles);
+                self.compare_y(value);
+                Ok(cycles)
+            }
+            // ASL
+            CPU::ASL_ACCUMULATOR => {
+                Ok(self.modify_accumulator(cycles, CPU65816::shift_left))
+            }
+            CPU::ASL_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_left))
+            }
+            CPU::ASL_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_left))
+            }
+            CPU::ASL_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_left))
+            }
+            CPU::ASL_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_left))
+            }
+            // LSR
+            CPU::LSR_ACCUMULATOR => {
+                Ok(self.modify_accumulator(cycles, CPU65816::shift_right))
+            }
+            CPU::LSR_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_right))
+            }
+            CPU::LSR_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_right))
+            }
+            CPU::LSR_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_right))
+            }
+            CPU::LSR_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::shift_right))
+            }
+            // ROL
+            CPU::ROL_ACCUMULATOR => {
+                Ok(self.modify_accumulator(cycles, CPU65816::rotate_left))
+            }
+            CPU::ROL_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_left))
+            }
+            CPU::ROL_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_left))
+            }
+            CPU::ROL_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_left))
+            }
+            CPU::ROL_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_left))
+            }
+            // ROR
+            CPU::ROR_ACCUMULATOR => {
+                Ok(self.modify_accumulator(cycles, CPU65816::rotate_right))
+            }
+            CPU::ROR_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_right))
+            }
+            CPU::ROR_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_right))
+            }
+            CPU::ROR_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_right))
+            }
+            CPU::ROR_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::rotate_right))
+            }
+            // INC
+            CPU::INC_ACCUMULATOR => {
+                Ok(self.modify_accumulator(cycles, CPU65816::increment))
+            }
+            CPU::INC_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::increment))
+            }
+            CPU::INC_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::increment))
+            }
+            CPU::INC_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::increment))
+            }
+            CPU::INC_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::increment))
+            }
+            // DEC
+            CPU::DEC_ACCUMULATOR => {
+                Ok(self.modify_accumulator(cycles, CPU65816::decrement))
+            }
+            CPU::DEC_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::decrement))
+            }
+            CPU::DEC_ZERO_PAGE_X => {
+                let (address, cycles): (Long, i32) = self.addr_direct_indexed(bus, cycles, self.r_x);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::decrement))
+            }
+            CPU::DEC_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::decrement))
+            }
+            CPU::DEC_ABSOLUTE_X => {
+                let (address, cycles): (Long, i32) = self.addr_absolute_indexed(bus, cycles, self.r_x, Access::Write);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::decrement))
+            }
+            // TSB / TRB
+            CPU::TSB_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::test_and_set))
+            }
+            CPU::TSB_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::test_and_set))
+            }
+            CPU::TRB_ZERO_PAGE => {
+                let (address, cycles): (Long, i32) = self.addr_direct(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::test_and_reset))
+            }
+            CPU::TRB_ABSOLUTE => {
+                let (address, cycles): (Long, i32) = self.addr_absolute(bus, cycles);
+                Ok(self.read_modify_write(address, bus, cycles, CPU65816::test_and_reset))
+            }
+
+            // increments / decrements of the index registers
+            CPU::INX_IMPLIED => {
+                self.r_x = self.set_index(self.r_x.wrapping_add(1));
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::INY_IMPLIED => {
+                self.r_y = self.set_index(self.r_y.wrapping_add(1));
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::DEX_IMPLIED => {
+                self.r_x = self.set_index(self.r_x.wrapping_sub(1));
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::DEY_IMPLIED => {
+                self.r_y = self.set_index(self.r_y.wrapping_sub(1));
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+
+            // register transfers: the destination decides the width
+            CPU::TAX_IMPLIED => {
+                self.r_x = self.set_index(self.r_a);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::TAY_IMPLIED => {
+                self.r_y = self.set_index(self.r_a);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::TXA_IMPLIED => {
+                self.set_accumulator(self.r_x);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::TYA_IMPLIED => {
+                self.set_accumulator(self.r_y);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::TSX_IMPLIED => {
+                self.r_x = self.set_index(self.sp);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::TXS_IMPLIED => {
+                self.sp = match self.ps_emulation {
+                    1 => 0x0100 | (self.r_x & 0x00FF),
+                    _ => self.r_x,
+                };
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::TXY_IMPLIED => {
+                self.r_y = self.set_index(self.r_x);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::TYX_IMPLIED => {
+                self.r_x = self.set_index(self.r_y);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            // C <-> D and C <-> S always move all 16 bits
+            CPU65816::TCD_IMPLIED => {
+                self.d = self.r_a;
+                self.set_zero_negative_flags(self.d, true);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::TDC_IMPLIED => {
+                self.r_a = self.d;
+                self.set_zero_negative_flags(self.r_a, true);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::TCS_IMPLIED => {
+                self.sp = match self.ps_emulation {
+                    1 => 0x0100 | (self.r_a & 0x00FF),
+                    _ => self.r_a,
+                };
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::TSC_IMPLIED => {
+                self.r_a = self.sp;
+                self.set_zero_negative_flags(self.r_a, true);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::XBA_IMPLIED => {
+                // flags come from the new A, always 8 bits
+                self.r_a = self.r_a.rotate_left(8);
+                self.set_zero_negative_flags(self.r_a, false);
+                Ok(CPU65816::internal_cycle(CPU65816::internal_cycle(cycles)))
+            }
+            CPU65816::XCE_IMPLIED => {
+                self.exchange_carry_emulation();
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+
+            // flag instructions
+            CPU::CLC_IMPLIED => {
+                self.ps_carry = 0;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::SEC_IMPLIED => {
+                self.ps_carry = 1;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::CLI_IMPLIED => {
+                self.ps_interrupt = 0;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::SEI_IMPLIED => {
+                self.ps_interrupt = 1;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::CLV_IMPLIED => {
+                self.ps_overflow = 0;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::CLD_IMPLIED => {
+                self.ps_decimal = 0;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU::SED_IMPLIED => {
+                self.ps_decimal = 1;
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::REP_IMMEDIATE => {
+                let (mask, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.set_processor_status(self.processor_status() & !mask);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::SEP_IMMEDIATE => {
+                let (mask, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                self.set_processor_status(self.processor_status() | mask);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+
+            // NOP / WDM
+            CPU::NOP_IMPLIED => {
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::WDM_IMMEDIATE => {
+                // reserved for future expansion, skips its signature byte
+                let (_signature, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                Ok(cycles)
+            }
+
+            // branches
+            CPU::BCC_RELATIVE => {
+                Ok(self.branch(self.ps_carry == 0, bus, cycles))
+            }
+            CPU::BCS_RELATIVE => {
+                Ok(self.branch(self.ps_carry == 1, bus, cycles))
+            }
+            CPU::BEQ_RELATIVE => {
+                Ok(self.branch(self.ps_zero == 1, bus, cycles))
+            }
+            CPU::BMI_RELATIVE => {
+                Ok(self.branch(self.ps_negative == 1, bus, cycles))
+            }
+            CPU::BNE_RELATIVE => {
+                Ok(self.branch(self.ps_zero == 0, bus, cycles))
+            }
+            CPU::BPL_RELATIVE => {
+                Ok(self.branch(self.ps_negative == 0, bus, cycles))
+            }
+            CPU::BVC_RELATIVE => {
+                Ok(self.branch(self.ps_overflow == 0, bus, cycles))
+            }
+            CPU::BVS_RELATIVE => {
+                Ok(self.branch(self.ps_overflow == 1, bus, cycles))
+            }
+            CPU::BRA_RELATIVE => {
+                Ok(self.branch(true, bus, cycles))
+            }
+            CPU65816::BRL_RELATIVE_LONG => {
+                let (offset, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                self.pc = self.pc.wrapping_add(offset);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+
+            // JMP / JML
+            CPU::JMP_ABSOLUTE => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU::JMP_INDIRECT => {
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (address, cycles): (Word, i32) = CPU65816::read_bank_zero_word(pointer, bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU::JMP_ABSOLUTE_X_INDIRECT => {
+                // the pointer table lives in the program bank
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                let pointer_address: Long = ((self.pbr as Long) << 16) | pointer.wrapping_add(self.r_x) as Long;
+                let (address, cycles): (Word, i32) = CPU65816::read_data(pointer_address, true, bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU65816::JML_ABSOLUTE_LONG => {
+                let (address, cycles): (Long, i32) = self.fetch_long(bus, cycles);
+                self.pbr = (address >> 16) as Byte;
+                self.pc = address as Word;
+                Ok(cycles)
+            }
+            CPU65816::JML_ABSOLUTE_INDIRECT_LONG => {
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let (address, cycles): (Word, i32) = CPU65816::read_bank_zero_word(pointer, bus, cycles);
+                let (bank, cycles): (Byte, i32) = CPU65816::read_byte(pointer.wrapping_add(2) as Long, bus, cycles);
+                self.pbr = bank;
+                self.pc = address;
+                Ok(cycles)
+            }
+
+            // JSR / JSL / RTS / RTL
+            CPU::JSR_ABSOLUTE => {
+                let (subroutine_addr, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                // the return address pushed is the last byte of the JSR itself
+                let cycles: i32 = self.push_word(self.pc.wrapping_sub(1), bus, cycles);
+                self.pc = subroutine_addr;
+                Ok(cycles)
+            }
+            CPU65816::JSR_ABSOLUTE_X_INDIRECT => {
+                let (pointer, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = self.push_word(self.pc.wrapping_sub(1), bus, cycles);
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                let pointer_address: Long = ((self.pbr as Long) << 16) | pointer.wrapping_add(self.r_x) as Long;
+                let (address, cycles): (Word, i32) = CPU65816::read_data(pointer_address, true, bus, cycles);
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU65816::JSL_ABSOLUTE_LONG => {
+                let (address, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = self.push_byte(self.pbr, bus, cycles);
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                let (bank, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                let cycles: i32 = self.push_word(self.pc.wrapping_sub(1), bus, cycles);
+                self.pbr = bank;
+                self.pc = address;
+                Ok(cycles)
+            }
+            CPU::RTS_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (return_addr, cycles): (Word, i32) = self.pull_word(bus, cycles);
+                self.pc = return_addr.wrapping_add(1);
+                Ok(CPU65816::internal_cycle(cycles))
+            }
+            CPU65816::RTL_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (return_addr, cycles): (Word, i32) = self.pull_word(bus, cycles);
+                let (bank, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+                self.pc = return_addr.wrapping_add(1);
+                self.pbr = bank;
+                Ok(cycles)
+            }
+
+            // BRK / COP / RTI
+            CPU::BRK_IMPLIED => {
+                let (_signature, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                Ok(self.interrupt(NATIVE_BRK_VECTOR, super::IRQ_VECTOR, self.processor_status(), bus, cycles))
+            }
+            CPU65816::COP_IMMEDIATE => {
+                let (_signature, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                Ok(self.interrupt(NATIVE_COP_VECTOR, EMULATION_COP_VECTOR, self.processor_status(), bus, cycles))
+            }
+            CPU::RTI_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (status, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+                self.set_processor_status(status);
+                let (return_addr, mut cycles): (Word, i32) = self.pull_word(bus, cycles);
+                self.pc = return_addr;
+                if self.ps_emulation == 0 {
+                    let (bank, bank_cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+                    self.pbr = bank;
+                    cycles = bank_cycles;
+                }
+                Ok(cycles)
+            }
+
+            // stack operations
+            CPU::PHA_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_data(self.r_a, self.accumulator_wide(), bus, cycles))
+            }
+            CPU::PHX_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_data(self.r_x, self.index_wide(), bus, cycles))
+            }
+            CPU::PHY_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_data(self.r_y, self.index_wide(), bus, cycles))
+            }
+            CPU::PLA_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (value, cycles): (Word, i32) = self.pull_data(self.accumulator_wide(), bus, cycles);
+                self.set_accumulator(value);
+                Ok(cycles)
+            }
+            CPU::PLX_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (value, cycles): (Word, i32) = self.pull_data(self.index_wide(), bus, cycles);
+                self.r_x = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::PLY_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (value, cycles): (Word, i32) = self.pull_data(self.index_wide(), bus, cycles);
+                self.r_y = self.set_index(value);
+                Ok(cycles)
+            }
+            CPU::PHP_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_byte(self.processor_status(), bus, cycles))
+            }
+            CPU::PLP_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (status, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+                self.set_processor_status(status);
+                Ok(cycles)
+            }
+            CPU65816::PHB_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_byte(self.dbr, bus, cycles))
+            }
+            CPU65816::PLB_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (bank, cycles): (Byte, i32) = self.pull_byte(bus, cycles);
+                self.dbr = bank;
+                self.set_zero_negative_flags(bank as Word, false);
+                Ok(cycles)
+            }
+            CPU65816::PHK_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_byte(self.pbr, bus, cycles))
+            }
+            CPU65816::PHD_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_word(self.d, bus, cycles))
+            }
+            CPU65816::PLD_IMPLIED => {
+                let cycles: i32 = CPU65816::internal_cycle(CPU65816::internal_cycle(cycles));
+                let (direct_page, cycles): (Word, i32) = self.pull_word(bus, cycles);
+                self.d = direct_page;
+                self.set_zero_negative_flags(direct_page, true);
+                Ok(cycles)
+            }
+            CPU65816::PEA_ABSOLUTE => {
+                let (value, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                Ok(self.push_word(value, bus, cycles))
+            }
+            CPU65816::PEI_ZERO_PAGE_INDIRECT => {
+                let (offset, cycles): (Byte, i32) = self.fetch_byte(bus, cycles);
+                let cycles: i32 = self.direct_cycle(cycles);
+                let (value, cycles): (Word, i32) = self.read_direct_word(offset as Word, bus, cycles);
+                Ok(self.push_word(value, bus, cycles))
+            }
+            CPU65816::PER_RELATIVE_LONG => {
+                let (offset, cycles): (Word, i32) = self.fetch_word(bus, cycles);
+                let cycles: i32 = CPU65816::internal_cycle(cycles);
+                Ok(self.push_word(self.pc.wrapping_add(offset), bus, cycles))
+            }
+
+            // block moves
+            CPU65816::MVN_BLOCK_MOVE => {
+                Ok(self.block_move(0x0001, bus, cycles))
+            }
+            CPU65816::MVP_BLOCK_MOVE => {
+                Ok(self.block_move(0xFFFF, bus, cycles))
+            }
+
+            // WAI / STP
+            CPU::WAI_IMPLIED => {
+                self.waiting = true;
+                Ok(CPU65816::internal_cycle(CPU65816::internal_cycle(cycles)))
+            }
+            CPU::STP_IMPLIED => {
+                self.pc = self.pc.wrapping_sub(1);
+                self.stopped = true;
+                Ok(CPU65816::internal_cycle(CPU65816::internal_cycle(cycles)))
+            }
+        }
+
+    }
+}
+
+pub fn build_cpu_65816() -> CPU65816 {
+    CPU65816 {
+        pc: 0xFFFC,
+        sp: 0x01FF,
+        pbr: 0,
+        dbr: 0,
+        d: 0,
+        r_a: 0,
+        r_x: 0,
+        r_y: 0,
+        ps_carry: 0,
+        ps_zero: 0,
+        ps_interrupt: 0,
+        ps_decimal: 0,
+        ps_index: 1,
+        ps_memory: 1,
+        ps_overflow: 0,
+        ps_negative: 0,
+        ps_emulation: 1,
+        irq_line: false,
+        nmi_line: false,
+        nmi_pending: false,
+        waiting: false,
+        stopped: false,
+    }
+}
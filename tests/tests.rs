@@ -1,223 +1,8760 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    use rust6502::mos;
-    use rust6502::mos::Opcodes;
-    use std::process;
-
-    #[test]
-    fn setup_debug_autotest() {
-        
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-
-        println!("before memory set ...");
-        println!("[0x0001] : {:#04x}", mem.memory[0x0001]);
-        println!("[0x0002] : {:#04x}", mem.memory[0x0002]);
-        println!("[0x0003] : {:#04x}", mem.memory[0x0003]);
-    
-        mem.memory[0x0002] = 0x0F;
-    
-        println!("after memory set ...");
-        println!("[0x0001] : {:#04x}", mem.memory[0x0001]);
-        println!("[0x0002] : {:#04x}", mem.memory[0x0002]);
-        println!("[0x0003] : {:#04x}", mem.memory[0x0003]);
-    
-        // basic cpu tests
-    
-        println!("CPU DEBUGS");
-        println!("value test (LDA_IMMEDIATE 0xA9): {:#02x}", mos::CPU::LDA_IMMEDIATE);
-        println!("program counter: {:#04x}", cpu.pc);
-        println!("stack pointer: {:#04x}", cpu.sp);
-        println!("reg A: {}", cpu.r_a);
-        
-        assert_eq!(1, 1);
-    }
-
-    #[test]
-    fn mem_test_basic() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        mem.memory[0x0002] = 0x0F;
-        assert_eq!(mem.memory[0x0002], 0x0F);
-    }
-
-    #[test]
-    fn cpu_init_pc() {
-        let mut cpu = mos::build_cpu();
-        assert_eq!(cpu.pc, 0xFFFC);
-    }
-
-    #[test]
-    fn cpu_init_sp() {
-        let mut cpu = mos::build_cpu();
-        assert_eq!(cpu.sp, 0x00FF);
-    }
-
-    #[test]
-    fn LDA_immediate() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        mem.memory[0xFFFC] = mos::CPU::LDA_IMMEDIATE;
-        mem.memory[0xFFFD] = 0x84;
-        let cycles_init = 2;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_zero_page() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE;
-        mem.memory[0xFFFD] = 0x42;
-        mem.memory[0x0042] = 0x84;
-        let cycles_init = 3;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_zero_page_X() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        cpu.r_x = 4;
-        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE_X;
-        mem.memory[0xFFFD] = 0x42; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0x0046] = 0x84;
-        let cycles_init = 4;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_zero_page_X_wraparound() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        cpu.r_x = 0x00FF;
-        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE_X;
-        mem.memory[0xFFFD] = 0x0080; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0x007F] = 0x0084;
-        let cycles_init = 4;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_absolute() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE;
-        mem.memory[0xFFFD] = 0x80; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0xFFFE] = 0x44;
-        mem.memory[0x4480] = 0x84;
-        let cycles_init = 4;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_absolute_X() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        cpu.r_x = 1;
-        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_X;
-        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0xFFFE] = 0x44;
-        mem.memory[0x4403] = 0x84;
-        let cycles_init = 4;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_absolute_X_cross_page() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        cpu.r_x = 0xFF;
-        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_X;
-        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0xFFFE] = 0x44;
-        mem.memory[0x4501] = 0x84;
-        let cycles_init = 5;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_absolute_Y() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        cpu.r_y = 1;
-        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_Y;
-        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0xFFFE] = 0x44;
-        mem.memory[0x4403] = 0x84;
-        let cycles_init = 4;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    #[test]
-    fn LDA_absolute_Y_cross_page() {
-        let mut cpu = mos::build_cpu();
-        let mut mem = mos::build_memory();
-        cpu.r_y = 0xFF;
-        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_Y;
-        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
-        mem.memory[0xFFFE] = 0x44;
-        mem.memory[0x4501] = 0x84;
-        let cycles_init = 5;
-        let cycles = cpu.execute(cycles_init, &mem).unwrap_or_else( |err| {
-            println!("invalid memory and instruction situation {} ...", err);
-            process::exit(1);
-        });
-        let good_cycles = cycles_init == cycles;
-        let good_result = cpu.r_a == (0x84);
-        assert!(good_result && good_cycles);
-    }
-
-    
-
-}
\ No newline at end of file
+#![allow(non_snake_case)]
+
+#[cfg(test)]
+mod tests {
+
+    use rust6502::mos;
+    use rust6502::mos::Opcodes;
+    use rust6502::mos::Opcodes65816;
+    use std::process;
+
+    #[test]
+    fn setup_debug_autotest() {
+        
+        let cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+
+        println!("before memory set ...");
+        println!("[0x0001] : {:#04x}", mem.memory[0x0001]);
+        println!("[0x0002] : {:#04x}", mem.memory[0x0002]);
+        println!("[0x0003] : {:#04x}", mem.memory[0x0003]);
+    
+        mem.memory[0x0002] = 0x0F;
+    
+        println!("after memory set ...");
+        println!("[0x0001] : {:#04x}", mem.memory[0x0001]);
+        println!("[0x0002] : {:#04x}", mem.memory[0x0002]);
+        println!("[0x0003] : {:#04x}", mem.memory[0x0003]);
+    
+        // basic cpu tests
+    
+        println!("CPU DEBUGS");
+        println!("value test (LDA_IMMEDIATE 0xA9): {:#02x}", mos::CPU::LDA_IMMEDIATE);
+        println!("program counter: {:#04x}", cpu.pc);
+        println!("stack pointer: {:#04x}", cpu.sp);
+        println!("reg A: {}", cpu.r_a);
+        
+        assert_eq!(1, 1);
+    }
+
+    #[test]
+    fn mem_test_basic() {
+        let mut mem = mos::build_memory();
+        mem.memory[0x0002] = 0x0F;
+        assert_eq!(mem.memory[0x0002], 0x0F);
+    }
+
+    #[test]
+    fn cpu_init_pc() {
+        let cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        assert_eq!(cpu.pc, 0xFFFC);
+    }
+
+    #[test]
+    fn cpu_init_sp() {
+        let cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        assert_eq!(cpu.sp, 0x00FF);
+    }
+
+    #[test]
+    fn LDA_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x84;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        mem.memory[0x0042] = 0x84;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 4;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0x0046] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_zero_page_X_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x00FF;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x0080; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0x007F] = 0x0084;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4480] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4403] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4501] = 0x84;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4403] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02; // 0x0042 + 0x0004 = 0x0046
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4501] = 0x84;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x04;
+        mem.memory[0xFFFC] = mos::CPU::LDA_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x84;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_indirect_X_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDA_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0x00FF] = 0x00;
+        mem.memory[0x0000] = 0x80;
+        mem.memory[0x8000] = 0x84;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x04;
+        mem.memory[0xFFFC] = mos::CPU::LDA_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x84;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_indirect_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDA_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0x0002] = 0x02;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8101] = 0x84;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDX_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x84;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDX_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        mem.memory[0x0042] = 0x84;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_zero_page_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 4;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ZERO_PAGE_Y;
+        mem.memory[0xFFFD] = 0x42;
+        mem.memory[0x0046] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_zero_page_Y_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ZERO_PAGE_Y;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0x007F] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDX_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4480] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4403] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_absolute_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4501] = 0x84;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDY_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x84;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDY_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        mem.memory[0x0042] = 0x84;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 4;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        mem.memory[0x0046] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDY_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4480] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4403] = 0x84;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        mem.memory[0x4501] = 0x84;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x84);
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn STA_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::STA_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STA_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.r_x = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::STA_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x80;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x007F] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STA_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::STA_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STA_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.r_x = 1;
+        mem.memory[0xFFFC] = mos::CPU::STA_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STA_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.r_y = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::STA_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4501] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STA_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::STA_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x8000] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STA_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::STA_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x8004] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STX_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::STX_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STX_zero_page_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        cpu.r_y = 4;
+        mem.memory[0xFFFC] = mos::CPU::STX_ZERO_PAGE_Y;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STX_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::STX_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STY_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::STY_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STY_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        cpu.r_x = 4;
+        mem.memory[0xFFFC] = mos::CPU::STY_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn STY_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::STY_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x84;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn LDA_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x00;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_zero_page_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_zero_page_X_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_x = 4;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_X_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_x = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_absolute_Y_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_y = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_indirect_X_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_x = 0x04;
+        mem.memory[0xFFFC] = mos::CPU::LDA_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDA_indirect_Y_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_y = 0x04;
+        mem.memory[0xFFFC] = mos::CPU::LDA_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDX_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x00;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_zero_page_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_zero_page_Y_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_y = 4;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ZERO_PAGE_Y;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_absolute_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDX_absolute_Y_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_y = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDX_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDY_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x00;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_zero_page_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_zero_page_X_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_x = 4;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_absolute_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn LDY_absolute_X_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        cpu.ps_negative = 1;
+        cpu.r_x = 1;
+        mem.memory[0xFFFC] = mos::CPU::LDY_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == (0x00);
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_cycles && good_flags);
+    }
+
+    #[test]
+    fn STA_does_not_affect_flags() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x00;
+        cpu.ps_negative = 1;
+        mem.memory[0xFFFC] = mos::CPU::STA_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_cycles && good_flags);
+    }
+
+    #[test]
+    fn ADC_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        mem.memory[0x0042] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        mem.memory[0x4480] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_x = 0xFF;
+        mem.memory[0x4501] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_y = 1;
+        mem.memory[0x4403] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_absolute_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_y = 0xFF;
+        mem.memory[0x4501] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_indirect_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_y = 0xFF;
+        mem.memory[0x0002] = 0x02;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8101] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::ADC_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_immediate_carry_in() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x31;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_immediate_carry_out() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x01;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_immediate_overflow_positive() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x7F;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x80;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_immediate_overflow_negative() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x7F;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_decimal() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x12;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x34;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x46;
+        let good_flags = cpu.ps_carry == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_decimal_carry() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x58;
+        cpu.ps_decimal = 1;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x46;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x05;
+        let good_flags = cpu.ps_carry == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_decimal_nmos_flags() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x99;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_decimal_nmos_overflow() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x79;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x89;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_overflow == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        mem.memory[0x0042] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        mem.memory[0x4480] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_x = 0xFF;
+        mem.memory[0x4501] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_y = 1;
+        mem.memory[0x4403] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_absolute_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_y = 0xFF;
+        mem.memory[0x4501] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_indirect_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.r_y = 0xFF;
+        mem.memory[0x0002] = 0x02;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8101] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::SBC_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_borrow_in() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x2F;
+        let good_flags = cpu.ps_carry == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_borrow_out() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x20;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x30;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x20;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_overflow() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x7F;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_decimal() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x46;
+        cpu.ps_decimal = 1;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x12;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x34;
+        let good_flags = cpu.ps_carry == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_decimal_borrow_in() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x13;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x26;
+        let good_flags = cpu.ps_carry == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_decimal_borrow_out() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x00;
+        cpu.ps_decimal = 1;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x99;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn JSR_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::JSR_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x80;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8000;
+        let good_sp = cpu.sp == 0xFD;
+        let good_stack = mem.memory[0x01FF] == 0xFF && mem.memory[0x01FE] == 0xFE;
+        assert!(good_pc && good_sp && good_stack && good_cycles);
+    }
+
+    #[test]
+    fn RTS_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFD;
+        mem.memory[0x01FF] = 0x12;
+        mem.memory[0x01FE] = 0x33;
+        mem.memory[0xFFFC] = mos::CPU::RTS_IMPLIED;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x1234;
+        let good_sp = cpu.sp == 0xFF;
+        assert!(good_pc && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PHA_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::PHA_IMPLIED;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_stack = mem.memory[0x01FF] == 0x84;
+        let good_sp = cpu.sp == 0xFE;
+        assert!(good_stack && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PHA_stack_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.sp = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::PHA_IMPLIED;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_stack = mem.memory[0x0100] == 0x84;
+        let good_sp = cpu.sp == 0xFF;
+        assert!(good_stack && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PLA_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFE;
+        mem.memory[0x01FF] = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::PLA_IMPLIED;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x84;
+        let good_sp = cpu.sp == 0xFF;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_sp && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn PLA_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        cpu.sp = 0xFE;
+        mem.memory[0x01FF] = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::PLA_IMPLIED;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn PLA_stack_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFF;
+        mem.memory[0x0100] = 0x42;
+        mem.memory[0xFFFC] = mos::CPU::PLA_IMPLIED;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x42;
+        let good_sp = cpu.sp == 0x00;
+        assert!(good_result && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PHP_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_negative = 1;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::PHP_IMPLIED;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_stack = mem.memory[0x01FF] == 0xB1;
+        let good_sp = cpu.sp == 0xFE;
+        assert!(good_stack && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PLP_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFE;
+        mem.memory[0x01FF] = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::PLP_IMPLIED;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_overflow == 1 && cpu.ps_decimal == 1 && cpu.ps_interrupt == 1 && cpu.ps_zero == 1 && cpu.ps_carry == 1 && cpu.ps_break == 0;
+        let good_sp = cpu.sp == 0xFF;
+        assert!(good_flags && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PLP_ignores_break_and_unused_bits() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFE;
+        mem.memory[0x01FF] = 0x30;
+        mem.memory[0xFFFC] = mos::CPU::PLP_IMPLIED;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_status = cpu.processor_status() == 0x20;
+        assert!(good_status && good_cycles);
+    }
+
+    #[test]
+    fn TSX_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TSX_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x84;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TSX_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0x00;
+        cpu.r_x = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TSX_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TXS_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::TXS_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_sp = cpu.sp == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_sp && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn JSR_RTS_round_trip() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::JSR_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x80;
+        mem.memory[0x8000] = mos::CPU::RTS_IMPLIED;
+        let jsr_cycles = cpu.execute(6, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let rts_cycles = cpu.execute(6, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = jsr_cycles == 6 && rts_cycles == 6;
+        let good_pc = cpu.pc == 0xFFFF;
+        let good_sp = cpu.sp == 0xFF;
+        assert!(good_pc && good_sp && good_cycles);
+    }
+
+    #[test]
+    fn PHP_PLP_round_trip() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_overflow = 1;
+        cpu.ps_zero = 1;
+        mem.memory[0xFFFC] = mos::CPU::PHP_IMPLIED;
+        mem.memory[0xFFFD] = mos::CPU::PLP_IMPLIED;
+        cpu.execute(3, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        cpu.ps_overflow = 0;
+        cpu.ps_zero = 0;
+        cpu.execute(4, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_flags = cpu.ps_overflow == 1 && cpu.ps_zero == 1 && cpu.ps_break == 0;
+        let good_sp = cpu.sp == 0xFF;
+        assert!(good_flags && good_sp);
+    }
+
+    #[test]
+    fn BCC_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_carry = 0;
+        mem.memory[0x8000] = mos::CPU::BCC_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BCC_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_carry = 1;
+        mem.memory[0x8000] = mos::CPU::BCC_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BCS_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_carry = 1;
+        mem.memory[0x8000] = mos::CPU::BCS_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BCS_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_carry = 0;
+        mem.memory[0x8000] = mos::CPU::BCS_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BEQ_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_zero = 1;
+        mem.memory[0x8000] = mos::CPU::BEQ_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BEQ_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_zero = 0;
+        mem.memory[0x8000] = mos::CPU::BEQ_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BMI_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_negative = 1;
+        mem.memory[0x8000] = mos::CPU::BMI_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BMI_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_negative = 0;
+        mem.memory[0x8000] = mos::CPU::BMI_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BNE_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_zero = 0;
+        mem.memory[0x8000] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BNE_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_zero = 1;
+        mem.memory[0x8000] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BPL_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_negative = 0;
+        mem.memory[0x8000] = mos::CPU::BPL_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BPL_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_negative = 1;
+        mem.memory[0x8000] = mos::CPU::BPL_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BVC_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_overflow = 0;
+        mem.memory[0x8000] = mos::CPU::BVC_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BVC_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_overflow = 1;
+        mem.memory[0x8000] = mos::CPU::BVC_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BVS_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_overflow = 1;
+        mem.memory[0x8000] = mos::CPU::BVS_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BVS_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_overflow = 0;
+        mem.memory[0x8000] = mos::CPU::BVS_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BNE_relative_backward() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8010;
+        mem.memory[0x8010] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x8011] = 0xF0;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BNE_relative_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x80FC;
+        mem.memory[0x80FC] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x80FD] = 0x04;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8102;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BNE_relative_backward_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x8001] = 0xFC;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x7FFE;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn JMP_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::JMP_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x34;
+        mem.memory[0xFFFE] = 0x12;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x1234;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn JMP_indirect() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0120] = 0x34;
+        mem.memory[0x0121] = 0x12;
+        mem.memory[0xFFFC] = mos::CPU::JMP_INDIRECT;
+        mem.memory[0xFFFD] = 0x20;
+        mem.memory[0xFFFE] = 0x01;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x1234;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn JMP_indirect_page_wrap_bug() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x10FF] = 0x34;
+        mem.memory[0x1000] = 0x12;
+        mem.memory[0x1100] = 0x56;
+        mem.memory[0xFFFC] = mos::CPU::JMP_INDIRECT;
+        mem.memory[0xFFFD] = 0xFF;
+        mem.memory[0xFFFE] = 0x10;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x1234;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn reset_loads_vector() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = 0x00;
+        mem.memory[0xFFFD] = 0x80;
+        cpu.r_a = 0x84;
+        cpu.reset_cpu(&mut mem);
+        let good_pc = cpu.pc == 0x8000;
+        let good_sp = cpu.sp == 0xFD;
+        let good_registers = cpu.r_a == 0 && cpu.ps_interrupt == 1;
+        assert!(good_pc && good_sp && good_registers);
+    }
+
+    #[test]
+    fn reset_runs_program_at_vector() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = 0x00;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0x8000] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0x8001] = 0x84;
+        cpu.reset_cpu(&mut mem);
+        let cycles = cpu.execute(2, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == 2;
+        let good_result = cpu.r_a == 0x84 && cpu.pc == 0x8002;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn BRK_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x90;
+        mem.memory[0x8000] = mos::CPU::BRK_IMPLIED;
+        mem.memory[0x8001] = 0xEA;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x9000;
+        let good_sp = cpu.sp == 0xFC;
+        let good_stack = mem.memory[0x01FF] == 0x80 && mem.memory[0x01FE] == 0x02 && mem.memory[0x01FD] == 0x31;
+        let good_flags = cpu.ps_interrupt == 1;
+        assert!(good_pc && good_sp && good_stack && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn RTI_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x9000;
+        cpu.sp = 0xFC;
+        mem.memory[0x01FF] = 0x80;
+        mem.memory[0x01FE] = 0x02;
+        mem.memory[0x01FD] = 0xC3;
+        mem.memory[0x9000] = mos::CPU::RTI_IMPLIED;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8002;
+        let good_sp = cpu.sp == 0xFF;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_overflow == 1 && cpu.ps_interrupt == 0 && cpu.ps_zero == 1 && cpu.ps_carry == 1 && cpu.ps_break == 0;
+        assert!(good_pc && good_sp && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn IRQ_serviced_when_unmasked() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x90;
+        cpu.set_irq_line(true);
+        let cycles = cpu.execute(7, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == 7;
+        let good_pc = cpu.pc == 0x9000;
+        // hardware interrupts push the status with B clear
+        let good_stack = mem.memory[0x01FF] == 0x80 && mem.memory[0x01FE] == 0x00 && mem.memory[0x01FD] == 0x20;
+        let good_flags = cpu.ps_interrupt == 1;
+        assert!(good_cycles && good_pc && good_stack && good_flags);
+    }
+
+    #[test]
+    fn IRQ_masked_by_interrupt_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_interrupt = 1;
+        mem.memory[0x8000] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0x8001] = 0x84;
+        cpu.set_irq_line(true);
+        let cycles = cpu.execute(2, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == 2;
+        let good_result = cpu.r_a == 0x84 && cpu.pc == 0x8002;
+        assert!(good_cycles && good_result);
+    }
+
+    #[test]
+    fn IRQ_level_triggered_retriggers_after_RTI() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x90;
+        mem.memory[0x9000] = mos::CPU::RTI_IMPLIED;
+        cpu.set_irq_line(true);
+        cpu.execute(7, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        cpu.execute(6, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_return = cpu.pc == 0x8000 && cpu.ps_interrupt == 0;
+        cpu.execute(7, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_retrigger = cpu.pc == 0x9000;
+        assert!(good_return && good_retrigger);
+    }
+
+    #[test]
+    fn NMI_edge_triggered() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_interrupt = 1;
+        mem.memory[0xFFFA] = 0x00;
+        mem.memory[0xFFFB] = 0xA0;
+        mem.memory[0xA000] = mos::CPU::RTI_IMPLIED;
+        mem.memory[0x8000] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0x8001] = 0x84;
+        cpu.set_nmi_line(true);
+        let cycles = cpu.execute(7, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // NMI ignores the interrupt mask
+        let good_nmi = cycles == 7 && cpu.pc == 0xA000;
+        cpu.execute(6, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // holding the line does not retrigger
+        cpu.set_nmi_line(true);
+        cpu.execute(2, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_no_retrigger = cpu.r_a == 0x84 && cpu.pc == 0x8002;
+        cpu.set_nmi_line(false);
+        cpu.set_nmi_line(true);
+        cpu.execute(7, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_second_edge = cpu.pc == 0xA000;
+        assert!(good_nmi && good_no_retrigger && good_second_edge);
+    }
+
+    #[test]
+    fn NMI_has_priority_over_IRQ() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0xFFFA] = 0x00;
+        mem.memory[0xFFFB] = 0xA0;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x90;
+        cpu.set_irq_line(true);
+        cpu.set_nmi_line(true);
+        cpu.execute(7, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        assert_eq!(cpu.pc, 0xA000);
+    }
+
+    #[test]
+    fn AND_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        mem.memory[0xFFFC] = mos::CPU::AND_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x9F;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        mem.memory[0x0042] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        mem.memory[0x4480] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_y = 1;
+        mem.memory[0x4403] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        mem.memory[0xFFFC] = mos::CPU::AND_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x0F;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn AND_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_x = 0xFF;
+        mem.memory[0x4501] = 0x9F;
+        mem.memory[0xFFFC] = mos::CPU::AND_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x90;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn ORA_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::ORA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x05;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        mem.memory[0x0042] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        mem.memory[0x4480] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        cpu.r_y = 1;
+        mem.memory[0x4403] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::ORA_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x85;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ORA_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::ORA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x00;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::EOR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x0F;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0x0042] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0x4480] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.r_y = 1;
+        mem.memory[0x4403] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::EOR_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn EOR_immediate_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::EOR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x84;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn BIT_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        mem.memory[0x0042] = 0xC0;
+        mem.memory[0xFFFC] = mos::CPU::BIT_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x01;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_overflow == 1 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn BIT_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        mem.memory[0x4480] = 0xC0;
+        mem.memory[0xFFFC] = mos::CPU::BIT_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x01;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_overflow == 1 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn BIT_zero_page_clears_flags() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        cpu.ps_negative = 1;
+        cpu.ps_overflow = 1;
+        mem.memory[0x0042] = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::BIT_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_overflow == 0 && cpu.ps_zero == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::CMP_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        mem.memory[0x0042] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        mem.memory[0x4480] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        cpu.r_y = 1;
+        mem.memory[0x4403] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        cpu.r_x = 0x04;
+        mem.memory[0x0006] = 0x00;
+        mem.memory[0x0007] = 0x80;
+        mem.memory[0x8000] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        cpu.r_y = 0x04;
+        mem.memory[0x0002] = 0x00;
+        mem.memory[0x0003] = 0x80;
+        mem.memory[0x8004] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_immediate_equal() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::CMP_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x40;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CMP_immediate_less() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CMP_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x40;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPX_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::CPX_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x40;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPX_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x40;
+        mem.memory[0x0042] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::CPX_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPX_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x40;
+        mem.memory[0x4480] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::CPX_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPX_immediate_less() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CPX_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x40;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPY_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::CPY_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPY_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x40;
+        mem.memory[0x0042] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CPY_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPY_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x40;
+        mem.memory[0x4480] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CPY_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CPY_immediate_less() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::CPY_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x40;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ASL_accumulator() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x02;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ASL_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x02;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ASL_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x02;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ASL_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x4480] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x02;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ASL_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x02;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ASL_accumulator_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LSR_accumulator() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_negative = 1;
+        cpu.r_a = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::LSR_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LSR_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_negative = 1;
+        mem.memory[0x0042] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::LSR_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LSR_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_negative = 1;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::LSR_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LSR_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_negative = 1;
+        mem.memory[0x4480] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::LSR_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LSR_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_negative = 1;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::LSR_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x40;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROL_accumulator() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        cpu.r_a = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::ROL_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROL_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        mem.memory[0x0042] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::ROL_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROL_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::ROL_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROL_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        mem.memory[0x4480] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::ROL_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROL_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::ROL_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROL_accumulator_carry_out() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::ROL_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROR_accumulator() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        cpu.r_a = 0x02;
+        mem.memory[0xFFFC] = mos::CPU::ROR_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROR_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        mem.memory[0x0042] = 0x02;
+        mem.memory[0xFFFC] = mos::CPU::ROR_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROR_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x02;
+        mem.memory[0xFFFC] = mos::CPU::ROR_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROR_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        mem.memory[0x4480] = 0x02;
+        mem.memory[0xFFFC] = mos::CPU::ROR_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROR_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x02;
+        mem.memory[0xFFFC] = mos::CPU::ROR_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ROR_accumulator_carry_out() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::ROR_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INC_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x7F;
+        mem.memory[0xFFFC] = mos::CPU::INC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x80;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INC_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x7F;
+        mem.memory[0xFFFC] = mos::CPU::INC_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x80;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INC_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x4480] = 0x7F;
+        mem.memory[0xFFFC] = mos::CPU::INC_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x80;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INC_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x7F;
+        mem.memory[0xFFFC] = mos::CPU::INC_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x80;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INC_zero_page_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::INC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEC_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::DEC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEC_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 4;
+        mem.memory[0x0046] = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::DEC_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0046] == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEC_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x4480] = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::DEC_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x80;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4480] == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEC_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 1;
+        mem.memory[0x4403] = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::DEC_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x02;
+        mem.memory[0xFFFE] = 0x44;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x4403] == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEC_zero_page_wraparound() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::DEC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = mem.memory[0x0042] == 0xFF;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INX_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::INX_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x00;
+        let good_flags = cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn INY_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x7F;
+        mem.memory[0xFFFC] = mos::CPU::INY_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == 0x80;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEX_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::DEX_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x00;
+        let good_flags = cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEY_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::DEY_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == 0xFF;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TAX_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TAX_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x84;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TAX_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TAX_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TAY_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TAY_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == 0x84;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TAY_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TAY_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TXA_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TXA_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x84;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TXA_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TXA_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TYA_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TYA_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x84;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TYA_zero_flag() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::TYA_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CLC_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::CLC_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SEC_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_carry = 0;
+        mem.memory[0xFFFC] = mos::CPU::SEC_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_carry == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CLI_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_interrupt = 1;
+        mem.memory[0xFFFC] = mos::CPU::CLI_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_interrupt == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SEI_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_interrupt = 0;
+        mem.memory[0xFFFC] = mos::CPU::SEI_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_interrupt == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CLV_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_overflow = 1;
+        mem.memory[0xFFFC] = mos::CPU::CLV_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_overflow == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn CLD_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::CLD_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_decimal == 0;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SED_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.ps_decimal = 0;
+        mem.memory[0xFFFC] = mos::CPU::SED_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_decimal == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn NOP_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::NOP_IMPLIED;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFD;
+        let good_result = cpu.r_a == 0x84;
+        assert!(good_pc && good_result && good_cycles);
+    }
+
+    #[test]
+    fn execute_runs_until_budget_used() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        // LDX #5 ; loop: DEX ; BNE loop
+        mem.memory[0x8000] = mos::CPU::LDX_IMMEDIATE;
+        mem.memory[0x8001] = 0x05;
+        mem.memory[0x8002] = mos::CPU::DEX_IMPLIED;
+        mem.memory[0x8003] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x8004] = 0xFD;
+        // 2 + 5 * DEX (2) + 4 * BNE taken (3) + BNE not taken (2)
+        let cycles_init = 26;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == cycles_init;
+        let good_result = cpu.r_x == 0 && cpu.pc == 0x8005;
+        assert!(good_cycles && good_result);
+    }
+
+    #[test]
+    fn execute_reports_overshoot() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x84;
+        mem.memory[0xFFFE] = mos::CPU::LDX_ABSOLUTE;
+        // 3 cycles requested, LDA (2) + LDX absolute (4) = 6 spent
+        let cycles = cpu.execute(3, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        assert_eq!(cycles, 6);
+    }
+
+    #[test]
+    fn step_runs_one_instruction() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0x8001] = 0x00;
+        mem.memory[0x8002] = 0x90;
+        mem.memory[0x8003] = mos::CPU::LDX_IMMEDIATE;
+        mem.memory[0x9000] = 0x84;
+        let cycles = cpu.step(&mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == 4;
+        let good_result = cpu.r_a == 0x84 && cpu.pc == 0x8003;
+        assert!(good_cycles && good_result);
+    }
+
+    #[test]
+    fn illegal_opcode_error() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.undocumented_opcodes = false;
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = 0x02;
+        let result = cpu.execute(2, &mut mem);
+        assert_eq!(result, Err(mos::EmuError::IllegalOpcode { address: 0x8000, opcode: 0x02 }));
+    }
+
+    #[test]
+    fn illegal_opcode_error_message() {
+        let err = mos::EmuError::IllegalOpcode { address: 0x8000, opcode: 0x02 };
+        assert_eq!(err.to_string(), "illegal opcode 0x02 at 0x8000");
+    }
+
+    #[test]
+    fn strict_stack_overflow() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.strict_stack = true;
+        cpu.sp = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::PHA_IMPLIED;
+        let result = cpu.execute(3, &mut mem);
+        assert_eq!(result, Err(mos::EmuError::StackOverflow));
+    }
+
+    #[test]
+    fn strict_stack_underflow() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.strict_stack = true;
+        cpu.sp = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::RTS_IMPLIED;
+        let result = cpu.execute(6, &mut mem);
+        assert_eq!(result, Err(mos::EmuError::StackUnderflow));
+    }
+
+    // flat RAM with a read-to-clear status register at 0xD000 and a write log
+    struct DeviceBus {
+        mem: mos::MEMORY,
+        status: u8,
+        writes: Vec<(u16, u8)>,
+    }
+
+    impl mos::Bus for DeviceBus {
+        fn read(&mut self, address: u16) -> u8 {
+            if address == 0xD000 {
+                let status = self.status;
+                self.status = 0;
+                return status;
+            }
+            self.mem.memory[address as usize]
+        }
+
+        fn write(&mut self, address: u16, value: u8) {
+            self.writes.push((address, value));
+            self.mem.memory[address as usize] = value;
+        }
+
+        fn peek(&self, address: u16) -> u8 {
+            if address == 0xD000 {
+                return self.status;
+            }
+            self.mem.memory[address as usize]
+        }
+    }
+
+    #[test]
+    fn bus_memory_mapped_device() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = DeviceBus { mem: mos::build_memory(), status: 0x84, writes: Vec::new() };
+        bus.mem.memory[0xFFFC] = mos::CPU::LDA_ABSOLUTE;
+        bus.mem.memory[0xFFFD] = 0x00;
+        bus.mem.memory[0xFFFE] = 0xD0;
+        let cycles = cpu.execute(4, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == 4;
+        let good_result = cpu.r_a == 0x84;
+        // the read had its side effect on the device
+        let good_device = bus.status == 0;
+        assert!(good_cycles && good_result && good_device);
+    }
+
+    #[test]
+    fn bus_peek_has_no_side_effects() {
+        let bus = DeviceBus { mem: mos::build_memory(), status: 0x84, writes: Vec::new() };
+        let first = mos::Bus::peek(&bus, 0xD000);
+        let second = mos::Bus::peek(&bus, 0xD000);
+        assert!(first == 0x84 && second == 0x84);
+    }
+
+    #[test]
+    fn bus_read_modify_write_double_write() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = DeviceBus { mem: mos::build_memory(), status: 0, writes: Vec::new() };
+        bus.mem.memory[0xFFFC] = mos::CPU::INC_ZERO_PAGE;
+        bus.mem.memory[0xFFFD] = 0x42;
+        bus.mem.memory[0x0042] = 0x10;
+        cpu.execute(5, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // the unmodified value is written back before the result
+        assert_eq!(bus.writes, vec![(0x0042, 0x10), (0x0042, 0x11)]);
+    }
+
+    #[test]
+    fn memory_map_ram_mirror() {
+        let mut map = mos::build_memory_map()
+            .ram(0x0000, 0x07FF)
+            .mirror(0x0800, 0x1FFF, 0x0000, 0x0800)
+            .build();
+        mos::Bus::write(&mut map, 0x1805, 0x84);
+        let good_base = mos::Bus::read(&mut map, 0x0005) == 0x84;
+        let good_mirror = mos::Bus::read(&mut map, 0x0805) == 0x84 && mos::Bus::peek(&map, 0x1005) == 0x84;
+        assert!(good_base && good_mirror);
+    }
+
+    #[test]
+    fn memory_map_rom_is_write_protected() {
+        let mut map = mos::build_memory_map()
+            .rom(0xC000, &[0x11, 0x22, 0x33])
+            .build();
+        mos::Bus::write(&mut map, 0xC001, 0x84);
+        assert_eq!(mos::Bus::read(&mut map, 0xC001), 0x22);
+    }
+
+    #[test]
+    fn memory_map_rom_file() {
+        let path = std::env::temp_dir().join("rust6502_memory_map_rom_file.bin");
+        std::fs::write(&path, [0xEA, 0x84]).unwrap();
+        let mut map = mos::build_memory_map()
+            .rom_file(0xF000, path.to_str().unwrap())
+            .unwrap()
+            .build();
+        std::fs::remove_file(&path).unwrap();
+        let good_rom = mos::Bus::read(&mut map, 0xF000) == 0xEA && mos::Bus::read(&mut map, 0xF001) == 0x84;
+        assert!(good_rom);
+    }
+
+    #[test]
+    fn memory_map_rom_file_missing() {
+        let result = mos::build_memory_map().rom_file(0xF000, "does/not/exist.bin");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "ends before it starts")]
+    fn memory_map_ram_reversed_range() {
+        mos::build_memory_map().ram(0x07FF, 0x0000);
+    }
+
+    #[test]
+    #[should_panic(expected = "ends before it starts")]
+    fn memory_map_mirror_reversed_range() {
+        mos::build_memory_map().mirror(0x1FFF, 0x0800, 0x0000, 0x0800);
+    }
+
+    #[test]
+    #[should_panic(expected = "ends before it starts")]
+    fn memory_map_unmapped_reversed_range() {
+        mos::build_memory_map().unmapped(0x4FFF, 0x4000);
+    }
+
+    #[test]
+    fn memory_map_later_regions_shadow_earlier() {
+        let mut map = mos::build_memory_map()
+            .ram(0x0000, 0xFFFF)
+            .unmapped(0x4000, 0x4FFF)
+            .build();
+        mos::Bus::write(&mut map, 0x4000, 0x84);
+        mos::Bus::write(&mut map, 0x3FFF, 0x42);
+        let good_hole = mos::Bus::read(&mut map, 0x4000) == 0x42;
+        assert!(good_hole);
+    }
+
+    #[test]
+    fn memory_map_unmapped_reads_open_bus() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut map = mos::build_memory_map()
+            .ram(0x0000, 0x1FFF)
+            .rom(0x8000, &[mos::CPU::LDA_ABSOLUTE, 0x00, 0x50])
+            .build();
+        cpu.pc = 0x8000;
+        let cycles = cpu.execute(4, &mut map).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // nothing drives the bus at 0x5000, so the high byte of the operand is still floating on it
+        let good_result = cpu.r_a == 0x50;
+        let good_cycles = cycles == 4;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn memory_map_fault_on_unmapped() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut map = mos::build_memory_map()
+            .rom(0x8000, &[mos::CPU::STA_ABSOLUTE, 0x00, 0x50])
+            .fault_on_unmapped(true)
+            .build();
+        cpu.pc = 0x8000;
+        let result = cpu.execute(4, &mut map);
+        assert_eq!(result, Err(mos::EmuError::BusFault { address: 0x5000 }));
+    }
+
+    #[test]
+    fn memory_map_runs_program() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut map = mos::build_memory_map()
+            .ram(0x0000, 0x07FF)
+            .mirror(0x0800, 0x1FFF, 0x0000, 0x0800)
+            .rom(0xFFF0, &[
+                mos::CPU::LDA_IMMEDIATE, 0x84,
+                mos::CPU::STA_ABSOLUTE, 0x10, 0x08,
+                mos::CPU::LDX_ZERO_PAGE, 0x10,
+                0x00, 0x00, 0x00, 0x00, 0x00,
+                0xF0, 0xFF,
+                0x00, 0x00,
+            ])
+            .build();
+        cpu.reset_cpu(&mut map);
+        let cycles = cpu.execute(9, &mut map).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_result = cpu.r_x == 0x84;
+        let good_cycles = cycles == 9;
+        assert!(good_result && good_cycles);
+    }
+
+    // every byte of a bank holds the bank number
+    fn numbered_banks(bank_size: usize, banks: usize) -> Vec<u8> {
+        (0..bank_size * banks).map(|offset| (offset / bank_size) as u8).collect()
+    }
+
+    #[test]
+    fn banked_switchable_16k() {
+        let mut banked = mos::build_switchable_16k(mos::build_memory(), numbered_banks(0x4000, 4));
+        let good_initial = mos::Bus::read(&mut banked, 0x8000) == 0 && mos::Bus::read(&mut banked, 0xC000) == 3;
+        mos::Bus::write(&mut banked, 0x8123, 2);
+        let good_switched = mos::Bus::read(&mut banked, 0xBFFF) == 2 && mos::Bus::read(&mut banked, 0xFFFF) == 3;
+        let good_bank = banked.bank(0) == 2;
+        assert!(good_initial && good_switched && good_bank);
+    }
+
+    #[test]
+    fn banked_switchable_16k_from_cpu() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut rom = numbered_banks(0x4000, 8);
+        // program in the fixed bank at 0xC000: LDA #5 ; STA $8000 ; LDX $8000
+        let program = [mos::CPU::LDA_IMMEDIATE, 0x05, mos::CPU::STA_ABSOLUTE, 0x00, 0x80, mos::CPU::LDX_ABSOLUTE, 0x00, 0x80];
+        rom[0x1C000..0x1C000 + program.len()].copy_from_slice(&program);
+        let mut banked = mos::build_switchable_16k(mos::build_memory(), rom);
+        cpu.pc = 0xC000;
+        let cycles = cpu.execute(10, &mut banked).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_result = cpu.r_x == 5;
+        let good_cycles = cycles == 10;
+        assert!(good_result && good_cycles);
+    }
+
+    #[test]
+    fn banked_8k_x4() {
+        let mut banked = mos::build_8k_x4(mos::build_memory(), numbered_banks(0x2000, 16), [0x5000, 0x5001, 0x5002, 0x5003]);
+        let good_initial = mos::Bus::read(&mut banked, 0x8000) == 0 && mos::Bus::read(&mut banked, 0xE000) == 3;
+        mos::Bus::write(&mut banked, 0x5001, 9);
+        mos::Bus::write(&mut banked, 0x5003, 15);
+        let good_switched = mos::Bus::read(&mut banked, 0xA000) == 9 && mos::Bus::read(&mut banked, 0xE000) == 15;
+        let good_fixed = mos::Bus::read(&mut banked, 0x8000) == 0 && mos::Bus::read(&mut banked, 0xC000) == 2;
+        assert!(good_initial && good_switched && good_fixed);
+    }
+
+    #[test]
+    fn banked_bank_number_wraps() {
+        let mut banked = mos::build_switchable_16k(mos::build_memory(), numbered_banks(0x4000, 4));
+        mos::Bus::write(&mut banked, 0x8000, 6);
+        assert_eq!(banked.bank(0), 2);
+    }
+
+    #[test]
+    fn banked_ram_window() {
+        let mut banked = mos::build_banked_memory(mos::build_memory(), Vec::new())
+            .ram(0x2000 * 2)
+            .window(0x6000, 0x2000, mos::BankSource::Ram, 0)
+            .register(0x5FFF, 0x5FFF, 0);
+        mos::Bus::write(&mut banked, 0x6000, 0x11);
+        mos::Bus::write(&mut banked, 0x5FFF, 1);
+        mos::Bus::write(&mut banked, 0x6000, 0x22);
+        let good_bank_one = mos::Bus::read(&mut banked, 0x6000) == 0x22;
+        mos::Bus::write(&mut banked, 0x5FFF, 0);
+        let good_bank_zero = mos::Bus::read(&mut banked, 0x6000) == 0x11;
+        // the register is not backed by memory
+        let good_inner = banked.inner.memory[0x5FFF] == 0;
+        assert!(good_bank_one && good_bank_zero && good_inner);
+    }
+
+    #[test]
+    fn banked_passes_through_to_inner_bus() {
+        let mut banked = mos::build_switchable_16k(mos::build_memory(), numbered_banks(0x4000, 2));
+        mos::Bus::write(&mut banked, 0x0200, 0x84);
+        let good_inner = banked.inner.memory[0x0200] == 0x84 && mos::Bus::peek(&banked, 0x0200) == 0x84;
+        assert!(good_inner);
+    }
+
+    // flat RAM recording every bus access
+    struct LogBus {
+        mem: mos::MEMORY,
+        log: Vec<(&'static str, u16, u8)>,
+    }
+
+    impl mos::Bus for LogBus {
+        fn read(&mut self, address: u16) -> u8 {
+            let value = self.mem.memory[address as usize];
+            self.log.push(("read", address, value));
+            value
+        }
+
+        fn write(&mut self, address: u16, value: u8) {
+            self.log.push(("write", address, value));
+            self.mem.memory[address as usize] = value;
+        }
+
+        fn peek(&self, address: u16) -> u8 {
+            self.mem.memory[address as usize]
+        }
+    }
+
+    fn build_log_bus() -> LogBus {
+        LogBus { mem: mos::build_memory(), log: Vec::new() }
+    }
+
+    #[test]
+    fn cycle_accurate_one_bus_access_per_cycle() {
+        for opcode in 0..=255u8 {
+            let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+            let mut bus = build_log_bus();
+            cpu.cycle_accurate = true;
+            cpu.pc = 0x8000;
+            cpu.sp = 0xF0;
+            cpu.r_y = 0xF0;
+            bus.mem.memory[0x8000] = opcode;
+            bus.mem.memory[0x8001] = 0x20;
+            bus.mem.memory[0x8002] = 0x30;
+            match cpu.step(&mut bus) {
+                Ok(cycles) => assert_eq!(bus.log.len() as i32, cycles, "opcode {:#04x}", opcode),
+                Err(mos::EmuError::Jammed { .. }) => continue,
+                Err(err) => panic!("opcode {:#04x}: {}", opcode, err),
+            }
+        }
+    }
+
+    #[test]
+    fn cycle_accurate_page_cross_dummy_read() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.r_x = 0x11;
+        bus.mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE_X;
+        bus.mem.memory[0x8001] = 0xF0;
+        bus.mem.memory[0x8002] = 0x44;
+        bus.mem.memory[0x4501] = 0x84;
+        cpu.execute(5, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let expected = vec![
+            ("read", 0x8000, mos::CPU::LDA_ABSOLUTE_X),
+            ("read", 0x8001, 0xF0),
+            ("read", 0x8002, 0x44),
+            ("read", 0x4401, 0x00), // high byte not fixed up yet
+            ("read", 0x4501, 0x84),
+        ];
+        assert_eq!(bus.log, expected);
+    }
+
+    #[test]
+    fn cycle_accurate_store_indexed_dummy_read() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.r_a = 0x84;
+        cpu.r_y = 0x01;
+        bus.mem.memory[0x8000] = mos::CPU::STA_ABSOLUTE_Y;
+        bus.mem.memory[0x8001] = 0x00;
+        bus.mem.memory[0x8002] = 0x44;
+        cpu.execute(5, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let expected = vec![
+            ("read", 0x8000, mos::CPU::STA_ABSOLUTE_Y),
+            ("read", 0x8001, 0x00),
+            ("read", 0x8002, 0x44),
+            ("read", 0x4401, 0x00),
+            ("write", 0x4401, 0x84),
+        ];
+        assert_eq!(bus.log, expected);
+    }
+
+    #[test]
+    fn cycle_accurate_RTS_dummy_reads() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.sp = 0xFD;
+        bus.mem.memory[0x8000] = mos::CPU::RTS_IMPLIED;
+        bus.mem.memory[0x01FE] = 0x33;
+        bus.mem.memory[0x01FF] = 0x12;
+        cpu.execute(6, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let expected = vec![
+            ("read", 0x8000, mos::CPU::RTS_IMPLIED),
+            ("read", 0x8001, 0x00),
+            ("read", 0x01FD, 0x00),
+            ("read", 0x01FE, 0x33),
+            ("read", 0x01FF, 0x12),
+            ("read", 0x1233, 0x00),
+        ];
+        let good_log = bus.log == expected;
+        let good_pc = cpu.pc == 0x1234;
+        assert!(good_log && good_pc);
+    }
+
+    #[test]
+    fn cycle_accurate_JSR_cycle_order() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.sp = 0xFD;
+        bus.mem.memory[0x8000] = mos::CPU::JSR_ABSOLUTE;
+        bus.mem.memory[0x8001] = 0x34;
+        bus.mem.memory[0x8002] = 0x12;
+        cpu.execute(6, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // the high byte of the target is fetched last, after the return address is pushed
+        let expected = vec![
+            ("read", 0x8000, mos::CPU::JSR_ABSOLUTE),
+            ("read", 0x8001, 0x34),
+            ("read", 0x01FD, 0x00),
+            ("write", 0x01FD, 0x80),
+            ("write", 0x01FC, 0x02),
+            ("read", 0x8002, 0x12),
+        ];
+        let good_log = bus.log == expected;
+        let good_pc = cpu.pc == 0x1234;
+        let good_sp = cpu.sp == 0xFB;
+        assert!(good_log && good_pc && good_sp);
+    }
+
+    #[test]
+    fn JSR_fetches_high_byte_after_push() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        // JSR $3000 in the stack page with sp on its high operand byte: the push of
+        // the return address high byte (0x01) overwrites it before it is fetched
+        cpu.pc = 0x017E;
+        cpu.sp = 0x80;
+        mem.memory[0x017E] = mos::CPU::JSR_ABSOLUTE;
+        mem.memory[0x017F] = 0x00;
+        mem.memory[0x0180] = 0x30;
+        cpu.execute(6, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        assert_eq!(cpu.pc, 0x0100);
+    }
+
+    #[test]
+    fn cycle_accurate_PLA_dummy_reads() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.sp = 0xFE;
+        bus.mem.memory[0x8000] = mos::CPU::PLA_IMPLIED;
+        bus.mem.memory[0x01FF] = 0x84;
+        cpu.execute(4, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let expected = vec![
+            ("read", 0x8000, mos::CPU::PLA_IMPLIED),
+            ("read", 0x8001, 0x00),
+            ("read", 0x01FE, 0x00),
+            ("read", 0x01FF, 0x84),
+        ];
+        assert_eq!(bus.log, expected);
+    }
+
+    #[test]
+    fn cycle_accurate_read_modify_write_indexed() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.r_x = 0x02;
+        bus.mem.memory[0x8000] = mos::CPU::ASL_ABSOLUTE_X;
+        bus.mem.memory[0x8001] = 0xFF;
+        bus.mem.memory[0x8002] = 0x44;
+        bus.mem.memory[0x4501] = 0x41;
+        cpu.execute(7, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let expected = vec![
+            ("read", 0x8000, mos::CPU::ASL_ABSOLUTE_X),
+            ("read", 0x8001, 0xFF),
+            ("read", 0x8002, 0x44),
+            ("read", 0x4401, 0x00),
+            ("read", 0x4501, 0x41),
+            ("write", 0x4501, 0x41),
+            ("write", 0x4501, 0x82),
+        ];
+        assert_eq!(bus.log, expected);
+    }
+
+    #[test]
+    fn dummy_reads_skipped_when_not_cycle_accurate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = build_log_bus();
+        cpu.pc = 0x8000;
+        cpu.r_x = 0x11;
+        bus.mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE_X;
+        bus.mem.memory[0x8001] = 0xF0;
+        bus.mem.memory[0x8002] = 0x44;
+        let cycles = cpu.execute(5, &mut bus).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles == 5;
+        let good_accesses = bus.log.len() == 4;
+        assert!(good_cycles && good_accesses);
+    }
+
+    #[test]
+    fn tick_commits_on_last_cycle() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0x8001] = 0x00;
+        mem.memory[0x8002] = 0x40;
+        mem.memory[0x4000] = 0x84;
+        let mut cycles = Vec::new();
+        for _ in 0..3 {
+            cycles.push(cpu.tick(&mut mem).unwrap());
+        }
+        let good_in_flight = !cpu.at_instruction_boundary() && cpu.r_a == 0x00;
+        cycles.push(cpu.tick(&mut mem).unwrap());
+        let good_done = cpu.at_instruction_boundary() && cpu.r_a == 0x84 && cpu.pc == 0x8003;
+        let good_addresses = cycles.iter().map(|cycle| cycle.address).collect::<Vec<u16>>() == vec![0x8000, 0x8001, 0x8002, 0x4000];
+        assert!(good_in_flight && good_done && good_addresses);
+    }
+
+    #[test]
+    fn tick_sees_memory_changed_mid_instruction() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0x8001] = 0x00;
+        mem.memory[0x8002] = 0x40;
+        mem.memory[0x4000] = 0x11;
+        for _ in 0..3 {
+            cpu.tick(&mut mem).unwrap();
+        }
+        // another chip updates the register between the operand fetch and the read
+        mem.memory[0x4000] = 0x84;
+        let cycle = cpu.tick(&mut mem).unwrap();
+        let good_cycle = cycle == mos::BusCycle { address: 0x4000, value: 0x84, access: mos::Access::Read };
+        assert!(good_cycle && cpu.r_a == 0x84);
+    }
+
+    #[test]
+    fn tick_read_side_effects_happen_once() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut bus = DeviceBus { mem: mos::build_memory(), status: 0x84, writes: Vec::new() };
+        cpu.pc = 0x8000;
+        bus.mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE;
+        bus.mem.memory[0x8001] = 0x00;
+        bus.mem.memory[0x8002] = 0xD0;
+        bus.mem.memory[0x8003] = mos::CPU::NOP_IMPLIED;
+        for _ in 0..4 {
+            cpu.tick(&mut bus).unwrap();
+        }
+        let good_result = cpu.r_a == 0x84 && bus.status == 0;
+        assert!(good_result && cpu.at_instruction_boundary());
+    }
+
+    #[test]
+    fn tick_matches_cycle_accurate_execute() {
+        let program = [
+            mos::CPU::LDX_IMMEDIATE, 0x03,
+            mos::CPU::JSR_ABSOLUTE, 0x00, 0x90,
+            mos::CPU::DEX_IMPLIED,
+            mos::CPU::BNE_RELATIVE, 0xFA,
+            mos::CPU::NOP_IMPLIED,
+        ];
+        let subroutine = [mos::CPU::INC_ABSOLUTE_X, 0xFF, 0x40, mos::CPU::PHA_IMPLIED, mos::CPU::PLA_IMPLIED, mos::CPU::RTS_IMPLIED];
+
+        let mut stepped_cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut stepped_bus = build_log_bus();
+        stepped_cpu.cycle_accurate = true;
+        stepped_cpu.pc = 0x8000;
+        stepped_bus.mem.memory[0x8000..0x8000 + program.len()].copy_from_slice(&program);
+        stepped_bus.mem.memory[0x9000..0x9000 + subroutine.len()].copy_from_slice(&subroutine);
+        let cycles = stepped_cpu.execute(96, &mut stepped_bus).unwrap();
+
+        let mut ticked_cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut ticked_bus = build_log_bus();
+        ticked_cpu.pc = 0x8000;
+        ticked_bus.mem.memory[0x8000..0x8000 + program.len()].copy_from_slice(&program);
+        ticked_bus.mem.memory[0x9000..0x9000 + subroutine.len()].copy_from_slice(&subroutine);
+        for _ in 0..cycles {
+            ticked_cpu.tick(&mut ticked_bus).unwrap();
+        }
+
+        let good_log = ticked_bus.log == stepped_bus.log;
+        let good_state = ticked_cpu.pc == stepped_cpu.pc && ticked_cpu.r_x == stepped_cpu.r_x && ticked_cpu.sp == stepped_cpu.sp;
+        let good_memory = ticked_bus.mem.memory[0x4100..0x4103] == [1, 1, 1] && ticked_cpu.pc == 0x8009;
+        assert!(good_log && good_state && good_memory && ticked_cpu.at_instruction_boundary());
+    }
+
+    #[test]
+    fn tick_irq_raised_mid_instruction() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x90;
+        cpu.tick(&mut mem).unwrap();
+        cpu.set_irq_line(true);
+        for _ in 0..3 {
+            cpu.tick(&mut mem).unwrap();
+        }
+        // the line survives the commit and is serviced at the next boundary
+        let good_line = cpu.irq_line && cpu.pc == 0x8003;
+        for _ in 0..7 {
+            cpu.tick(&mut mem).unwrap();
+        }
+        assert!(good_line && cpu.pc == 0x9000);
+    }
+
+    #[test]
+    fn tick_error_leaves_cpu_at_instruction() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.undocumented_opcodes = false;
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = 0x02;
+        let result = cpu.tick(&mut mem);
+        let good_error = result == Err(mos::EmuError::IllegalOpcode { address: 0x8000, opcode: 0x02 });
+        assert!(good_error && cpu.pc == 0x8000 && cpu.at_instruction_boundary());
+    }
+
+    #[test]
+    fn step_finishes_ticked_instruction() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0x8001] = 0x00;
+        mem.memory[0x8002] = 0x40;
+        mem.memory[0x4000] = 0x84;
+        cpu.tick(&mut mem).unwrap();
+        let cycles = cpu.step(&mut mem).unwrap();
+        let good_result = cpu.r_a == 0x84 && cpu.pc == 0x8003;
+        assert!(good_result && cycles == 3);
+    }
+
+    #[test]
+    fn SLO_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        mem.memory[0x0042] = 0x81;
+        mem.memory[0xFFFC] = mos::CPU::SLO_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0x02;
+        let good_result = cpu.r_a == 0x12;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SLO_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        cpu.r_y = 0x04;
+        mem.memory[0x0020] = 0x00;
+        mem.memory[0x0021] = 0x40;
+        mem.memory[0x4004] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::SLO_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 8;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4004] == 0x80;
+        let good_result = cpu.r_a == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn RLA_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.ps_carry = 1;
+        mem.memory[0x4000] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::RLA_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4000] == 0x81;
+        let good_result = cpu.r_a == 0x81;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SRE_zero_page_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        cpu.r_x = 0x02;
+        mem.memory[0x0044] = 0x03;
+        mem.memory[0xFFFC] = mos::CPU::SRE_ZERO_PAGE_X;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0044] == 0x01;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn RRA_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_x = 0x01;
+        cpu.ps_carry = 1;
+        mem.memory[0x4001] = 0x02;
+        mem.memory[0xFFFC] = mos::CPU::RRA_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4001] == 0x81;
+        let good_result = cpu.r_a == 0x91;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 0 && cpu.ps_negative == 1;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn RRA_zero_page_carry_into_add() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        mem.memory[0x0042] = 0x03;
+        mem.memory[0xFFFC] = mos::CPU::RRA_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0x01;
+        let good_result = cpu.r_a == 0x12;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DCP_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.r_x = 0x04;
+        mem.memory[0x0024] = 0x00;
+        mem.memory[0x0025] = 0x40;
+        mem.memory[0x4000] = 0x11;
+        mem.memory[0xFFFC] = mos::CPU::DCP_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 8;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4000] == 0x10;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_memory && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DCP_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        cpu.r_y = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::DCP_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4005] == 0xFF;
+        let good_register = cpu.r_a == 0x01;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_memory && good_register && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ISC_indirect_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x20;
+        cpu.r_y = 0x20;
+        cpu.ps_carry = 1;
+        mem.memory[0x0020] = 0xF0;
+        mem.memory[0x0021] = 0x40;
+        mem.memory[0x4110] = 0x0F;
+        mem.memory[0xFFFC] = mos::CPU::ISC_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 8;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4110] == 0x10;
+        let good_result = cpu.r_a == 0x10;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ISC_zero_page_decimal() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x50;
+        cpu.ps_carry = 1;
+        cpu.ps_decimal = 1;
+        mem.memory[0x0042] = 0x18;
+        mem.memory[0xFFFC] = mos::CPU::ISC_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0x19;
+        let good_result = cpu.r_a == 0x31;
+        let good_flags = cpu.ps_carry == 1;
+        assert!(good_memory && good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LAX_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::LAX_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x84 && cpu.r_x == 0x84;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LAX_absolute_Y_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x01;
+        mem.memory[0x4100] = 0x37;
+        mem.memory[0xFFFC] = mos::CPU::LAX_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0xFF;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x37 && cpu.r_x == 0x37;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LAX_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_y = 0x02;
+        mem.memory[0x0020] = 0x00;
+        mem.memory[0x0021] = 0x40;
+        mem.memory[0x4002] = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::LAX_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00 && cpu.r_x == 0x00;
+        let good_flags = cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SAX_zero_page_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_x = 0x3C;
+        cpu.r_y = 0x01;
+        cpu.ps_zero = 1;
+        mem.memory[0xFFFC] = mos::CPU::SAX_ZERO_PAGE_Y;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0043] == 0x30;
+        let good_flags = cpu.ps_zero == 1;
+        assert!(good_memory && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SAX_indirect_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x0F;
+        cpu.r_x = 0x06;
+        mem.memory[0x0026] = 0x00;
+        mem.memory[0x0027] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::SAX_INDIRECT_X;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4000] == 0x06;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn ANC_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::ANC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x80;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x80;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ANC_immediate_alias() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = 0x2B;
+        mem.memory[0xFFFD] = 0x7F;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x7F;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ALR_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::ALR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x03;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x01;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ARR_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::ARR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xFF;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0 && cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ARR_immediate_overflow() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x60;
+        mem.memory[0xFFFC] = mos::CPU::ARR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_overflow == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ARR_immediate_decimal() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ARR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x55;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x80;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 1 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBX_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF0;
+        cpu.r_x = 0x3C;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBX_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_x == 0x20 && cpu.r_a == 0xF0;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn USBC_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x30;
+        cpu.ps_carry = 1;
+        mem.memory[0xFFFC] = mos::CPU::USBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x10;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x20;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 0 && cpu.ps_overflow == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn XAA_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::XAA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xEE;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn XAA_immediate_magic() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.xaa_magic = 0x00;
+        cpu.r_a = 0x0F;
+        cpu.r_x = 0x3C;
+        mem.memory[0xFFFC] = mos::CPU::XAA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x0C;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LXA_immediate_magic() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.lxa_magic = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::LXA_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x5A;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x5A && cpu.r_x == 0x5A;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LAS_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xF0;
+        cpu.r_y = 0x01;
+        mem.memory[0x4001] = 0x3F;
+        mem.memory[0xFFFC] = mos::CPU::LAS_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x30 && cpu.r_x == 0x30 && cpu.sp == 0x30;
+        let good_flags = cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SHX_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0xFF;
+        cpu.r_y = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::SHX_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4005] == 0x41;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn SHX_absolute_Y_unmasked() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.sh_and_high_byte = false;
+        cpu.r_x = 0xFF;
+        cpu.r_y = 0x05;
+        mem.memory[0xFFFC] = mos::CPU::SHX_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4005] == 0xFF;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn SHY_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x02;
+        cpu.r_y = 0x1F;
+        mem.memory[0xFFFC] = mos::CPU::SHY_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0xFF;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0101] == 0x01 && mem.memory[0x4101] == 0x00;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn AHX_indirect_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.r_x = 0x0F;
+        cpu.r_y = 0x03;
+        mem.memory[0x0020] = 0x00;
+        mem.memory[0x0021] = 0x20;
+        mem.memory[0xFFFC] = mos::CPU::AHX_INDIRECT_Y;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x2003] == 0x01;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn TAS_absolute_Y() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xF3;
+        cpu.r_x = 0x3F;
+        mem.memory[0xFFFC] = mos::CPU::TAS_ABSOLUTE_Y;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_stack = cpu.sp == 0x33;
+        let good_memory = mem.memory[0x4000] == 0x01;
+        assert!(good_stack && good_memory && good_cycles);
+    }
+
+    #[test]
+    fn NOP_implied_alias() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x42;
+        mem.memory[0xFFFC] = 0x1A;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFD;
+        let good_result = cpu.r_a == 0x42;
+        assert!(good_pc && good_result && good_cycles);
+    }
+
+    #[test]
+    fn NOP_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = 0x89;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFE;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 0;
+        assert!(good_pc && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn NOP_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = mos::CPU::NOP_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFE;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn NOP_absolute_X_cross_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x01;
+        mem.memory[0xFFFC] = mos::CPU::NOP_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0xFF;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFF;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn JAM_halts_cpu() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::JAM_IMPLIED;
+        let result = cpu.execute(2, &mut mem);
+        let good_error = result == Err(mos::EmuError::Jammed { address: 0x8000, opcode: 0x02 });
+        let good_state = cpu.jammed && cpu.pc == 0x8000;
+        // interrupts can't wake it up, the next run reports the same jam
+        cpu.set_irq_line(true);
+        cpu.set_nmi_line(true);
+        let good_again = cpu.execute(2, &mut mem) == result && cpu.pc == 0x8000;
+        assert!(good_error && good_state && good_again);
+    }
+
+    #[test]
+    fn JAM_cleared_by_reset() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = 0xF2;
+        mem.memory[0xFFFC] = 0x00;
+        mem.memory[0xFFFD] = 0x90;
+        mem.memory[0x9000] = mos::CPU::NOP_IMPLIED;
+        let result = cpu.execute(2, &mut mem);
+        cpu.reset_cpu(&mut mem);
+        let cycles = cpu.execute(2, &mut mem);
+        let good_jam = result == Err(mos::EmuError::Jammed { address: 0x8000, opcode: 0xF2 });
+        assert!(good_jam && !cpu.jammed && cycles == Ok(2) && cpu.pc == 0x9001);
+    }
+
+    #[test]
+    fn JAM_halts_ticking_cpu() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::JAM_IMPLIED;
+        let result = cpu.tick(&mut mem);
+        let good_error = result == Err(mos::EmuError::Jammed { address: 0x8000, opcode: 0x02 });
+        assert!(good_error && cpu.jammed && cpu.at_instruction_boundary());
+    }
+
+    #[test]
+    fn undocumented_opcodes_disabled() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.undocumented_opcodes = false;
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::LAX_ZERO_PAGE;
+        let result = cpu.execute(3, &mut mem);
+        assert_eq!(result, Err(mos::EmuError::IllegalOpcode { address: 0x8000, opcode: 0xA7 }));
+    }
+
+    #[test]
+    fn ADC_immediate_decimal_2A03() {
+        let mut cpu = mos::build_cpu(mos::Variant::Ricoh2A03);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x09;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x0A;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0 && cpu.ps_decimal == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_decimal_2A03() {
+        let mut cpu = mos::build_cpu(mos::Variant::Ricoh2A03);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.ps_carry = 1;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x0F;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ARR_immediate_decimal_2A03() {
+        let mut cpu = mos::build_cpu(mos::Variant::Ricoh2A03);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        cpu.ps_carry = 0;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ARR_IMMEDIATE;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // no BCD fixup on the 2A03, the binary result stands
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x7F;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_overflow == 0 && cpu.ps_negative == 0 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn ADC_immediate_decimal_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x99;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x01;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_decimal_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x00;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x00;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x99;
+        let good_flags = cpu.ps_carry == 0 && cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn SBC_immediate_decimal_invalid_BCD_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x10;
+        cpu.ps_carry = 1;
+        cpu.ps_decimal = 1;
+        mem.memory[0xFFFC] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x0B;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        // the NMOS sequence gives 0x0F here
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xFF;
+        let good_flags = cpu.ps_carry == 1 && cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn JMP_indirect_page_wrap_fixed_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0x10FF] = 0x34;
+        mem.memory[0x1100] = 0x12;
+        mem.memory[0x1000] = 0x56;
+        mem.memory[0xFFFC] = mos::CPU::JMP_INDIRECT;
+        mem.memory[0xFFFD] = 0xFF;
+        mem.memory[0xFFFE] = 0x10;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x1234;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BRA_relative() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::BRA_RELATIVE;
+        mem.memory[0x8001] = 0x10;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8012;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn PHX_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x42;
+        mem.memory[0xFFFC] = mos::CPU::PHX_IMPLIED;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_stack = cpu.sp == 0xFE && mem.memory[0x01FF] == 0x42;
+        assert!(good_stack && good_cycles);
+    }
+
+    #[test]
+    fn PLY_implied() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.sp = 0xFE;
+        mem.memory[0x01FF] = 0x80;
+        mem.memory[0xFFFC] = mos::CPU::PLY_IMPLIED;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_y == 0x80 && cpu.sp == 0xFF;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn STZ_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x01;
+        mem.memory[0x4001] = 0x42;
+        mem.memory[0xFFFC] = mos::CPU::STZ_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4001] == 0x00;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn STZ_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x42;
+        mem.memory[0xFFFC] = mos::CPU::STZ_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 3;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0x00;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn TSB_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x0F;
+        mem.memory[0x0042] = 0xF0;
+        mem.memory[0xFFFC] = mos::CPU::TSB_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0xFF;
+        let good_flags = cpu.ps_zero == 1;
+        assert!(good_memory && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn TRB_absolute() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x0F;
+        mem.memory[0x4000] = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::TRB_ABSOLUTE;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4000] == 0xF0;
+        let good_flags = cpu.ps_zero == 0;
+        assert!(good_memory && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn LDA_zero_page_indirect() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0020] = 0x00;
+        mem.memory[0x0021] = 0x40;
+        mem.memory[0x4000] = 0x84;
+        mem.memory[0xFFFC] = mos::CPU::LDA_ZERO_PAGE_INDIRECT;
+        mem.memory[0xFFFD] = 0x20;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x84;
+        let good_flags = cpu.ps_negative == 1 && cpu.ps_zero == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn STA_zero_page_indirect() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x37;
+        mem.memory[0x00FF] = 0x00;
+        mem.memory[0x0000] = 0x40;
+        mem.memory[0xFFFC] = mos::CPU::STA_ZERO_PAGE_INDIRECT;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4000] == 0x37;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn INC_accumulator() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::INC_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0x00;
+        let good_flags = cpu.ps_zero == 1 && cpu.ps_negative == 0;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn DEC_accumulator() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::DEC_ACCUMULATOR;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_result = cpu.r_a == 0xFF;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 1;
+        assert!(good_result && good_flags && good_cycles);
+    }
+
+    #[test]
+    fn BIT_immediate() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        cpu.ps_negative = 1;
+        cpu.ps_overflow = 1;
+        mem.memory[0xFFFC] = mos::CPU::BIT_IMMEDIATE;
+        mem.memory[0xFFFD] = 0x02;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_zero == 1 && cpu.ps_negative == 1 && cpu.ps_overflow == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn BIT_absolute_X() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x01;
+        cpu.r_x = 0x02;
+        mem.memory[0x4002] = 0xC1;
+        mem.memory[0xFFFC] = mos::CPU::BIT_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 4;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_flags = cpu.ps_zero == 0 && cpu.ps_negative == 1 && cpu.ps_overflow == 1;
+        assert!(good_flags && good_cycles);
+    }
+
+    #[test]
+    fn JMP_absolute_X_indirect() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x02;
+        mem.memory[0x4002] = 0x34;
+        mem.memory[0x4003] = 0x12;
+        mem.memory[0xFFFC] = mos::CPU::JMP_ABSOLUTE_X_INDIRECT;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x1234;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn ASL_absolute_X_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x01;
+        mem.memory[0x4001] = 0x41;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4001] == 0x82;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn ASL_absolute_X_cross_page_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_x = 0x01;
+        mem.memory[0x4100] = 0x41;
+        mem.memory[0xFFFC] = mos::CPU::ASL_ABSOLUTE_X;
+        mem.memory[0xFFFD] = 0xFF;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 7;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x4100] == 0x82;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn NOP_single_cycle_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.r_a = 0x42;
+        mem.memory[0xFFFC] = 0x03;
+        let cycles_init = 1;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFD;
+        let good_result = cpu.r_a == 0x42;
+        assert!(good_pc && good_result && good_cycles);
+    }
+
+    #[test]
+    fn NOP_immediate_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = 0x02;
+        mem.memory[0xFFFD] = 0xFF;
+        let cycles_init = 2;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFE;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn NOP_eight_cycles_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = 0x5C;
+        mem.memory[0xFFFD] = 0x00;
+        mem.memory[0xFFFE] = 0x40;
+        let cycles_init = 8;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0xFFFF;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn RMB_single_cycle_NOP_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::RMB0_ZERO_PAGE;
+        let cycles_init = 1;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0xFF;
+        let good_pc = cpu.pc == 0xFFFD;
+        assert!(good_memory && good_pc && good_cycles);
+    }
+
+    #[test]
+    fn RMB3_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Rockwell65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0xFF;
+        mem.memory[0xFFFC] = mos::CPU::RMB3_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0xF7;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn SMB7_zero_page() {
+        let mut cpu = mos::build_cpu(mos::Variant::Rockwell65C02);
+        let mut mem = mos::build_memory();
+        mem.memory[0x0042] = 0x00;
+        mem.memory[0xFFFC] = mos::CPU::SMB7_ZERO_PAGE;
+        mem.memory[0xFFFD] = 0x42;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_memory = mem.memory[0x0042] == 0x80;
+        assert!(good_memory && good_cycles);
+    }
+
+    #[test]
+    fn BBR2_zero_page_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Rockwell65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x0042] = 0xFB;
+        mem.memory[0x8000] = mos::CPU::BBR2_ZERO_PAGE_RELATIVE;
+        mem.memory[0x8001] = 0x42;
+        mem.memory[0x8002] = 0x10;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8013;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BBR2_zero_page_relative_not_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Rockwell65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x0042] = 0x04;
+        mem.memory[0x8000] = mos::CPU::BBR2_ZERO_PAGE_RELATIVE;
+        mem.memory[0x8001] = 0x42;
+        mem.memory[0x8002] = 0x10;
+        let cycles_init = 5;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8003;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn BBS0_zero_page_relative_taken() {
+        let mut cpu = mos::build_cpu(mos::Variant::Rockwell65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x0042] = 0x01;
+        mem.memory[0x8000] = mos::CPU::BBS0_ZERO_PAGE_RELATIVE;
+        mem.memory[0x8001] = 0x42;
+        mem.memory[0x8002] = 0x10;
+        let cycles_init = 6;
+        let cycles = cpu.execute(cycles_init, &mut mem).unwrap_or_else( |err| {
+            println!("invalid memory and instruction situation {} ...", err);
+            process::exit(1);
+        });
+        let good_cycles = cycles_init == cycles;
+        let good_pc = cpu.pc == 0x8013;
+        assert!(good_pc && good_cycles);
+    }
+
+    #[test]
+    fn WAI_idles_until_interrupt() {
+        let mut cpu = mos::build_cpu(mos::Variant::Wdc65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_interrupt = 1;
+        mem.memory[0x8000] = mos::CPU::WAI_IMPLIED;
+        mem.memory[0x8001] = mos::CPU::INX_IMPLIED;
+        let cycles = cpu.execute(10, &mut mem).unwrap();
+        let good_waiting = cpu.waiting && cpu.pc == 0x8001 && cycles == 10;
+        // with I set the interrupt only wakes the cpu, execution carries on after WAI
+        cpu.set_irq_line(true);
+        let cycles = cpu.execute(2, &mut mem).unwrap();
+        let good_resumed = !cpu.waiting && cpu.r_x == 0x01 && cycles == 2;
+        assert!(good_waiting && good_resumed);
+    }
+
+    #[test]
+    fn WAI_services_interrupt() {
+        let mut cpu = mos::build_cpu(mos::Variant::Wdc65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::WAI_IMPLIED;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x90;
+        cpu.execute(5, &mut mem).unwrap();
+        cpu.set_irq_line(true);
+        let cycles = cpu.execute(7, &mut mem).unwrap();
+        let good_stack = mem.memory[0x01FF] == 0x80 && mem.memory[0x01FE] == 0x01;
+        assert!(good_stack && cpu.pc == 0x9000 && cycles == 7);
+    }
+
+    #[test]
+    fn STP_halts_until_reset() {
+        let mut cpu = mos::build_cpu(mos::Variant::Wdc65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::STP_IMPLIED;
+        mem.memory[0xFFFC] = 0x00;
+        mem.memory[0xFFFD] = 0x90;
+        let result = cpu.execute(10, &mut mem);
+        let good_stop = result == Err(mos::EmuError::Stopped { address: 0x8000 }) && cpu.stopped;
+        cpu.set_nmi_line(true);
+        let good_still = cpu.execute(10, &mut mem) == result;
+        cpu.reset_cpu(&mut mem);
+        assert!(good_stop && good_still && !cpu.stopped && cpu.pc == 0x9000);
+    }
+
+    #[test]
+    fn STP_is_NOP_on_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::STP_IMPLIED;
+        let cycles = cpu.execute(1, &mut mem);
+        assert!(cycles == Ok(1) && !cpu.stopped && cpu.pc == 0x8001);
+    }
+
+    #[test]
+    fn BRK_clears_decimal_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_decimal = 1;
+        mem.memory[0x8000] = mos::CPU::BRK_IMPLIED;
+        let cycles = cpu.execute(7, &mut mem);
+        let good_stack = mem.memory[0x01FD] & 0x08 == 0x08;
+        assert!(cycles == Ok(7) && good_stack && cpu.ps_decimal == 0);
+    }
+
+    #[test]
+    fn BRK_keeps_decimal_NMOS() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.ps_decimal = 1;
+        mem.memory[0x8000] = mos::CPU::BRK_IMPLIED;
+        let cycles = cpu.execute(7, &mut mem);
+        assert!(cycles == Ok(7) && cpu.ps_decimal == 1);
+    }
+
+    #[test]
+    fn undocumented_opcode_differs_per_variant() {
+        let mut nmos = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut cmos = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut mem = mos::build_memory();
+        nmos.pc = 0x8000;
+        cmos.pc = 0x8000;
+        mem.memory[0x8000] = 0x1A;
+        nmos.r_a = 0x41;
+        cmos.r_a = 0x41;
+        nmos.step(&mut mem).unwrap();
+        cmos.step(&mut mem).unwrap();
+        assert!(nmos.r_a == 0x41 && cmos.r_a == 0x42);
+    }
+
+    #[test]
+    fn cycle_accurate_one_bus_access_per_cycle_all_variants() {
+        let variants = [mos::Variant::Ricoh2A03, mos::Variant::Cmos65C02, mos::Variant::Rockwell65C02, mos::Variant::Wdc65C02];
+        for variant in variants {
+            for opcode in 0..=255u8 {
+                let mut cpu = mos::build_cpu(variant);
+                let mut bus = build_log_bus();
+                cpu.cycle_accurate = true;
+                cpu.pc = 0x8000;
+                cpu.sp = 0xF0;
+                cpu.r_y = 0xF0;
+                cpu.ps_decimal = 1;
+                bus.mem.memory[0x8000] = opcode;
+                bus.mem.memory[0x8001] = 0x20;
+                bus.mem.memory[0x8002] = 0x30;
+                match cpu.step(&mut bus) {
+                    Ok(cycles) => assert_eq!(bus.log.len() as i32, cycles, "{:?} opcode {:#04x}", variant, opcode),
+                    Err(mos::EmuError::Jammed { .. }) => continue,
+                    Err(err) => panic!("{:?} opcode {:#04x}: {}", variant, opcode, err),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cycle_accurate_read_modify_write_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        bus.mem.memory[0x8000] = mos::CPU::INC_ZERO_PAGE;
+        bus.mem.memory[0x8001] = 0x42;
+        bus.mem.memory[0x0042] = 0x10;
+        cpu.step(&mut bus).unwrap();
+        // the operand is read twice instead of written twice
+        let expected = vec![("read", 0x8000, 0xE6), ("read", 0x8001, 0x42), ("read", 0x0042, 0x10), ("read", 0x0042, 0x10), ("write", 0x0042, 0x11)];
+        assert_eq!(bus.log, expected);
+    }
+
+    #[test]
+    fn cycle_accurate_page_cross_dummy_read_65C02() {
+        let mut cpu = mos::build_cpu(mos::Variant::Cmos65C02);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = 0x8000;
+        cpu.r_x = 0x11;
+        bus.mem.memory[0x8000] = mos::CPU::LDA_ABSOLUTE_X;
+        bus.mem.memory[0x8001] = 0xF0;
+        bus.mem.memory[0x8002] = 0x40;
+        cpu.step(&mut bus).unwrap();
+        // no read of the unfixed address 0x4001, the last operand byte is read again
+        let good_dummy = bus.log[3] == ("read", 0x8002, 0x40);
+        assert!(good_dummy && bus.log[4].1 == 0x4101 && bus.log.len() == 5);
+    }
+
+    /* 65C816 */
+
+    fn native_65816() -> mos::CPU65816 {
+        let mut cpu = mos::build_cpu_65816();
+        cpu.ps_emulation = 0;
+        cpu.pc = 0x8000;
+        cpu
+    }
+
+    #[test]
+    fn w65c816_init_emulation_mode() {
+        let cpu = mos::build_cpu_65816();
+        assert_eq!(cpu.pc, 0xFFFC);
+        assert_eq!(cpu.sp, 0x01FF);
+        assert!(cpu.ps_emulation == 1 && cpu.ps_memory == 1 && cpu.ps_index == 1);
+    }
+
+    #[test]
+    fn w65c816_reset() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFC] = 0x00;
+        mem.memory[0xFFFD] = 0x80;
+        cpu.d = 0x1234;
+        cpu.pbr = 0x05;
+        cpu.ps_memory = 0;
+        cpu.reset_cpu(&mut mem);
+        assert_eq!(cpu.pc, 0x8000);
+        assert_eq!(cpu.sp, 0x01FD);
+        assert!(cpu.d == 0 && cpu.pbr == 0);
+        assert!(cpu.ps_emulation == 1 && cpu.ps_memory == 1 && cpu.ps_interrupt == 1);
+    }
+
+    #[test]
+    fn w65c816_XCE_native_and_back() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU::CLC_IMPLIED;
+        mem.memory[0x8001] = mos::CPU65816::XCE_IMPLIED;
+        mem.memory[0x8002] = mos::CPU::SEC_IMPLIED;
+        mem.memory[0x8003] = mos::CPU65816::XCE_IMPLIED;
+        cpu.execute(4, &mut mem).unwrap();
+        assert!(cpu.ps_emulation == 0 && cpu.ps_carry == 1);
+        cpu.ps_memory = 0;
+        cpu.ps_index = 0;
+        cpu.sp = 0x1FF0;
+        cpu.r_x = 0x1234;
+        cpu.execute(4, &mut mem).unwrap();
+        assert!(cpu.ps_emulation == 1 && cpu.ps_carry == 0);
+        assert!(cpu.ps_memory == 1 && cpu.ps_index == 1);
+        assert_eq!(cpu.r_x, 0x0034);
+        assert_eq!(cpu.sp, 0x01F0);
+    }
+
+    #[test]
+    fn w65c816_REP_sixteen_bit_immediates() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        let program = [0xC2, 0x30, 0xA9, 0x34, 0x12, 0xA2, 0x78, 0x56, 0xA0, 0x00, 0x80];
+        mem.memory[0x8000..0x8000 + program.len()].copy_from_slice(&program);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert!(cpu.ps_memory == 0 && cpu.ps_index == 0);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert!(cpu.r_a == 0x1234 && cpu.r_x == 0x5678 && cpu.r_y == 0x8000);
+        assert!(cpu.ps_negative == 1 && cpu.ps_zero == 0);
+        assert_eq!(cpu.pc, 0x800B);
+    }
+
+    #[test]
+    fn w65c816_REP_ignored_in_emulation_mode() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        mem.memory[0x8000] = mos::CPU65816::REP_IMMEDIATE;
+        mem.memory[0x8001] = 0x31;
+        cpu.ps_carry = 1;
+        cpu.step(&mut mem).unwrap();
+        assert!(cpu.ps_memory == 1 && cpu.ps_index == 1 && cpu.ps_carry == 0);
+    }
+
+    #[test]
+    fn w65c816_SEP_clears_index_high_bytes() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.ps_index = 0;
+        cpu.r_x = 0x1234;
+        cpu.r_y = 0xABCD;
+        cpu.r_a = 0xFFFF;
+        mem.memory[0x8000] = mos::CPU65816::SEP_IMMEDIATE;
+        mem.memory[0x8001] = 0x30;
+        cpu.step(&mut mem).unwrap();
+        assert!(cpu.r_x == 0x0034 && cpu.r_y == 0x00CD);
+        // B is kept when the accumulator narrows
+        assert_eq!(cpu.r_a, 0xFFFF);
+    }
+
+    #[test]
+    fn w65c816_ADC_sixteen_bit() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.ps_memory = 0;
+        cpu.r_a = 0x12FF;
+        mem.memory[0x8000] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0x8001] = 0x01;
+        mem.memory[0x8002] = 0x00;
+        mem.memory[0x8003] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0x8004] = 0x00;
+        mem.memory[0x8005] = 0xED;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert!(cpu.r_a == 0x1300 && cpu.ps_carry == 0);
+        cpu.step(&mut mem).unwrap();
+        assert!(cpu.r_a == 0x0000 && cpu.ps_carry == 1 && cpu.ps_zero == 1);
+    }
+
+    #[test]
+    fn w65c816_ADC_SBC_sixteen_bit_decimal() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.ps_memory = 0;
+        cpu.ps_decimal = 1;
+        cpu.r_a = 0x1999;
+        mem.memory[0x8000] = mos::CPU::ADC_IMMEDIATE;
+        mem.memory[0x8001] = 0x01;
+        mem.memory[0x8002] = 0x00;
+        mem.memory[0x8003] = mos::CPU::SEC_IMPLIED;
+        mem.memory[0x8004] = mos::CPU::SBC_IMMEDIATE;
+        mem.memory[0x8005] = 0x01;
+        mem.memory[0x8006] = 0x00;
+        cpu.step(&mut mem).unwrap();
+        assert!(cpu.r_a == 0x2000 && cpu.ps_carry == 0);
+        cpu.step(&mut mem).unwrap();
+        cpu.step(&mut mem).unwrap();
+        assert!(cpu.r_a == 0x1999 && cpu.ps_carry == 1);
+    }
+
+    #[test]
+    fn w65c816_direct_page_offset_cycle() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.d = 0x0101;
+        mem.memory[0x8000] = mos::CPU::LDA_ZERO_PAGE;
+        mem.memory[0x8001] = 0x10;
+        mem.memory[0x8002] = mos::CPU::LDA_ZERO_PAGE;
+        mem.memory[0x8003] = 0x10;
+        mem.memory[0x0111] = 0x42;
+        mem.memory[0x0210] = 0x24;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 4);
+        assert_eq!(cpu.r_a, 0x42);
+        cpu.d = 0x0200;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert_eq!(cpu.r_a, 0x24);
+    }
+
+    #[test]
+    fn w65c816_LDA_absolute_long() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(4);
+        mem.memory[0x8000] = mos::CPU65816::LDA_ABSOLUTE_LONG;
+        mem.memory[0x8001] = 0x34;
+        mem.memory[0x8002] = 0x12;
+        mem.memory[0x8003] = 0x02;
+        mem.memory[0x021234] = 0x77;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 5);
+        assert_eq!(cpu.r_a, 0x77);
+    }
+
+    #[test]
+    fn w65c816_LDA_indirect_long_Y() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(4);
+        cpu.ps_memory = 0;
+        cpu.r_y = 0x0010;
+        mem.memory[0x8000] = mos::CPU65816::LDA_ZERO_PAGE_INDIRECT_LONG_Y;
+        mem.memory[0x8001] = 0x20;
+        mem.memory[0x0020] = 0xF8;
+        mem.memory[0x0021] = 0xFF;
+        mem.memory[0x0022] = 0x02;
+        // the indexed address carries into bank 3
+        mem.memory[0x030008] = 0xCD;
+        mem.memory[0x030009] = 0xAB;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 7);
+        assert_eq!(cpu.r_a, 0xABCD);
+    }
+
+    #[test]
+    fn w65c816_data_bank_register() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(4);
+        cpu.dbr = 0x02;
+        mem.memory[0x8000] = mos::CPU::STA_ABSOLUTE;
+        mem.memory[0x8001] = 0x34;
+        mem.memory[0x8002] = 0x12;
+        cpu.r_a = 0x99;
+        cpu.step(&mut mem).unwrap();
+        assert!(mem.memory[0x021234] == 0x99 && mem.memory[0x1234] == 0x00);
+    }
+
+    #[test]
+    fn w65c802_bank_lines_ignored() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        mem.memory[0x8000] = mos::CPU65816::LDA_ABSOLUTE_LONG;
+        mem.memory[0x8001] = 0x34;
+        mem.memory[0x8002] = 0x12;
+        mem.memory[0x8003] = 0x05;
+        mem.memory[0x1234] = 0x55;
+        cpu.step(&mut mem).unwrap();
+        assert_eq!(cpu.r_a, 0x55);
+    }
+
+    #[test]
+    fn w65c816_JSL_RTL() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(2);
+        cpu.sp = 0x01FF;
+        mem.memory[0x8000] = mos::CPU65816::JSL_ABSOLUTE_LONG;
+        mem.memory[0x8001] = 0x00;
+        mem.memory[0x8002] = 0x90;
+        mem.memory[0x8003] = 0x01;
+        mem.memory[0x019000] = mos::CPU65816::RTL_IMPLIED;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 8);
+        assert!(cpu.pbr == 0x01 && cpu.pc == 0x9000);
+        assert_eq!(&mem.memory[0x01FD..0x0200], &[0x03, 0x80, 0x00]);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 6);
+        assert!(cpu.pbr == 0x00 && cpu.pc == 0x8004 && cpu.sp == 0x01FF);
+    }
+
+    #[test]
+    fn w65c816_MVN() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(4);
+        cpu.ps_memory = 0;
+        cpu.ps_index = 0;
+        cpu.r_a = 0x0002;
+        cpu.r_x = 0x1000;
+        cpu.r_y = 0x2000;
+        mem.memory[0x8000] = mos::CPU65816::MVN_BLOCK_MOVE;
+        mem.memory[0x8001] = 0x01;
+        mem.memory[0x8002] = 0x02;
+        mem.memory[0x021000..0x021003].copy_from_slice(&[0xAA, 0xBB, 0xCC]);
+        for _ in 0..3 {
+            assert_eq!(cpu.step(&mut mem).unwrap(), 7);
+        }
+        assert_eq!(&mem.memory[0x012000..0x012003], &[0xAA, 0xBB, 0xCC]);
+        assert!(cpu.r_a == 0xFFFF && cpu.r_x == 0x1003 && cpu.r_y == 0x2003);
+        assert!(cpu.dbr == 0x01 && cpu.pc == 0x8003);
+    }
+
+    #[test]
+    fn w65c816_MVP() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(4);
+        cpu.ps_memory = 0;
+        cpu.ps_index = 0;
+        cpu.r_a = 0x0002;
+        cpu.r_x = 0x1002;
+        cpu.r_y = 0x2002;
+        mem.memory[0x8000] = mos::CPU65816::MVP_BLOCK_MOVE;
+        mem.memory[0x8001] = 0x01;
+        mem.memory[0x8002] = 0x02;
+        mem.memory[0x021000..0x021003].copy_from_slice(&[0xAA, 0xBB, 0xCC]);
+        cpu.execute(21, &mut mem).unwrap();
+        assert_eq!(&mem.memory[0x012000..0x012003], &[0xAA, 0xBB, 0xCC]);
+        assert!(cpu.r_a == 0xFFFF && cpu.r_x == 0x0FFF && cpu.r_y == 0x1FFF);
+        assert_eq!(cpu.pc, 0x8003);
+    }
+
+    #[test]
+    fn w65c816_BRK_native() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_long_memory(2);
+        cpu.pbr = 0x01;
+        cpu.pc = 0x9000;
+        cpu.sp = 0x01FF;
+        cpu.ps_decimal = 1;
+        mem.memory[0x019000] = mos::CPU::BRK_IMPLIED;
+        mem.memory[0xFFE6] = 0x00;
+        mem.memory[0xFFE7] = 0xA0;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 8);
+        assert!(cpu.pbr == 0x00 && cpu.pc == 0xA000);
+        assert!(cpu.ps_interrupt == 1 && cpu.ps_decimal == 0);
+        assert_eq!(&mem.memory[0x01FC..0x0200], &[0x38, 0x02, 0x90, 0x01]);
+        assert_eq!(cpu.sp, 0x01FB);
+    }
+
+    #[test]
+    fn w65c816_BRK_emulation() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x9000;
+        mem.memory[0x9000] = mos::CPU::BRK_IMPLIED;
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0xA0;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 7);
+        assert_eq!(cpu.pc, 0xA000);
+        // no program bank, B set in the pushed status
+        assert_eq!(&mem.memory[0x01FD..0x0200], &[0x30, 0x02, 0x90]);
+    }
+
+    #[test]
+    fn w65c816_COP_native_and_RTI() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.sp = 0x01FF;
+        mem.memory[0x8000] = mos::CPU65816::COP_IMMEDIATE;
+        mem.memory[0x8001] = 0x42;
+        mem.memory[0xFFE4] = 0x00;
+        mem.memory[0xFFE5] = 0xB0;
+        mem.memory[0xB000] = mos::CPU::RTI_IMPLIED;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 8);
+        assert_eq!(cpu.pc, 0xB000);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 7);
+        assert!(cpu.pc == 0x8002 && cpu.sp == 0x01FF && cpu.ps_interrupt == 0);
+    }
+
+    #[test]
+    fn w65c816_IRQ_native_vector() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.ps_interrupt = 0;
+        mem.memory[0xFFEE] = 0x00;
+        mem.memory[0xFFEF] = 0xC0;
+        cpu.set_irq_line(true);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 8);
+        assert!(cpu.pc == 0xC000 && cpu.ps_interrupt == 1);
+    }
+
+    #[test]
+    fn w65c816_XBA() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.r_a = 0x8012;
+        mem.memory[0x8000] = mos::CPU65816::XBA_IMPLIED;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 3);
+        assert_eq!(cpu.r_a, 0x1280);
+        assert!(cpu.ps_negative == 1 && cpu.ps_zero == 0);
+    }
+
+    #[test]
+    fn w65c816_TCD_TDC() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.r_a = 0x1234;
+        mem.memory[0x8000] = mos::CPU65816::TCD_IMPLIED;
+        mem.memory[0x8001] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0x8002] = 0x00;
+        mem.memory[0x8003] = mos::CPU65816::TDC_IMPLIED;
+        cpu.execute(6, &mut mem).unwrap();
+        assert_eq!(cpu.d, 0x1234);
+        // TDC moves all 16 bits even with an 8-bit accumulator
+        assert_eq!(cpu.r_a, 0x1234);
+    }
+
+    #[test]
+    fn w65c816_TXS_emulation_keeps_page_one() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.r_x = 0x40;
+        mem.memory[0x8000] = mos::CPU::TXS_IMPLIED;
+        cpu.step(&mut mem).unwrap();
+        assert_eq!(cpu.sp, 0x0140);
+    }
+
+    #[test]
+    fn w65c816_emulation_stack_wraps_in_page_one() {
+        let mut cpu = mos::build_cpu_65816();
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x8000;
+        cpu.sp = 0x0100;
+        cpu.r_a = 0x42;
+        mem.memory[0x8000] = mos::CPU::PHA_IMPLIED;
+        cpu.step(&mut mem).unwrap();
+        assert!(mem.memory[0x0100] == 0x42 && cpu.sp == 0x01FF);
+    }
+
+    #[test]
+    fn w65c816_PHA_PLA_sixteen_bit() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.ps_memory = 0;
+        cpu.sp = 0x1000;
+        cpu.r_a = 0xBEEF;
+        mem.memory[0x8000] = mos::CPU::PHA_IMPLIED;
+        mem.memory[0x8001] = mos::CPU::LDA_IMMEDIATE;
+        mem.memory[0x8002] = 0x00;
+        mem.memory[0x8003] = 0x00;
+        mem.memory[0x8004] = mos::CPU::PLA_IMPLIED;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 4);
+        assert_eq!(cpu.sp, 0x0FFE);
+        cpu.step(&mut mem).unwrap();
+        assert_eq!(cpu.step(&mut mem).unwrap(), 5);
+        assert!(cpu.r_a == 0xBEEF && cpu.sp == 0x1000 && cpu.ps_negative == 1);
+    }
+
+    #[test]
+    fn w65c816_PEA_PEI_PER() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.sp = 0x01FF;
+        mem.memory[0x8000] = mos::CPU65816::PEA_ABSOLUTE;
+        mem.memory[0x8001] = 0x34;
+        mem.memory[0x8002] = 0x12;
+        mem.memory[0x8003] = mos::CPU65816::PEI_ZERO_PAGE_INDIRECT;
+        mem.memory[0x8004] = 0x10;
+        mem.memory[0x8005] = mos::CPU65816::PER_RELATIVE_LONG;
+        mem.memory[0x8006] = 0x10;
+        mem.memory[0x8007] = 0x00;
+        mem.memory[0x0010] = 0x78;
+        mem.memory[0x0011] = 0x56;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 5);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 6);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 6);
+        assert_eq!(&mem.memory[0x01FA..0x0200], &[0x18, 0x80, 0x78, 0x56, 0x34, 0x12]);
+    }
+
+    #[test]
+    fn w65c816_stack_relative() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        cpu.sp = 0x01F0;
+        cpu.r_y = 0x10;
+        mem.memory[0x8000] = mos::CPU65816::LDA_STACK_RELATIVE;
+        mem.memory[0x8001] = 0x03;
+        mem.memory[0x8002] = mos::CPU65816::LDA_STACK_RELATIVE_INDIRECT_Y;
+        mem.memory[0x8003] = 0x03;
+        mem.memory[0x01F3] = 0x00;
+        mem.memory[0x01F4] = 0x20;
+        mem.memory[0x2010] = 0x42;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 4);
+        assert_eq!(cpu.r_a, 0x00);
+        assert_eq!(cpu.step(&mut mem).unwrap(), 7);
+        assert_eq!(cpu.r_a, 0x42);
+    }
+
+    #[test]
+    fn w65c816_BRL() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        mem.memory[0x8000] = mos::CPU65816::BRL_RELATIVE_LONG;
+        mem.memory[0x8001] = 0x00;
+        mem.memory[0x8002] = 0xF0;
+        assert_eq!(cpu.step(&mut mem).unwrap(), 4);
+        assert_eq!(cpu.pc, 0x7003);
+    }
+
+    #[test]
+    fn w65c816_STP_WAI() {
+        let mut cpu = native_65816();
+        let mut mem = mos::build_memory();
+        mem.memory[0x8000] = mos::CPU::WAI_IMPLIED;
+        mem.memory[0x8001] = mos::CPU::STP_IMPLIED;
+        cpu.step(&mut mem).unwrap();
+        assert!(cpu.waiting);
+        cpu.step(&mut mem).unwrap();
+        assert_eq!(cpu.pc, 0x8001);
+        cpu.set_irq_line(true);
+        cpu.ps_interrupt = 1;
+        // a masked IRQ still ends the wait
+        cpu.step(&mut mem).unwrap();
+        cpu.set_irq_line(false);
+        assert!(!cpu.waiting && cpu.stopped);
+        assert_eq!(cpu.step(&mut mem), Err(mos::EmuError::Stopped { address: 0x8001 }));
+    }
+
+    #[test]
+    fn w65c816_emulation_runs_6502_program() {
+        let mut nmos = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut wdc = mos::build_cpu_65816();
+        let mut nmos_mem = mos::build_memory();
+        let mut wdc_mem = mos::build_memory();
+        let program = [0xA9, 0x00, 0xA2, 0x05, 0x18, 0x69, 0x03, 0xCA, 0xD0, 0xFA, 0x8D, 0x00, 0x02, 0x48, 0x68, 0xA8];
+        nmos_mem.memory[0x80F4..0x80F4 + program.len()].copy_from_slice(&program);
+        wdc_mem.memory[0x80F4..0x80F4 + program.len()].copy_from_slice(&program);
+        nmos.pc = 0x80F4;
+        wdc.pc = 0x80F4;
+        for _ in 0..26 {
+            assert_eq!(nmos.step(&mut nmos_mem).unwrap(), wdc.step(&mut wdc_mem).unwrap());
+        }
+        assert!(wdc.r_a == 15 && wdc.r_x == 0 && wdc.r_y == 15);
+        assert_eq!(wdc_mem.memory[0x0200], 15);
+        assert_eq!(nmos.pc, wdc.pc);
+    }
+
+    /* ASSEMBLER */
+
+    #[test]
+    fn asm_basic_program() {
+        let source = "
+            .org $8000
+    start:  LDA #$42
+            STA $0200
+            RTS
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.segments.len(), 1);
+        assert_eq!(assembly.segments[0].address, 0x8000);
+        assert_eq!(assembly.segments[0].bytes, vec![0xA9, 0x42, 0x8D, 0x00, 0x02, 0x60]);
+        assert_eq!(assembly.symbol("start"), Some(0x8000));
+    }
+
+    #[test]
+    fn asm_addressing_modes() {
+        let source = "
+            * = $1000
+            NOP
+            ASL
+            ASL A
+            LDA #1
+            LDA $10
+            LDA $10,X
+            LDX $10,Y
+            LDA $1234
+            LDA $1234,X
+            LDA $1234,Y
+            LDA ($10,X)
+            LDA ($10),Y
+            JMP ($1234)
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        let expected = vec![
+            0xEA, 0x0A, 0x0A, 0xA9, 0x01, 0xA5, 0x10, 0xB5, 0x10, 0xB6, 0x10, 0xAD, 0x34, 0x12,
+            0xBD, 0x34, 0x12, 0xB9, 0x34, 0x12, 0xA1, 0x10, 0xB1, 0x10, 0x6C, 0x34, 0x12,
+        ];
+        assert_eq!(assembly.segments[0].bytes, expected);
+    }
+
+    #[test]
+    fn asm_65C02_modes() {
+        let source = "
+            LDA ($10)
+            JMP ($1234,X)
+            STZ $20
+            BRA *
+            BBR3 $12, *
+            INC
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        let expected = vec![0xB2, 0x10, 0x7C, 0x34, 0x12, 0x64, 0x20, 0x80, 0xFE, 0x3F, 0x12, 0xFD, 0x1A];
+        assert_eq!(assembly.segments[0].bytes, expected);
+    }
+
+    #[test]
+    fn asm_branches_forward_and_backward() {
+        let source = "
+            .org $0600
+    loop:   DEX
+            BNE loop
+            BEQ done
+            NOP
+    done:   RTS
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.segments[0].bytes, vec![0xCA, 0xD0, 0xFD, 0xF0, 0x01, 0xEA, 0x60]);
+        assert_eq!(assembly.symbol("done"), Some(0x0606));
+    }
+
+    #[test]
+    fn asm_branch_out_of_range() {
+        let source = "
+    start:  BNE far
+            .org start + 200
+    far:    RTS
+        ";
+        let err = mos::asm::assemble(source).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("out of range"));
+    }
+
+    #[test]
+    fn asm_forward_reference_assembles_absolute() {
+        let source = "
+            LDA later
+            LDA early
+            RTS
+    early = $20
+    later = $30
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        // `later` is unknown in pass 1, so it keeps the absolute form
+        assert_eq!(assembly.segments[0].bytes, vec![0xAD, 0x30, 0x00, 0xAD, 0x20, 0x00, 0x60]);
+    }
+
+    #[test]
+    fn asm_constant_used_before_its_definition() {
+        let source = "
+            LDA foo
+    foo = bar + 1
+    bar:    NOP
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.symbol("foo"), Some(0x0004));
+        assert_eq!(assembly.segments[0].bytes, vec![0xAD, 0x04, 0x00, 0xEA]);
+
+        let undefined = mos::asm::assemble("    LDA foo\nfoo = bar\n    NOP").unwrap_err();
+        assert_eq!((undefined.line, undefined.message.as_str()), (2, "undefined symbol bar"));
+        let circular = mos::asm::assemble("    LDA foo\nfoo = foo + 1").unwrap_err();
+        assert_eq!((circular.line, circular.message.as_str()), (2, "undefined symbol foo"));
+    }
+
+    #[test]
+    fn asm_constants_pick_zero_page() {
+        let source = "
+    pointer = $FB
+            LDA (pointer),Y
+            STA pointer+1
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.segments[0].bytes, vec![0xB1, 0xFB, 0x85, 0xFC]);
+        assert_eq!(assembly.symbol("pointer"), Some(0xFB));
+    }
+
+    #[test]
+    fn asm_low_high_byte_and_expressions() {
+        let source = "
+            * = $C000
+    table:  LDX #<table
+            LDY #>table
+            .byte <(table + $1FF), >table + 1, 2 * 3 + 1, %1010, 'A', -1
+            .word table, * + 2, $10 << 4 | 1
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        let expected = vec![
+            0xA2, 0x00, 0xA0, 0xC0, 0xFF, 0xC1, 0x07, 0x0A, 0x41, 0xFF, 0x00, 0xC0, 0x0C, 0xC0, 0x01, 0x01,
+        ];
+        assert_eq!(assembly.segments[0].bytes, expected);
+    }
+
+    #[test]
+    fn asm_text_and_byte_strings() {
+        let source = "
+            .text \"HI; there\"
+            .byte \"A,B\", 0
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.segments[0].bytes, b"HI; thereA,B\0".to_vec());
+    }
+
+    #[test]
+    fn asm_local_labels() {
+        let source = "
+    first:  LDX #3
+    @loop:  DEX
+            BNE @loop
+    second: LDY #3
+    @loop:  DEY
+            BNE @loop
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.symbol("first@loop"), Some(0x0002));
+        assert_eq!(assembly.symbol("second@loop"), Some(0x0007));
+        assert_eq!(assembly.segments[0].bytes[3..5], [0xD0, 0xFD]);
+        assert_eq!(assembly.segments[0].bytes[8..10], [0xD0, 0xFD]);
+    }
+
+    #[test]
+    fn asm_labels_without_colon_and_case() {
+        let source = "
+start   lda #$01
+        bne start
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.segments[0].bytes, vec![0xA9, 0x01, 0xD0, 0xFC]);
+    }
+
+    #[test]
+    fn asm_org_segments_and_image() {
+        let source = "
+            .org $8000
+            NOP
+            .org $8004
+            RTS
+            .org $FFFC
+            .word $8000
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assert_eq!(assembly.segments.len(), 3);
+        assert_eq!(assembly.origin(), 0x8000);
+        let image = assembly.image();
+        assert_eq!(image.len(), 0x7FFE);
+        assert_eq!(image[0..5], [0xEA, 0x00, 0x00, 0x00, 0x60]);
+        assert_eq!(image[0x7FFC..], [0x00, 0x80]);
+    }
+
+    #[test]
+    fn asm_errors() {
+        let undefined = mos::asm::assemble("    LDA nowhere").unwrap_err();
+        assert_eq!(undefined.message, "undefined symbol nowhere");
+        let mnemonic = mos::asm::assemble("    LDQ #1").unwrap_err();
+        assert_eq!(mnemonic.message, "unknown instruction LDQ");
+        let mode = mos::asm::assemble("    STA #1").unwrap_err();
+        assert_eq!(mode.message, "STA does not support this addressing mode");
+        let twice = mos::asm::assemble("a: NOP\na: NOP").unwrap_err();
+        assert_eq!((twice.line, twice.message.as_str()), (2, "symbol a defined twice"));
+        assert_eq!(twice.to_string(), "<source>:2: symbol a defined twice");
+    }
+
+    #[test]
+    fn asm_include_and_symbol_file() {
+        let directory = std::env::temp_dir().join(format!("rust6502_asm_include_{}", process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("defs.s"), "screen = $0400\n").unwrap();
+        std::fs::write(directory.join("main.s"), ".include \"defs.s\"\n* = $0800\nmain: STA screen\n").unwrap();
+        let assembly = mos::asm::assemble_file(directory.join("main.s").to_str().unwrap()).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(assembly.segments[0].bytes, vec![0x8D, 0x00, 0x04]);
+        assert_eq!(assembly.symbol_file(), "screen = $0400\nmain = $0800\n");
+    }
+
+    #[test]
+    fn asm_program_runs() {
+        let source = "
+            * = $0600
+            LDX #5
+            LDA #0
+            CLC
+    @add:   ADC #3
+            DEX
+            BNE @add
+            STA result
+            BRK
+    result = $0200
+        ";
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        let assembly = mos::asm::assemble(source).unwrap();
+        assembly.load(&mut mem);
+        cpu.pc = 0x0600;
+        while mem.memory[cpu.pc as usize] != mos::CPU::BRK_IMPLIED {
+            cpu.step(&mut mem).unwrap();
+        }
+        assert_eq!(mem.memory[0x0200], 15);
+    }
+
+    /* DISASSEMBLER */
+
+    #[test]
+    fn opcode_table_lookup() {
+        let lda = mos::opcode_table::opcode_info(mos::Variant::Nmos6502, mos::CPU::LDA_ABSOLUTE_X);
+        assert_eq!(lda.mnemonic, "LDA");
+        assert_eq!(lda.mode, mos::opcode_table::AddressingMode::AbsoluteX);
+        assert!(lda.size() == 3 && lda.cycles == 4 && lda.documented);
+        let jmp = mos::opcode_table::opcode_info(mos::Variant::Cmos65C02, mos::CPU::JMP_INDIRECT);
+        assert_eq!(jmp.cycles, 6);
+    }
+
+    #[test]
+    fn opcode_table_variants() {
+        let nmos = mos::opcode_table::opcode_info(mos::Variant::Nmos6502, 0x07);
+        let cmos = mos::opcode_table::opcode_info(mos::Variant::Cmos65C02, 0x07);
+        let rockwell = mos::opcode_table::opcode_info(mos::Variant::Rockwell65C02, 0x07);
+        assert!(nmos.mnemonic == "SLO" && !nmos.documented);
+        assert!(cmos.mnemonic == "NOP" && cmos.size() == 1 && !cmos.documented);
+        assert!(rockwell.mnemonic == "RMB0" && rockwell.documented);
+        let rockwell_wai = mos::opcode_table::opcode_info(mos::Variant::Rockwell65C02, mos::CPU::WAI_IMPLIED);
+        let wdc_wai = mos::opcode_table::opcode_info(mos::Variant::Wdc65C02, mos::CPU::WAI_IMPLIED);
+        assert!(rockwell_wai.mnemonic == "NOP" && wdc_wai.mnemonic == "WAI");
+    }
+
+    #[test]
+    fn disasm_addressing_modes() {
+        let mut mem = mos::build_memory();
+        let program = [
+            0xEA, 0x0A, 0xA9, 0x42, 0xA5, 0x10, 0xB5, 0x10, 0xB6, 0x10, 0xAD, 0x34, 0x12, 0xBD, 0x34, 0x12,
+            0xB9, 0x34, 0x12, 0xA1, 0x10, 0xB1, 0x10, 0x6C, 0x34, 0x12,
+        ];
+        mem.memory[0x8000..0x8000 + program.len()].copy_from_slice(&program);
+        let disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        let texts: Vec<String> = disassembler.disassemble_range(&mem, 0x8000, 0x8019).iter().map(|i| i.text()).collect();
+        let expected = vec![
+            "NOP", "ASL A", "LDA #$42", "LDA $10", "LDA $10,X", "LDX $10,Y", "LDA $1234", "LDA $1234,X",
+            "LDA $1234,Y", "LDA ($10,X)", "LDA ($10),Y", "JMP ($1234)",
+        ];
+        assert_eq!(texts, expected);
+    }
+
+    #[test]
+    fn disasm_branch_targets() {
+        let mut mem = mos::build_memory();
+        mem.memory[0x8000] = mos::CPU::BNE_RELATIVE;
+        mem.memory[0x8001] = 0xFE;
+        mem.memory[0x8002] = mos::CPU::BEQ_RELATIVE;
+        mem.memory[0x8003] = 0x10;
+        let disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        let back = disassembler.disassemble(&mem, 0x8000);
+        let forward = disassembler.disassemble(&mem, 0x8002);
+        assert!(back.text() == "BNE $8000" && back.target == Some(0x8000));
+        assert!(forward.text() == "BEQ $8014" && forward.next() == 0x8004);
+    }
+
+    #[test]
+    fn disasm_65C02_and_bit_branches() {
+        let mut mem = mos::build_memory();
+        let program = [0xB2, 0x10, 0x7C, 0x34, 0x12, 0x3F, 0x12, 0xFD, 0x80, 0x00];
+        mem.memory[0x0400..0x0400 + program.len()].copy_from_slice(&program);
+        let disassembler = mos::disasm::build_disassembler(mos::Variant::Wdc65C02);
+        let texts: Vec<String> = disassembler.disassemble_range(&mem, 0x0400, 0x0409).iter().map(|i| i.text()).collect();
+        assert_eq!(texts, vec!["LDA ($10)", "JMP ($1234,X)", "BBR3 $12,$0405", "BRA $040A"]);
+    }
+
+    #[test]
+    fn disasm_flags_illegal_opcodes_by_variant() {
+        let mut mem = mos::build_memory();
+        mem.memory[0x0000] = 0xA7;
+        mem.memory[0x0001] = 0x10;
+        let nmos = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        let rockwell = mos::disasm::build_disassembler(mos::Variant::Rockwell65C02);
+        let cmos = mos::disasm::build_disassembler(mos::Variant::Cmos65C02);
+        assert_eq!(nmos.disassemble(&mem, 0).text(), "*LAX $10");
+        assert_eq!(rockwell.disassemble(&mem, 0).text(), "SMB2 $10");
+        let nop = cmos.disassemble(&mem, 0);
+        assert!(nop.illegal() && nop.text() == "*NOP" && nop.bytes.len() == 1);
+    }
+
+    #[test]
+    fn disasm_symbol_substitution() {
+        let mut mem = mos::build_memory();
+        let source = "
+            * = $C000
+    screen = $0400
+    ptr = $FB
+    main:   LDA (ptr),Y
+            STA screen,X
+    @loop:  JMP @loop
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assembly.load(&mut mem);
+        let mut disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        disassembler.load_symbols(&assembly.symbol_file());
+        let listing = disassembler.listing(&mem, 0xC000, 0xC007);
+        let expected = "main:\nC000  B1 FB     LDA (ptr),Y\nC002  9D 00 04  STA screen,X\nmain@loop:\nC005  4C 05 C0  JMP main@loop\n";
+        assert_eq!(listing, expected);
+    }
+
+    #[test]
+    fn disasm_load_symbols_format() {
+        let mut disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        disassembler.load_symbols("; comment\nreset = $E000\nvalue = 16\nbogus line\nalias = $E000\n");
+        assert_eq!(disassembler.symbols.get(&0xE000).map(|s| s.as_str()), Some("reset"));
+        assert_eq!(disassembler.symbols.get(&16).map(|s| s.as_str()), Some("value"));
+        assert_eq!(disassembler.symbols.len(), 2);
+    }
+
+    #[test]
+    fn disasm_range_end_of_memory() {
+        let mut mem = mos::build_memory();
+        mem.memory[0xFFFE] = mos::CPU::LDA_ABSOLUTE;
+        mem.memory[0xFFFF] = 0x34;
+        mem.memory[0x0000] = 0x12;
+        let disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        let instructions = disassembler.disassemble_range(&mem, 0xFFFE, 0xFFFF);
+        // the operand wraps around to 0x0000 and the range stops at the top
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].text(), "LDA $1234");
+    }
+
+    #[test]
+    fn disasm_assembler_round_trip() {
+        let mut mem = mos::build_memory();
+        let source = "
+            * = $0600
+            LDX #$08
+            DEC $0200,X
+            ROR A
+            BIT $44
+            SBC ($20),Y
+            JSR $FFD2
+            RTI
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        assembly.load(&mut mem);
+        let disassembler = mos::disasm::build_disassembler(mos::Variant::Nmos6502);
+        let lines: Vec<String> = disassembler.disassemble_range(&mem, 0x0600, 0x060D).iter().map(|i| format!("            {}", i.text())).collect();
+        let rebuilt = mos::asm::assemble(&format!("* = $0600\n{}", lines.join("\n"))).unwrap();
+        assert_eq!(rebuilt.segments, assembly.segments);
+    }
+
+    /* OPCODE TABLE */
+
+    static ALL_VARIANTS: [mos::Variant; 5] = [
+        mos::Variant::Nmos6502, mos::Variant::Ricoh2A03, mos::Variant::Cmos65C02, mos::Variant::Rockwell65C02, mos::Variant::Wdc65C02,
+    ];
+
+    // runs one opcode at `address` with every flag clear and then set, and the zero page
+    // operand 0x00 and then 0xFF, returning the fewest and most cycles taken with the pc after each
+    fn run_opcode(variant: mos::Variant, opcode: u8, address: u16, index: u8) -> Option<((i32, u16), (i32, u16))> {
+        let mut fewest: (i32, u16) = (i32::MAX, 0);
+        let mut most: (i32, u16) = (i32::MIN, 0);
+        for flags in [0, 1] {
+            for zero_page in [0x00, 0xFF] {
+                let mut cpu = mos::build_cpu(variant);
+                let mut mem = mos::build_memory();
+                cpu.pc = address;
+                cpu.sp = 0xFD;
+                cpu.r_x = index;
+                cpu.r_y = index;
+                cpu.ps_carry = flags;
+                cpu.ps_zero = flags;
+                cpu.ps_negative = flags;
+                cpu.ps_overflow = flags;
+                mem.memory[address as usize] = opcode;
+                mem.memory[address as usize + 1] = 0x20;
+                mem.memory[address as usize + 2] = 0x30;
+                mem.memory[0x0020] = if index == 0 { zero_page } else { 0x20 };
+                mem.memory[0x0021] = 0x30;
+                let cycles = cpu.step(&mut mem).ok()?;
+                if cycles < fewest.0 {
+                    fewest = (cycles, cpu.pc);
+                }
+                if cycles > most.0 {
+                    most = (cycles, cpu.pc);
+                }
+            }
+        }
+        Some((fewest, most))
+    }
+
+    #[test]
+    fn opcode_table_base_cycles_and_size_match_executor() {
+        for variant in ALL_VARIANTS {
+            for opcode in 0..=255u8 {
+                let info = mos::opcode_table::opcode_info(variant, opcode);
+                let ((cycles, pc), _) = match run_opcode(variant, opcode, 0x8000, 0) {
+                    Some(result) => result,
+                    None => {
+                        assert_eq!(info.mnemonic, "JAM", "{:?} opcode {:#04x}", variant, opcode);
+                        continue;
+                    }
+                };
+                assert_eq!(cycles, info.cycles as i32, "{:?} {} opcode {:#04x}", variant, info.mnemonic, opcode);
+                if !["JMP", "JSR", "RTS", "RTI", "BRK", "BRA", "STP"].contains(&info.mnemonic) {
+                    assert_eq!(pc, 0x8000 + info.size() as u16, "{:?} {} opcode {:#04x}", variant, info.mnemonic, opcode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn opcode_table_page_cross_matches_executor() {
+        use mos::opcode_table::AddressingMode;
+        for variant in ALL_VARIANTS {
+            for opcode in 0..=255u8 {
+                let info = mos::opcode_table::opcode_info(variant, opcode);
+                match info.mode {
+                    AddressingMode::AbsoluteX | AddressingMode::AbsoluteY | AddressingMode::IndirectY => {
+                        let ((cycles, _), _) = run_opcode(variant, opcode, 0x8000, 0xFF).unwrap();
+                        let expected = (info.cycles + info.page_cross) as i32;
+                        assert_eq!(cycles, expected, "{:?} {} opcode {:#04x}", variant, info.mnemonic, opcode);
+                    }
+                    AddressingMode::Relative | AddressingMode::ZeroPageRelative => {
+                        // placed so that the taken branch lands on the next page
+                        let (_, (cycles, pc)) = run_opcode(variant, opcode, 0x80F0, 0).unwrap();
+                        let taken = if info.mnemonic == "BRA" { 0 } else { 1 };
+                        let expected = (info.cycles + taken + info.page_cross) as i32;
+                        assert_eq!(cycles, expected, "{:?} {} opcode {:#04x}", variant, info.mnemonic, opcode);
+                        assert_eq!(pc & 0xFF00, 0x8100);
+                    }
+                    _ => assert_eq!(info.page_cross, 0, "{:?} {} opcode {:#04x}", variant, info.mnemonic, opcode),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn opcode_table_documented_matches_executor() {
+        for opcode in 0..=255u8 {
+            let info = mos::opcode_table::opcode_info(mos::Variant::Nmos6502, opcode);
+            let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+            let mut mem = mos::build_memory();
+            cpu.undocumented_opcodes = false;
+            cpu.pc = 0x8000;
+            mem.memory[0x8000] = opcode;
+            let illegal = matches!(cpu.step(&mut mem), Err(mos::EmuError::IllegalOpcode { .. }));
+            assert_eq!(illegal, !info.documented, "opcode {:#04x}", opcode);
+        }
+    }
+
+    #[test]
+    fn opcode_table_matches_opcode_constants() {
+        use mos::opcode_table::{find_opcode, AddressingMode};
+        assert_eq!(find_opcode("LDA", AddressingMode::Immediate), Some(mos::CPU::LDA_IMMEDIATE));
+        assert_eq!(find_opcode("nop", AddressingMode::Implied), Some(mos::CPU::NOP_IMPLIED));
+        assert_eq!(find_opcode("NOP", AddressingMode::Immediate), Some(mos::CPU::NOP_IMMEDIATE));
+        assert_eq!(find_opcode("STZ", AddressingMode::AbsoluteX), Some(mos::CPU::STZ_ABSOLUTE_X));
+        assert_eq!(find_opcode("BBS7", AddressingMode::ZeroPageRelative), Some(mos::CPU::BBS7_ZERO_PAGE_RELATIVE));
+        assert_eq!(find_opcode("LAX", AddressingMode::IndirectY), Some(mos::CPU::LAX_INDIRECT_Y));
+        assert_eq!(find_opcode("LDA", AddressingMode::Relative), None);
+        // every opcode decodes back to itself unless it is an alias of an earlier one
+        for variant in ALL_VARIANTS {
+            for opcode in 0..=255u8 {
+                let info = mos::opcode_table::opcode_info(variant, opcode);
+                let found = find_opcode(info.mnemonic, info.mode).unwrap();
+                let alias = mos::opcode_table::opcode_info(variant, found);
+                assert!(found == opcode || (alias.mnemonic == info.mnemonic && alias.mode == info.mode) || !info.documented);
+            }
+        }
+    }
+
+    // every Opcodes constant is named MNEMONIC_MODE after the table entry it stands for
+    macro_rules! opcode_constants {
+        ($($name:ident),* $(,)?) => {
+            vec![$((stringify!($name), mos::CPU::$name)),*]
+        };
+    }
+
+    fn addressing_mode_named(name: &str) -> mos::opcode_table::AddressingMode {
+        use mos::opcode_table::AddressingMode;
+        match name {
+            "IMPLIED" => AddressingMode::Implied,
+            "ACCUMULATOR" => AddressingMode::Accumulator,
+            "IMMEDIATE" => AddressingMode::Immediate,
+            "ZERO_PAGE" => AddressingMode::ZeroPage,
+            "ZERO_PAGE_X" => AddressingMode::ZeroPageX,
+            "ZERO_PAGE_Y" => AddressingMode::ZeroPageY,
+            "ABSOLUTE" => AddressingMode::Absolute,
+            "ABSOLUTE_X" => AddressingMode::AbsoluteX,
+            "ABSOLUTE_Y" => AddressingMode::AbsoluteY,
+            "INDIRECT" => AddressingMode::Indirect,
+            "INDIRECT_X" => AddressingMode::IndirectX,
+            "INDIRECT_Y" => AddressingMode::IndirectY,
+            "ZERO_PAGE_INDIRECT" => AddressingMode::ZeroPageIndirect,
+            "ABSOLUTE_X_INDIRECT" => AddressingMode::AbsoluteXIndirect,
+            "RELATIVE" => AddressingMode::Relative,
+            "ZERO_PAGE_RELATIVE" => AddressingMode::ZeroPageRelative,
+            _ => panic!("no addressing mode called {}", name),
+        }
+    }
+
+    #[test]
+    fn opcode_table_matches_every_opcode_constant() {
+        let constants: Vec<(&str, u8)> = opcode_constants!(
+            LDA_IMMEDIATE, LDA_ZERO_PAGE, LDA_ZERO_PAGE_X, LDA_ABSOLUTE,
+            LDA_ABSOLUTE_X, LDA_ABSOLUTE_Y, LDA_INDIRECT_X, LDA_INDIRECT_Y,
+            LDX_IMMEDIATE, LDX_ZERO_PAGE, LDX_ZERO_PAGE_Y, LDX_ABSOLUTE,
+            LDX_ABSOLUTE_Y, LDY_IMMEDIATE, LDY_ZERO_PAGE, LDY_ZERO_PAGE_X,
+            LDY_ABSOLUTE, LDY_ABSOLUTE_X, STA_ZERO_PAGE, STA_ZERO_PAGE_X,
+            STA_ABSOLUTE, STA_ABSOLUTE_X, STA_ABSOLUTE_Y, STA_INDIRECT_X,
+            STA_INDIRECT_Y, STX_ZERO_PAGE, STX_ZERO_PAGE_Y, STX_ABSOLUTE,
+            STY_ZERO_PAGE, STY_ZERO_PAGE_X, STY_ABSOLUTE, ADC_IMMEDIATE,
+            ADC_ZERO_PAGE, ADC_ZERO_PAGE_X, ADC_ABSOLUTE, ADC_ABSOLUTE_X,
+            ADC_ABSOLUTE_Y, ADC_INDIRECT_X, ADC_INDIRECT_Y, SBC_IMMEDIATE,
+            SBC_ZERO_PAGE, SBC_ZERO_PAGE_X, SBC_ABSOLUTE, SBC_ABSOLUTE_X,
+            SBC_ABSOLUTE_Y, SBC_INDIRECT_X, SBC_INDIRECT_Y, AND_IMMEDIATE,
+            AND_ZERO_PAGE, AND_ZERO_PAGE_X, AND_ABSOLUTE, AND_ABSOLUTE_X,
+            AND_ABSOLUTE_Y, AND_INDIRECT_X, AND_INDIRECT_Y, ORA_IMMEDIATE,
+            ORA_ZERO_PAGE, ORA_ZERO_PAGE_X, ORA_ABSOLUTE, ORA_ABSOLUTE_X,
+            ORA_ABSOLUTE_Y, ORA_INDIRECT_X, ORA_INDIRECT_Y, EOR_IMMEDIATE,
+            EOR_ZERO_PAGE, EOR_ZERO_PAGE_X, EOR_ABSOLUTE, EOR_ABSOLUTE_X,
+            EOR_ABSOLUTE_Y, EOR_INDIRECT_X, EOR_INDIRECT_Y, BIT_ZERO_PAGE,
+            BIT_ABSOLUTE, CMP_IMMEDIATE, CMP_ZERO_PAGE, CMP_ZERO_PAGE_X,
+            CMP_ABSOLUTE, CMP_ABSOLUTE_X, CMP_ABSOLUTE_Y, CMP_INDIRECT_X,
+            CMP_INDIRECT_Y, CPX_IMMEDIATE, CPX_ZERO_PAGE, CPX_ABSOLUTE,
+            CPY_IMMEDIATE, CPY_ZERO_PAGE, CPY_ABSOLUTE, ASL_ACCUMULATOR,
+            ASL_ZERO_PAGE, ASL_ZERO_PAGE_X, ASL_ABSOLUTE, ASL_ABSOLUTE_X,
+            LSR_ACCUMULATOR, LSR_ZERO_PAGE, LSR_ZERO_PAGE_X, LSR_ABSOLUTE,
+            LSR_ABSOLUTE_X, ROL_ACCUMULATOR, ROL_ZERO_PAGE, ROL_ZERO_PAGE_X,
+            ROL_ABSOLUTE, ROL_ABSOLUTE_X, ROR_ACCUMULATOR, ROR_ZERO_PAGE,
+            ROR_ZERO_PAGE_X, ROR_ABSOLUTE, ROR_ABSOLUTE_X, INC_ZERO_PAGE,
+            INC_ZERO_PAGE_X, INC_ABSOLUTE, INC_ABSOLUTE_X, DEC_ZERO_PAGE,
+            DEC_ZERO_PAGE_X, DEC_ABSOLUTE, DEC_ABSOLUTE_X, INX_IMPLIED,
+            INY_IMPLIED, DEX_IMPLIED, DEY_IMPLIED, TAX_IMPLIED,
+            TAY_IMPLIED, TXA_IMPLIED, TYA_IMPLIED, CLC_IMPLIED,
+            SEC_IMPLIED, CLI_IMPLIED, SEI_IMPLIED, CLV_IMPLIED,
+            CLD_IMPLIED, SED_IMPLIED, NOP_IMPLIED, BCC_RELATIVE,
+            BCS_RELATIVE, BEQ_RELATIVE, BMI_RELATIVE, BNE_RELATIVE,
+            BPL_RELATIVE, BVC_RELATIVE, BVS_RELATIVE, JMP_ABSOLUTE,
+            JMP_INDIRECT, BRK_IMPLIED, RTI_IMPLIED, JSR_ABSOLUTE,
+            RTS_IMPLIED, PHA_IMPLIED, PLA_IMPLIED, PHP_IMPLIED,
+            PLP_IMPLIED, TSX_IMPLIED, TXS_IMPLIED, SLO_ZERO_PAGE,
+            SLO_ZERO_PAGE_X, SLO_ABSOLUTE, SLO_ABSOLUTE_X, SLO_ABSOLUTE_Y,
+            SLO_INDIRECT_X, SLO_INDIRECT_Y, RLA_ZERO_PAGE, RLA_ZERO_PAGE_X,
+            RLA_ABSOLUTE, RLA_ABSOLUTE_X, RLA_ABSOLUTE_Y, RLA_INDIRECT_X,
+            RLA_INDIRECT_Y, SRE_ZERO_PAGE, SRE_ZERO_PAGE_X, SRE_ABSOLUTE,
+            SRE_ABSOLUTE_X, SRE_ABSOLUTE_Y, SRE_INDIRECT_X, SRE_INDIRECT_Y,
+            RRA_ZERO_PAGE, RRA_ZERO_PAGE_X, RRA_ABSOLUTE, RRA_ABSOLUTE_X,
+            RRA_ABSOLUTE_Y, RRA_INDIRECT_X, RRA_INDIRECT_Y, DCP_ZERO_PAGE,
+            DCP_ZERO_PAGE_X, DCP_ABSOLUTE, DCP_ABSOLUTE_X, DCP_ABSOLUTE_Y,
+            DCP_INDIRECT_X, DCP_INDIRECT_Y, ISC_ZERO_PAGE, ISC_ZERO_PAGE_X,
+            ISC_ABSOLUTE, ISC_ABSOLUTE_X, ISC_ABSOLUTE_Y, ISC_INDIRECT_X,
+            ISC_INDIRECT_Y, LAX_ZERO_PAGE, LAX_ZERO_PAGE_Y, LAX_ABSOLUTE,
+            LAX_ABSOLUTE_Y, LAX_INDIRECT_X, LAX_INDIRECT_Y, SAX_ZERO_PAGE,
+            SAX_ZERO_PAGE_Y, SAX_ABSOLUTE, SAX_INDIRECT_X, ANC_IMMEDIATE,
+            ALR_IMMEDIATE, ARR_IMMEDIATE, SBX_IMMEDIATE, USBC_IMMEDIATE,
+            XAA_IMMEDIATE, LXA_IMMEDIATE, AHX_ABSOLUTE_Y, AHX_INDIRECT_Y,
+            TAS_ABSOLUTE_Y, SHY_ABSOLUTE_X, SHX_ABSOLUTE_Y, LAS_ABSOLUTE_Y,
+            NOP_IMMEDIATE, NOP_ZERO_PAGE, NOP_ZERO_PAGE_X, NOP_ABSOLUTE,
+            NOP_ABSOLUTE_X, JAM_IMPLIED, BRA_RELATIVE, PHX_IMPLIED,
+            PLX_IMPLIED, PHY_IMPLIED, PLY_IMPLIED, STZ_ZERO_PAGE,
+            STZ_ZERO_PAGE_X, STZ_ABSOLUTE, STZ_ABSOLUTE_X, TRB_ZERO_PAGE,
+            TRB_ABSOLUTE, TSB_ZERO_PAGE, TSB_ABSOLUTE, ORA_ZERO_PAGE_INDIRECT,
+            AND_ZERO_PAGE_INDIRECT, EOR_ZERO_PAGE_INDIRECT, ADC_ZERO_PAGE_INDIRECT, STA_ZERO_PAGE_INDIRECT,
+            LDA_ZERO_PAGE_INDIRECT, CMP_ZERO_PAGE_INDIRECT, SBC_ZERO_PAGE_INDIRECT, INC_ACCUMULATOR,
+            DEC_ACCUMULATOR, BIT_IMMEDIATE, BIT_ZERO_PAGE_X, BIT_ABSOLUTE_X,
+            JMP_ABSOLUTE_X_INDIRECT, RMB0_ZERO_PAGE, RMB1_ZERO_PAGE, RMB2_ZERO_PAGE,
+            RMB3_ZERO_PAGE, RMB4_ZERO_PAGE, RMB5_ZERO_PAGE, RMB6_ZERO_PAGE,
+            RMB7_ZERO_PAGE, SMB0_ZERO_PAGE, SMB1_ZERO_PAGE, SMB2_ZERO_PAGE,
+            SMB3_ZERO_PAGE, SMB4_ZERO_PAGE, SMB5_ZERO_PAGE, SMB6_ZERO_PAGE,
+            SMB7_ZERO_PAGE, BBR0_ZERO_PAGE_RELATIVE, BBR1_ZERO_PAGE_RELATIVE, BBR2_ZERO_PAGE_RELATIVE,
+            BBR3_ZERO_PAGE_RELATIVE, BBR4_ZERO_PAGE_RELATIVE, BBR5_ZERO_PAGE_RELATIVE, BBR6_ZERO_PAGE_RELATIVE,
+            BBR7_ZERO_PAGE_RELATIVE, BBS0_ZERO_PAGE_RELATIVE, BBS1_ZERO_PAGE_RELATIVE, BBS2_ZERO_PAGE_RELATIVE,
+            BBS3_ZERO_PAGE_RELATIVE, BBS4_ZERO_PAGE_RELATIVE, BBS5_ZERO_PAGE_RELATIVE, BBS6_ZERO_PAGE_RELATIVE,
+            BBS7_ZERO_PAGE_RELATIVE, WAI_IMPLIED, STP_IMPLIED,
+        );
+        for (name, opcode) in constants {
+            let (mnemonic, mode) = name.split_once('_').unwrap();
+            let mode = addressing_mode_named(mode);
+            // undocumented and Rockwell opcodes only decode as such on their own variants
+            let decoded = ALL_VARIANTS.iter().any(|variant| {
+                let info = mos::opcode_table::opcode_info(*variant, opcode);
+                info.mnemonic == mnemonic && info.mode == mode
+            });
+            assert!(decoded, "{} = {:#04x} is not {} {:?} in the opcode table", name, opcode, mnemonic, mode);
+        }
+    }
+
+    /* TEST SUITES */
+
+    // reads tests/fixtures/<name>, a missing fixture fails the test
+    fn read_fixture(name: &str) -> Vec<u8> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+        std::fs::read(&path).unwrap_or_else(|err| {
+            panic!("fixture {} missing ({}), see tests/fixtures/README.md", path.display(), err);
+        })
+    }
+
+    // loads a suite image, runs it from `start` until it traps and returns the trap address
+    // together with the number of the test in progress (kept at 0x0200 by Dormann's suites)
+    fn run_trap_suite(variant: mos::Variant, image: &[u8], load: u16, start: u16) -> (u16, u8, mos::MEMORY) {
+        let mut cpu = mos::build_cpu(variant);
+        let mut mem = mos::build_memory();
+        mem.memory[load as usize..load as usize + image.len()].copy_from_slice(image);
+        cpu.pc = start;
+        let trap = cpu.run_until_trap(&mut mem, 200_000_000).unwrap_or_else(|err| {
+            panic!("suite stopped at {:#06x} in test {:#04x}: {}", cpu.pc, mem.memory[0x0200], err);
+        });
+        let test_case = mem.memory[0x0200];
+        (trap, test_case, mem)
+    }
+
+    // the stock 6502_decimal_test.a65 ends in `db $db` (STP on the WDC parts) instead of a
+    // JMP * trap, and on the NMOS 6502 $DB is an undocumented DCP that keeps running. Steps
+    // until the opcode at pc is $DB, STP stops the cpu or an instruction jumps to itself
+    fn run_decimal_suite(variant: mos::Variant, image: &[u8], load: u16, start: u16) -> (u16, mos::MEMORY) {
+        let mut cpu = mos::build_cpu(variant);
+        let mut mem = mos::build_memory();
+        mem.memory[load as usize..load as usize + image.len()].copy_from_slice(image);
+        cpu.pc = start;
+        let mut total_cycles: u64 = 0;
+        while total_cycles < 200_000_000 {
+            let address = cpu.pc;
+            if mem.memory[address as usize] == mos::CPU::STP_IMPLIED {
+                return (address, mem);
+            }
+            match cpu.step(&mut mem) {
+                Ok(cycles) => total_cycles += cycles as u64,
+                Err(mos::EmuError::Stopped { address }) => return (address, mem),
+                Err(err) => panic!("decimal suite stopped at {:#06x}: {}", cpu.pc, err),
+            }
+            if cpu.pc == address {
+                return (address, mem);
+            }
+        }
+        panic!("decimal suite still running at {:#06x} after 200000000 cycles", cpu.pc);
+    }
+
+    #[test]
+    fn run_until_trap_jmp_self() {
+        let source = "
+            * = $0400
+            LDX #10
+    @loop:  DEX
+            BNE @loop
+    done:   JMP done
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        let (trap, _, _) = run_trap_suite(mos::Variant::Nmos6502, &assembly.image(), 0x0400, 0x0400);
+        assert_eq!(Some(trap), assembly.symbol("done"));
+    }
+
+    #[test]
+    fn run_decimal_suite_stops_on_stp_byte() {
+        let source = "
+    error = $0B
+            * = $0200
+            LDA #0
+            STA error
+    done:   .byte $DB
+            JMP done
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        for variant in [mos::Variant::Nmos6502, mos::Variant::Wdc65C02] {
+            let (end, mem) = run_decimal_suite(variant, &assembly.image(), 0x0200, 0x0200);
+            assert_eq!((Some(end), mem.memory[0x000B]), (assembly.symbol("done"), 0));
+        }
+    }
+
+    #[test]
+    fn run_until_trap_reports_failing_test_case() {
+        // the shape of a Dormann suite: test numbers at 0x0200 and a "BNE *" trap on failure
+        let source = "
+    test_case = $0200
+            * = $0400
+            LDA #1
+            STA test_case
+            LDA #2
+            CMP #2
+            BNE *
+            INC test_case
+            LDA #3
+            CMP #4
+    fail:   BNE fail
+    success: JMP success
+        ";
+        let assembly = mos::asm::assemble(source).unwrap();
+        let (trap, test_case, _) = run_trap_suite(mos::Variant::Nmos6502, &assembly.image(), 0x0400, 0x0400);
+        assert_eq!((Some(trap), test_case), (assembly.symbol("fail"), 2));
+    }
+
+    #[test]
+    fn run_until_trap_budget() {
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        let mut mem = mos::build_memory();
+        cpu.pc = 0x0000;
+        // NOP / JMP $0000 loops forever without a single instruction jumping to itself
+        mem.memory[0x0000] = mos::CPU::NOP_IMPLIED;
+        mem.memory[0x0001] = mos::CPU::JMP_ABSOLUTE;
+        assert_eq!(cpu.run_until_trap(&mut mem, 1000), Err(mos::EmuError::CycleBudgetExhausted { cycles: 1000 }));
+    }
+
+    #[test]
+    #[ignore = "needs the Dormann binaries in tests/fixtures, run with --ignored"]
+    fn dormann_6502_functional_test() {
+        let image = read_fixture("6502_functional_test.bin");
+        let (trap, test_case, _) = run_trap_suite(mos::Variant::Nmos6502, &image, 0x0000, 0x0400);
+        assert_eq!(trap, 0x3469, "trapped at {:#06x} in test {:#04x}", trap, test_case);
+    }
+
+    #[test]
+    #[ignore = "needs the Dormann binaries in tests/fixtures, run with --ignored"]
+    fn dormann_65C02_extended_opcodes_test() {
+        let image = read_fixture("65C02_extended_opcodes_test.bin");
+        let (trap, test_case, _) = run_trap_suite(mos::Variant::Wdc65C02, &image, 0x0000, 0x0400);
+        assert_eq!(trap, 0x24F1, "trapped at {:#06x} in test {:#04x}", trap, test_case);
+    }
+
+    #[test]
+    #[ignore = "needs the Dormann binaries in tests/fixtures, run with --ignored"]
+    fn dormann_6502_decimal_test() {
+        let image = read_fixture("6502_decimal_test.bin");
+        let (end, mem) = run_decimal_suite(mos::Variant::Nmos6502, &image, 0x0200, 0x0200);
+        assert_eq!(mem.memory[0x000B], 0, "decimal test failed, ended at {:#06x}", end);
+    }
+
+
+    /* SINGLE STEP TESTS */
+
+    // runs one ProcessorTests / SingleStepTests case: loads the initial state, steps a single
+    // instruction and compares registers, RAM and every bus cycle with the final state
+    fn run_single_step_case(variant: mos::Variant, case: &serde_json::Value) -> Result<(), String> {
+        let field = |state: &serde_json::Value, key: &str| state[key].as_u64().unwrap_or(0);
+        let initial: &serde_json::Value = &case["initial"];
+        let expected: &serde_json::Value = &case["final"];
+
+        let mut cpu = mos::build_cpu(variant);
+        let mut bus = build_log_bus();
+        cpu.cycle_accurate = true;
+        cpu.pc = field(initial, "pc") as u16;
+        cpu.sp = field(initial, "s") as u8;
+        cpu.r_a = field(initial, "a") as u8;
+        cpu.r_x = field(initial, "x") as u8;
+        cpu.r_y = field(initial, "y") as u8;
+        cpu.set_processor_status(field(initial, "p") as u8);
+        for entry in initial["ram"].as_array().into_iter().flatten() {
+            bus.mem.memory[entry[0].as_u64().unwrap_or(0) as usize] = entry[1].as_u64().unwrap_or(0) as u8;
+        }
+
+        let cycles: Vec<(&str, u16, u8)> = case["cycles"].as_array().into_iter().flatten()
+            .map(|cycle| (cycle[2].as_str().unwrap_or(""), cycle[0].as_u64().unwrap_or(0) as u16, cycle[1].as_u64().unwrap_or(0) as u8))
+            .collect();
+
+        match cpu.step(&mut bus) {
+            Ok(_) => {}
+            // the vectors go on with the bus activity of the locked up cpu, which is not
+            // emulated, so only the cycles up to the jam are compared
+            Err(mos::EmuError::Jammed { .. }) => {
+                if bus.log.is_empty() || !cycles.starts_with(&bus.log) {
+                    return Err(format!("bus cycles up to the jam {:x?}, expected {:x?}", bus.log, cycles));
+                }
+                return Ok(());
+            }
+            Err(err) => return Err(format!("step failed: {}", err)),
+        }
+
+        // B and bit 5 only exist on the stack, mask them out of the status
+        let registers: [(&str, u64); 6] = [
+            ("pc", cpu.pc as u64),
+            ("s", cpu.sp as u64),
+            ("a", cpu.r_a as u64),
+            ("x", cpu.r_x as u64),
+            ("y", cpu.r_y as u64),
+            ("p", (cpu.processor_status() & 0xCF) as u64),
+        ];
+        for (name, value) in registers {
+            let mut want: u64 = field(expected, name);
+            if name == "p" {
+                want &= 0xCF;
+            }
+            if value != want {
+                return Err(format!("{} is {:#x}, expected {:#x}", name, value, want));
+            }
+        }
+
+        for entry in expected["ram"].as_array().into_iter().flatten() {
+            let address: usize = entry[0].as_u64().unwrap_or(0) as usize;
+            let want: u8 = entry[1].as_u64().unwrap_or(0) as u8;
+            if bus.mem.memory[address] != want {
+                return Err(format!("ram {:#06x} is {:#04x}, expected {:#04x}", address, bus.mem.memory[address], want));
+            }
+        }
+
+        if bus.log != cycles {
+            return Err(format!("bus cycles {:x?}, expected {:x?}", bus.log, cycles));
+        }
+        Ok(())
+    }
+
+    // runs every case of a JSON file, returns "name: difference" for each failing case
+    fn run_single_step_file(variant: mos::Variant, path: &std::path::Path) -> Vec<String> {
+        let text: String = std::fs::read_to_string(path).unwrap();
+        let cases: serde_json::Value = serde_json::from_str(&text)
+            .unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+        let mut failures: Vec<String> = Vec::new();
+        for case in cases.as_array().into_iter().flatten() {
+            if let Err(message) = run_single_step_case(variant, case) {
+                failures.push(format!("{} [{}]: {}", path.display(), case["name"].as_str().unwrap_or("?"), message));
+            }
+        }
+        failures
+    }
+
+    // runs all *.json files of tests/fixtures/single_step/<directory>, fails when there are none
+    fn run_single_step_directory(variant: mos::Variant, directory: &str) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/single_step").join(directory);
+        let mut files: Vec<std::path::PathBuf> = match std::fs::read_dir(&path) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|extension| extension == "json"))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        assert!(!files.is_empty(), "no single step tests in {}, see tests/fixtures/README.md", path.display());
+        files.sort();
+
+        let failures: Vec<String> = files.iter().flat_map(|file| run_single_step_file(variant, file)).collect();
+        assert!(failures.is_empty(), "{} failing cases, first ones:\n{}", failures.len(), failures[..failures.len().min(20)].join("\n"));
+    }
+
+    #[test]
+    fn single_step_sample() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/single_step/sample.json");
+        let failures: Vec<String> = run_single_step_file(mos::Variant::Nmos6502, &path);
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn single_step_reports_differences() {
+        let case: serde_json::Value = serde_json::from_str(r#"{
+            "name": "a9 42",
+            "initial": { "pc": 4096, "s": 253, "a": 0, "x": 0, "y": 0, "p": 36, "ram": [[4096, 169], [4097, 66]] },
+            "final": { "pc": 4098, "s": 253, "a": 67, "x": 0, "y": 0, "p": 36, "ram": [[4096, 169], [4097, 66]] },
+            "cycles": [[4096, 169, "read"], [4097, 66, "read"]]
+        }"#).unwrap();
+        let result: Result<(), String> = run_single_step_case(mos::Variant::Nmos6502, &case);
+        assert_eq!(result, Err("a is 0x42, expected 0x43".to_string()));
+    }
+
+    #[test]
+    fn single_step_jam_compares_cycles_up_to_the_jam() {
+        let jam = r#"{
+            "name": "02 jam",
+            "initial": { "pc": 4096, "s": 253, "a": 0, "x": 0, "y": 0, "p": 36, "ram": [[4096, 2], [4097, 0]] },
+            "final": { "pc": 4097, "s": 253, "a": 0, "x": 0, "y": 0, "p": 36, "ram": [[4096, 2], [4097, 0]] },
+            "cycles": [[4096, 2, "read"], [4097, 0, "read"], [65535, 0, "read"], [65534, 0, "read"]]
+        }"#;
+        let case: serde_json::Value = serde_json::from_str(jam).unwrap();
+        assert_eq!(run_single_step_case(mos::Variant::Nmos6502, &case), Ok(()));
+        // the opcode fetch still has to match
+        let case: serde_json::Value = serde_json::from_str(&jam.replace("[[4096, 2, \"read\"]", "[[4096, 2, \"write\"]")).unwrap();
+        assert!(run_single_step_case(mos::Variant::Nmos6502, &case).is_err());
+    }
+
+    #[test]
+    #[ignore = "needs the SingleStepTests vectors in tests/fixtures/single_step, run with --ignored"]
+    fn single_step_nmos6502() {
+        run_single_step_directory(mos::Variant::Nmos6502, "6502");
+    }
+
+    #[test]
+    #[ignore = "needs the SingleStepTests vectors in tests/fixtures/single_step, run with --ignored"]
+    fn single_step_rockwell65c02() {
+        run_single_step_directory(mos::Variant::Rockwell65C02, "rockwell65c02");
+    }
+
+    #[test]
+    #[ignore = "needs the SingleStepTests vectors in tests/fixtures/single_step, run with --ignored"]
+    fn single_step_wdc65c02() {
+        run_single_step_directory(mos::Variant::Wdc65C02, "wdc65c02");
+    }
+
+    /* TRACE */
+
+    // the first lines of nestest.log with the PPU column cut out
+    #[test]
+    fn trace_matches_nestest_log() {
+        let mut mem = mos::build_memory();
+        let source = "
+            * = $C000
+            JMP $C5F5
+            * = $C5F5
+            LDX #$00
+            STX $00
+            STX $10
+            STX $11
+            JSR $C72D
+            * = $C72D
+            NOP
+        ";
+        mos::asm::assemble(source).unwrap().load(&mut mem);
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        cpu.pc = 0xC000;
+        cpu.sp = 0xFD;
+        cpu.set_processor_status(0x24);
+        let mut tracer = mos::trace::build_tracer(mos::Variant::Nmos6502, Vec::new());
+        tracer.cycles = 7;
+        let cycles = tracer.run(&mut cpu, &mut mem, 7).unwrap();
+        let expected = "\
+C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD CYC:7
+C5F5  A2 00     LDX #$00                        A:00 X:00 Y:00 P:24 SP:FD CYC:10
+C5F7  86 00     STX $00 = 00                    A:00 X:00 Y:00 P:26 SP:FD CYC:12
+C5F9  86 10     STX $10 = 00                    A:00 X:00 Y:00 P:26 SP:FD CYC:15
+C5FB  86 11     STX $11 = 00                    A:00 X:00 Y:00 P:26 SP:FD CYC:18
+C5FD  20 2D C7  JSR $C72D                       A:00 X:00 Y:00 P:26 SP:FD CYC:21
+C72D  EA        NOP                             A:00 X:00 Y:00 P:26 SP:FB CYC:27
+";
+        assert_eq!(String::from_utf8(tracer.output).unwrap(), expected);
+        assert!(cycles == 22 && tracer.cycles == 29);
+    }
+
+    #[test]
+    fn trace_effective_addresses() {
+        let mut mem = mos::build_memory();
+        let program = [
+            0xB5, 0xF0, 0xBD, 0xF0, 0x02, 0xA1, 0x7E, 0xB1, 0x89, 0x6C, 0xFF, 0x02, 0x04, 0xA9, 0x4A,
+        ];
+        mem.memory[0x8000..0x8000 + program.len()].copy_from_slice(&program);
+        mem.memory[0x0080] = 0x00;
+        mem.memory[0x0081] = 0x02;
+        mem.memory[0x0200] = 0x5A;
+        mem.memory[0x0089] = 0x00;
+        mem.memory[0x008A] = 0x03;
+        mem.memory[0x02FF] = 0x00;
+        mem.memory[0x0300] = 0x89;
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        cpu.r_x = 0x02;
+        cpu.r_y = 0x00;
+        let tracer = mos::trace::build_tracer(mos::Variant::Nmos6502, std::io::sink());
+        let mut disasm_column = |address: u16| {
+            cpu.pc = address;
+            tracer.trace_line(&cpu, &mem)[15..48].trim_end().to_string()
+        };
+        assert_eq!(disasm_column(0x8000), " LDA $F0,X @ F2 = 00");
+        assert_eq!(disasm_column(0x8002), " LDA $02F0,X @ 02F2 = 00");
+        assert_eq!(disasm_column(0x8005), " LDA ($7E,X) @ 80 = 0200 = 5A");
+        assert_eq!(disasm_column(0x8007), " LDA ($89),Y = 0300 @ 0300 = 89");
+        // the NMOS pointer fetch wraps inside the page: $02FF and $0200
+        assert_eq!(disasm_column(0x8009), " JMP ($02FF) = 5A00");
+        assert_eq!(disasm_column(0x800C), "*NOP $A9 = 00");
+    }
+
+    // the illegal opcodes carry nestest's names: *ISB for ISC and *SBC for USBC (0xEB)
+    #[test]
+    fn trace_illegal_opcodes_use_nestest_names() {
+        let mut mem = mos::build_memory();
+        let program = [0xE3, 0x45, 0xEB, 0x40];
+        mem.memory[0xC000..0xC000 + program.len()].copy_from_slice(&program);
+        mem.memory[0x0047] = 0x47;
+        mem.memory[0x0048] = 0x06;
+        mem.memory[0x0647] = 0xEB;
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        cpu.pc = 0xC000;
+        cpu.sp = 0xFD;
+        cpu.r_x = 0x02;
+        cpu.set_processor_status(0x24);
+        let mut tracer = mos::trace::build_tracer(mos::Variant::Nmos6502, Vec::new());
+        tracer.cycles = 7;
+        tracer.run(&mut cpu, &mut mem, 2).unwrap();
+        let expected = concat!(
+            "C000  E3 45    *ISB ($45,X) @ 47 = 0647 = EB    A:00 X:02 Y:00 P:24 SP:FD CYC:7\n",
+            "C002  EB 40    *SBC #$40                        A:13 X:02 Y:00 P:24 SP:FD CYC:15\n",
+        );
+        assert_eq!(String::from_utf8(tracer.output).unwrap(), expected);
+    }
+
+    /* BREAKPOINTS */
+
+    // assembles a program at $0400 for the breakpoint tests
+    fn debug_program(source: &str) -> (mos::CPU, mos::MEMORY, mos::asm::Assembly) {
+        let mut mem = mos::build_memory();
+        let assembly = mos::asm::assemble(source).unwrap();
+        assembly.load(&mut mem);
+        let mut cpu = mos::build_cpu(mos::Variant::Nmos6502);
+        cpu.pc = assembly.origin();
+        (cpu, mem, assembly)
+    }
+
+    #[test]
+    fn break_on_pc_and_continue() {
+        let (mut cpu, mut mem, assembly) = debug_program("
+            * = $0400
+            LDX #3
+    loop:   DEX
+            BNE loop
+    done:   JMP done
+        ");
+        let loop_address = assembly.symbol("loop").unwrap();
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.add_breakpoint(loop_address);
+        let mut hits: Vec<u8> = Vec::new();
+        for _ in 0..3 {
+            let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+            assert_eq!(reason, mos::debug::StopReason::Breakpoint { address: loop_address });
+            hits.push(cpu.r_x);
+        }
+        assert_eq!(hits, vec![3, 2, 1]);
+
+        // the fourth pass runs into the trap loop until the budget is gone
+        let result = cpu.run_until_break(&mut mem, &breakpoints, 1000);
+        assert_eq!(result, Err(mos::EmuError::CycleBudgetExhausted { cycles: 1000 }));
+        assert_eq!(Some(cpu.pc), assembly.symbol("done"));
+    }
+
+    #[test]
+    fn break_on_first_instruction_is_stepped_over() {
+        let (mut cpu, mut mem, _) = debug_program("
+            * = $0400
+            NOP
+            NOP
+            JMP $0400
+        ");
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.add_breakpoint(0x0400);
+        let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        assert_eq!(reason, mos::debug::StopReason::Breakpoint { address: 0x0400 });
+        assert!(breakpoints.remove_breakpoint(0x0400) && !breakpoints.remove_breakpoint(0x0400));
+    }
+
+    #[test]
+    fn break_on_write_watchpoint() {
+        let (mut cpu, mut mem, _) = debug_program("
+            * = $0400
+            LDA $2000
+            LDA #$42
+            STA $2001
+            JMP *
+        ");
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.watch(0x2000, 0x20FF, false, true);
+        let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        let cycle = mos::BusCycle { address: 0x2001, value: 0x42, access: mos::Access::Write };
+        assert_eq!(reason, mos::debug::StopReason::Watchpoint { pc: 0x0405, cycle });
+        // the store has completed
+        assert!(cpu.pc == 0x0408 && mem.memory[0x2001] == 0x42);
+    }
+
+    #[test]
+    fn break_on_read_watchpoint() {
+        let (mut cpu, mut mem, _) = debug_program("
+            * = $0400
+            STA $10
+            LDX $10
+            JMP *
+        ");
+        mem.memory[0x0010] = 0x99;
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.watch(0x0010, 0x0010, true, false);
+        let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        let cycle = mos::BusCycle { address: 0x0010, value: 0x00, access: mos::Access::Read };
+        assert_eq!(reason, mos::debug::StopReason::Watchpoint { pc: 0x0402, cycle });
+    }
+
+    #[test]
+    fn break_on_brk_and_illegal_opcodes() {
+        let (mut cpu, mut mem, _) = debug_program("
+            * = $0400
+            NOP
+            .byte $A7, $10
+            NOP
+            BRK
+        ");
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.illegal_opcodes = true;
+        breakpoints.break_on_opcode(mos::CPU::BRK_IMPLIED);
+        let first = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        assert_eq!(first, mos::debug::StopReason::Opcode { address: 0x0401, opcode: 0xA7 });
+        let second = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        assert_eq!(second, mos::debug::StopReason::Opcode { address: 0x0404, opcode: mos::CPU::BRK_IMPLIED });
+    }
+
+    #[test]
+    fn break_on_opcode_waits_for_pending_interrupt() {
+        let (mut cpu, mut mem, _) = debug_program("
+            * = $0400
+            CLI
+            NOP
+            * = $0500
+            LDA #0
+            NOP
+            RTI
+        ");
+        mem.memory[0xFFFE] = 0x00;
+        mem.memory[0xFFFF] = 0x05;
+        cpu.ps_interrupt = 1;
+        cpu.irq_line = true;
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.break_on_opcode(mos::CPU::NOP_IMPLIED);
+        // after CLI the IRQ is taken before the NOP at $0401, the NOP in the handler stops
+        let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        assert_eq!(reason, mos::debug::StopReason::Opcode { address: 0x0502, opcode: mos::CPU::NOP_IMPLIED });
+    }
+
+    #[test]
+    fn break_on_register_and_flag_conditions() {
+        let (mut cpu, mut mem, _) = debug_program("
+            * = $0400
+            LDA #$FE
+    loop:   CLC
+            ADC #1
+            JMP loop
+        ");
+        let mut breakpoints = mos::debug::build_breakpoints();
+        breakpoints.add_condition(mos::debug::Condition {
+            register: mos::debug::Register::Carry,
+            comparison: mos::debug::Comparison::Equal,
+            value: 1,
+        });
+        breakpoints.add_condition(mos::debug::Condition {
+            register: mos::debug::Register::A,
+            comparison: mos::debug::Comparison::GreaterOrEqual,
+            value: 0xFF,
+        });
+        let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        assert_eq!(reason, mos::debug::StopReason::Condition { address: 0x0405, index: 1 });
+        assert_eq!(cpu.r_a, 0xFF);
+        breakpoints.conditions.remove(1);
+        let reason = cpu.run_until_break(&mut mem, &breakpoints, 1000).unwrap();
+        assert_eq!(reason, mos::debug::StopReason::Condition { address: 0x0405, index: 0 });
+        assert!(cpu.r_a == 0x00 && cpu.ps_carry == 1);
+    }
+
+    /* MONITOR */
+
+    // feeds lines to a fresh monitor and returns the output of the last one
+    fn monitor_session(monitor: &mut mos::monitor::Monitor, lines: &[&str]) -> String {
+        let mut output: String = String::new();
+        for line in lines {
+            output = monitor.command(line);
+        }
+        output
+    }
+
+    #[test]
+    fn monitor_deposit_and_examine() {
+        let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+        let output = monitor_session(&mut monitor, &["0300: 48 49 00", "> 0303 21", "0300.0303"]);
+        assert_eq!(output, format!("0300: {:<47}  HI.!\n", "48 49 00 21"));
+        assert_eq!(monitor.command("m 0302 0302"), format!("0302: {:<47}  .\n", "00"));
+        assert_eq!(monitor.command("0300: 100"), "? 100 does not fit in a byte\n");
+        assert_eq!(monitor.command("bogus"), "? unknown command bogus\n");
+    }
+
+    #[test]
+    fn monitor_assemble_and_disassemble() {
+        let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+        monitor.command("a 0400 LDX #$03");
+        monitor.command("a 0402");
+        assert_eq!(monitor.prompt(), ".0402 ");
+        monitor_session(&mut monitor, &["DEX", "BNE $0402", "JMP *"]);
+        assert_eq!(monitor.prompt(), ".0408 ");
+        monitor.command("");
+        assert_eq!(monitor.prompt(), "> ");
+        let listing = monitor.command("d 0400 0405");
+        let expected = concat!(
+            " 0400  A2 03     LDX #$03\n",
+            " 0402  CA        DEX\n",
+            " 0403  D0 FD     BNE $0402\n",
+            " 0405  4C 05 04  JMP $0405\n",
+        );
+        assert_eq!(listing, expected);
+        assert!(monitor.command("a 0400 LDQ #1").starts_with("? "));
+    }
+
+    #[test]
+    fn monitor_registers_and_flags() {
+        let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+        let output = monitor.command("r pc=C000 a=$80 x=1 y=2 sp=FD n=1 c=1");
+        assert_eq!(output, "PC:C000 A:80 X:01 Y:02 SP:FD P:A1 NV-BDIZC:10100001\n");
+        monitor.command("r p=00");
+        assert!(monitor.cpu.ps_negative == 0 && monitor.cpu.ps_carry == 0);
+        assert_eq!(monitor.command("r a=100"), "? 100 does not fit in a byte\n");
+        assert_eq!(monitor.command("r q=1"), "? unknown register q\n");
+    }
+
+    #[test]
+    fn monitor_step_next_and_go() {
+        let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+        monitor_session(&mut monitor, &[
+            "a 0400", "LDA #$42", "JSR $0410", "STA $10", "JMP *", "",
+            "a 0410", "INX", "INX", "RTS", "",
+            "r pc=0400",
+        ]);
+        let step = monitor.command("z");
+        assert_eq!(step, " 0402  20 10 04  JSR $0410\nPC:0402 A:42 X:00 Y:00 SP:FF P:20 NV-BDIZC:00100000\n");
+        // next runs the whole subroutine
+        monitor.command("n");
+        assert!(monitor.cpu.pc == 0x0405 && monitor.cpu.r_x == 2);
+
+        monitor.command("break 0405");
+        let go = monitor.command("g 0400");
+        assert!(go.starts_with("break at 0405\n*0405  85 10     STA $10\n"), "{}", go);
+        assert_eq!(monitor.cpu.r_x, 4);
+        monitor.command("delete 0405");
+        assert_eq!(monitor.command("delete 0405"), "? no breakpoint at 0405\n");
+    }
+
+    #[test]
+    fn monitor_watch_conditions_and_listing() {
+        let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+        monitor_session(&mut monitor, &["a 0400", "INX", "STX $2000", "JMP $0400", "", "r pc=0400"]);
+        monitor.command("watch w 2000 20FF");
+        let watch = monitor.command("g");
+        assert!(watch.starts_with("watch: write 01 to 2000 at 0401\n"), "{}", watch);
+        monitor.command("delete");
+        monitor.command("cond x == 5");
+        let condition = monitor.command("g");
+        assert!(condition.starts_with("condition x == 05 at 0401\n"), "{}", condition);
+        monitor_session(&mut monitor, &["break 0400", "break op 00", "break illegal", "watch r 10"]);
+        assert_eq!(monitor.command("break"), "break 0400\nbreak op 00\nbreak illegal\nwatch r 0010 0010\ncond x == 05\n");
+    }
+
+    #[test]
+    fn monitor_load_and_save() {
+        let path = std::env::temp_dir().join(format!("rust6502_monitor_{}.bin", process::id()));
+        let file: String = path.display().to_string();
+        let mut monitor = mos::monitor::build_monitor(mos::Variant::Nmos6502);
+        monitor.command("1000: DE AD BE EF");
+        assert_eq!(monitor.command(&format!("s {} 1000 1003", file)), "saved 1000-1003\n");
+        assert_eq!(std::fs::read(&path).unwrap(), vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(monitor.command(&format!("l {} 2000", file)), "loaded 2000-2003\n");
+        assert_eq!(monitor.mem.memory[0x2000..0x2004], [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(monitor.command(&format!("l {} FFFE", file)), format!("? {} does not fit at FFFE\n", file));
+        std::fs::remove_file(&path).unwrap();
+        assert!(monitor.command(&format!("l {} 2000", file)).starts_with("? cannot read"));
+        monitor.command("x");
+        assert!(!monitor.running);
+    }
+
+}